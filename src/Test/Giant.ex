@@ -2,12 +2,12 @@
 // GIANT DEEP LEVEL ACCESS TESTS (CORRECTED)
 // ============================================
 
-// Test 25: Ultra deep mixed nesting (dict -> array -> dict -> axis -> dict -> array -> dict)
+// Test 25: Ultra deep mixed nesting (dict -> array -> dict -> array -> dict -> array -> dict)
 kprint "=== Giant Test 1: Ultra Deep Mixed Nesting ==="
 giant1 = [&d,
     "level1": [&l,
         [&d,
-            "level2": [&a,
+            "level2": [&l,
                 [&d,
                     "level3": [&l,
                         [&d,
@@ -43,7 +43,7 @@ giant2 = [&d,
     keySum: [&d,
         "inner": [&l,
             [&d,
-                keyMul: [&a,
+                keyMul: [&l,
                     "zero",
                     [&d, "final": "SUM_MUL_OK"],
                     "two"
@@ -77,11 +77,11 @@ kprint giant3[1]["path"][0][0]["go"][-1]["ok"]
 kprint giant3[1]["path"][0][0]["axis"][-2]
 
 
-// Test 28: Axis -> array -> axis -> dict -> array (very mixed)
-kprint "=== Giant Test 4: Axis -> Array -> Axis -> Dict -> Array ==="
-giant4 = [&a,
+// Test 28: array -> array -> array -> dict -> array (very mixed)
+kprint "=== Giant Test 4: Array -> Array -> Array -> Dict -> Array ==="
+giant4 = [&l,
     [&l,
-        [&a,
+        [&l,
             [&d,
                 "payload": [&l,
                     [&d, "value": "AXIS_MIX_OK"],
@@ -169,12 +169,12 @@ kprint giantCompany["meta"]["regions"][0]["countries"][0]["cities"][0]["offices"
 kprint giantCompany["meta"]["regions"][0]["countries"][0]["cities"][0]["offices"][0]["teams"][0]["stack"]["build"]["pipeline"][3]["status"]
 
 
-// Test 31: Edge but deep: dict -> dict -> array -> axis -> dict -> dict -> array -> dict
+// Test 31: Edge but deep: dict -> dict -> array -> array -> dict -> dict -> array -> dict
 kprint "=== Giant Test 7: Edge Deep Mixed ==="
 giantEdge = [&d,
     "a": [&d,
         "b": [&l,
-            [&a,
+            [&l,
                 [&d,
                     "c": [&d,
                         "d": [&l,
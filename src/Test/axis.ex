@@ -0,0 +1,39 @@
+// ============================================
+// AXIS TESTS
+// ============================================
+
+kprint "=== Axis Test 1: Shape ==="
+v = [&a, 1, 2, 3]
+m = [&a, [&a, 1, 2, 3], [&a, 4, 5, 6]]
+print(src=axis_shape(src=m))
+print(src=m)
+kprint m[1][2]
+
+kprint "=== Axis Test 2: Element-wise And Broadcasting ==="
+print(src=v + v)
+print(src=v * 2)
+print(src=m - v)
+print(src=m / 2)
+print(src=-v)
+
+kprint "=== Axis Test 3: Products ==="
+kprint axis_dot(x=v, y=v)
+print(src=axis_matmul(x=m, y=axis_transpose(src=m)))
+print(src=axis_dot(x=m, y=v))
+
+kprint "=== Axis Test 4: Reshape And Slice ==="
+print(src=axis_reshape(src=m, shape=[&l, 3, -1]))
+print(src=axis_slice(src=m, start=1, end=3, dim=1))
+print(src=axis_slice(src=m, start=0, end=1))
+
+kprint "=== Axis Test 5: Reductions ==="
+kprint axis_sum(src=m)
+print(src=axis_sum(src=m, dim=0))
+print(src=axis_mean(src=m, dim=1))
+kprint axis_min(src=m)
+print(src=axis_max(src=m, dim=-1))
+
+kprint "=== Axis Test 6: Exact Integers ==="
+print(src=[&a, 9007199254740992, -9007199254740992])
+// Past 2^53 an f64 would round the value, so it is rejected
+big = [&a, 9007199254740993]
@@ -0,0 +1,36 @@
+// _lock_, _unlock_, _const_ and _kill_ act on the binding a name resolves to
+
+total = 10
+for i _in_ ::[0..3] {
+    if i == 1 {
+        _lock_ total
+    }
+}
+kprint total
+_unlock_ total
+total = 11
+kprint total
+
+limit = 3
+label visit[] freeze() {
+    _const_ limit
+}
+freeze()
+
+counter = 1
+label visit[] drop() {
+    _kill_ counter
+}
+drop()
+_revive_ counter
+kprint counter
+counter = 5
+kprint counter
+
+name = "x"
+_lock_ name
+_const_ name
+kprint "locked variable made constant"
+
+// limit was made constant inside freeze()
+limit = 4
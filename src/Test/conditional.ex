@@ -0,0 +1,60 @@
+// ============================================
+// CONDITIONAL COMPILATION TESTS
+// Run with `./src/Test/conditional.ex -D MODE=fast -D VERBOSE` to take the
+// other branches.
+// ============================================
+
+kprint "=== Conditional Test 1: Std Vars ==="
+_ifdef_ __OS__
+    kprint "os is known"
+_endif_
+_ifdef_ __LANG__ == "EX"
+    kprint "lang is EX"
+_else_
+    kprint "unknown lang"
+_endif_
+_ifndef_ __NOT_A_VAR__
+    kprint "not defined"
+_endif_
+
+kprint "=== Conditional Test 2: Command Line Defines ==="
+_ifdef_ MODE == "fast"
+    level = 3
+_elif_ MODE == "safe"
+    level = 1
+_elif_ VERBOSE
+    level = 2
+_else_
+    level = 0
+_endif_
+kprint level
+
+kprint "=== Conditional Test 3: Nesting And Labels ==="
+label visit[] report() {
+    _ifdef_ VERBOSE
+        _ifdef_ MODE != "fast"
+            kprint "verbose"
+        _else_
+            kprint "verbose and fast"
+        _endif_
+    _else_
+        kprint "quiet"
+    _endif_
+}
+report()
+
+kprint "=== Conditional Test 4: Macros ==="
+_ifndef_ SQUARE
+    _define_ SQUARE(v) [
+        v * v
+    ]
+_endif_
+_ifdef_ SQUARE
+    kprint #SQUARE(7)
+_endif_
+_undef_ SQUARE
+_ifdef_ SQUARE
+    kprint "still defined"
+_elif_ __ARCH__
+    kprint "undefined again"
+_endif_
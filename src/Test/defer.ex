@@ -0,0 +1,68 @@
+// defer runs its block when the enclosing scope closes, last one first
+
+kprint "=== Defer Test 1: LIFO at Label Exit ==="
+label visit[] work() {
+    defer {
+        kprint "close file"
+    }
+    defer {
+        kprint "stop process"
+    }
+    kprint "working"
+}
+work()
+
+kprint "=== Defer Test 2: Each Loop Iteration ==="
+for i _in_ ::[1..3] {
+    defer {
+        kprint "end of iteration"
+    }
+    kprint i
+}
+
+kprint "=== Defer Test 3: Early Exit with ? ==="
+label visit[] lookup(key=k) {
+    defer {
+        kprint "lookup done"
+    }
+    found = nil
+    found?
+    kprint "not reached"
+}
+lookup(key="a")
+
+kprint "=== Defer Test 4: Deferred Blocks See the Scope ==="
+label visit[] count() {
+    n = 1
+    defer {
+        kprint n
+    }
+    n = 2
+}
+count()
+
+kprint "=== Defer Test 5: _scoped_ ==="
+session = "open"
+label visit[] closing(new=v) {
+    kprint v
+}
+_watch_ session -> closing
+label visit[] use_session() {
+    _scoped_ session
+    kprint session
+}
+use_session()
+_revive_ session
+kprint session
+
+kprint "=== Defer Test 6: Script End and Errors ==="
+defer {
+    kprint "script finished"
+}
+label visit[] fail() {
+    defer {
+        kprint "cleanup after error"
+    }
+    missing_variable
+}
+fail()
@@ -0,0 +1,62 @@
+// ============================================
+// DESTRUCTURING TESTS
+// ============================================
+
+kprint "=== Destructure Test 1: Arrays ==="
+xs = [&l, 1, 2, 3, 4]
+[first, second, ...others] = xs
+kprint first
+kprint second
+kprint array_len(src=others)
+[p, q] = [&l, "p", "q"]
+kprint q
+
+kprint "=== Destructure Test 2: Nested, Ranges And Axes ==="
+[[x1, y1], [x2, y2]] = [&l, [&l, 1, 2], [&l, 3, 4]]
+kprint x1 + y2
+[lo, ...tail] = ::[10..15]
+kprint lo
+kprint range_len(src=tail)
+[top, ...rows] = [&a, [&a, 1, 2], [&a, 3, 4], [&a, 5, 6]]
+print(src=top)
+print(src=rows)
+
+kprint "=== Destructure Test 3: Dictionaries ==="
+cfg = [&d, "host": "localhost", "port": 8080, "tls": false]
+{host, port: pt} = cfg
+kprint host
+kprint pt
+{tls, ...extra} = cfg
+kprint tls
+kprint dict_len(src=extra)
+
+kprint "=== Destructure Test 4: Records ==="
+record Point {
+    x = 0,
+    y = 0
+}
+{x, y} = Point(x=3, y=4)
+kprint x * y
+
+kprint "=== Destructure Test 5: For Headers ==="
+for [u, v] _in_ [&l, [&l, 1, 2], [&l, 3, 4]] {
+    kprint u + v
+}
+servers = [&d, "web": [&d, "port": 80], "db": [&d, "port": 5432]]
+for name, {port} _in_ servers {
+    kprint port
+}
+
+kprint "=== Destructure Test 6: Label Parameters ==="
+label visit[] span(range=[start, stop]) {
+    kprint stop - start
+}
+span(range=[&l, 3, 10])
+
+label visit[] connect(cfg={host, port}) {
+    kprint port
+}
+connect(cfg=cfg)
+
+kprint "=== Destructure Test 7: Shape Mismatch ==="
+[m, n] = [&l, 1, 2, 3]
@@ -191,14 +191,12 @@ kprint points[0][0]
 kprint points[1][1]
 kprint points[2][2]
 
-// Test 18: Axis with mixed types
-kprint "=== Axis Test 5: Mixed Types ==="
-axis4 = [&a, 1, 2.5, "text", true, 'c']
+// Test 18: Axis with mixed Int and Float (elements become Float)
+kprint "=== Axis Test 5: Mixed Numbers ==="
+axis4 = [&a, 1, 2.5, 4]
 kprint axis4[0]
 kprint axis4[1]
 kprint axis4[2]
-kprint axis4[3]
-kprint axis4[4]
 
 // ============================================
 // MIXED TESTS
@@ -0,0 +1,30 @@
+// Values reachable from a _const_ or _lock_ binding are read-only
+
+kprint "=== Frozen Test 1: Copies Stay Mutable ==="
+cfg = [&d, "db": [&d, "host": "localhost", "port": 5432]]
+_const_ cfg
+copy = cfg
+copy["db"]["port"] = 6543
+kprint copy["db"]["port"]
+kprint cfg["db"]["port"]
+
+kprint "=== Frozen Test 2: Unlocking Thaws ==="
+xs = [&l, 1, 2]
+_lock_ xs
+_unlock_ xs
+xs[0] = 10
+xs.push(3)
+kprint xs[0]
+kprint array_len(src=xs)
+
+kprint "=== Frozen Test 3: Read-only Methods on a Constant ==="
+record Point { x, y }
+label visit[] Point.sum(src=s) {
+    kprint s.x + s.y
+}
+origin = Point(x=1, y=2)
+_const_ origin
+origin.sum()
+
+kprint "=== Frozen Test 4: Nested Write ==="
+cfg["db"]["port"] = 1
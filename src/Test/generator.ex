@@ -0,0 +1,80 @@
+// ============================================
+// GENERATOR TESTS
+// ============================================
+
+kprint "=== Generator Test 1: Finite Generators In For ==="
+label visit[] countdown(from=n) {
+    while n > 0 {
+        yield n
+        n--
+    }
+}
+
+for x _in_ countdown(from=3) {
+    kprint x
+}
+
+kprint "=== Generator Test 2: Infinite Counter With next ==="
+label visit[] naturals(start=i) {
+    while true {
+        yield i
+        i++
+    }
+}
+
+g = naturals(start=10)
+kprint next(src=g)
+kprint next(src=g)
+kprint g.next()
+print(src=g.take(3))
+
+kprint "=== Generator Test 3: State Across Branches And Loops ==="
+label visit[] evens_in(src=xs) {
+    for x _in_ xs {
+        if x % 2 == 0 {
+            yield x
+        }
+    }
+    yield "done"
+}
+
+evens = evens_in(src=[&l, 1, 2, 3, 4, 6])
+print(src=gen_take(src=evens, n=10))
+kprint next(src=evens) == nil
+
+kprint "=== Generator Test 4: Array Builtins ==="
+kprint array_len(src=countdown(from=5))
+print(src=array_reverse(src=countdown(from=3)))
+g = countdown(from=3)
+print(src=array_reverse(src=g))
+kprint next(src=g) == nil
+
+kprint "=== Generator Test 5: Visible Blocks ==="
+visible bounds(
+    limit = 3
+)
+label visit[bounds] upto() {
+    n = 1
+    while n <= limit {
+        yield n
+        n += 1
+    }
+}
+print(src=gen_take(src=upto(), n=10))
+
+kprint "=== Generator Test 6: Unfinished Generators Clean Up ==="
+label visit[] opened(name=n) {
+    defer {
+        kprint "closed"
+        kprint n
+    }
+    yield 1
+    yield 2
+}
+first = opened(name="dropped")
+kprint next(src=first)
+first = nil
+kprint "after drop"
+kept = opened(name="kept")
+kprint next(src=kept)
+kprint "script ends"
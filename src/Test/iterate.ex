@@ -0,0 +1,51 @@
+// ============================================
+// FOR-LOOP ITERATION TESTS
+// ============================================
+
+kprint "=== Iterate Test 1: Dictionaries ==="
+ages = [&d, "bo": 2, "al": 1]
+for name _in_ ages {
+    kprint name
+}
+for name, age _in_ ages {
+    kprint age
+}
+
+kprint "=== Iterate Test 2: Strings And Indexes ==="
+for ch _in_ "hey" {
+    kprint ch
+}
+for i, x _in_ [&l, "a", "b"] {
+    kprint i
+}
+
+kprint "=== Iterate Test 3: Axes, Sets, Ranges ==="
+m = [&a, [&a, 1, 2], [&a, 3, 4]]
+for row _in_ m {
+    print(src=row)
+}
+for x _in_ [&s, 3, 1, 2] {
+    kprint x
+}
+for x _in_ ::[1..<3] {
+    kprint x
+}
+
+kprint "=== Iterate Test 4: User-Defined Iterables ==="
+record Countdown {
+    n = 3,
+    value = nil
+}
+
+label visit[] Countdown.next(src=self) {
+    if self.n > 0 {
+        self.value = self.n
+        self.n--
+    } else {
+        self.value = nil
+    }
+}
+
+for x _in_ Countdown(n=3) {
+    kprint x
+}
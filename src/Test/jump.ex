@@ -0,0 +1,74 @@
+// jump transfers control to a control flow label in this block or an
+// enclosing one; after the label body, execution goes on after the label
+
+kprint "=== Jump Test 1: Forward Jump Skips Statements ==="
+jump skip
+kprint "not printed"
+label @skip {
+    kprint "at skip"
+}
+kprint "after skip"
+
+kprint "=== Jump Test 2: Backward Jump to Retry ==="
+attempts = 0
+label @retry {
+    attempts += 1
+    kprint attempts
+}
+if attempts < 3 {
+    jump retry
+}
+kprint "gave up retrying"
+
+kprint "=== Jump Test 3: State Machine Without Recursion ==="
+count = 0
+jump counting
+label @counting {
+    count += 1
+    if count < 100000 {
+        jump counting
+    }
+    jump finished
+}
+label @finished {
+    kprint count
+}
+
+kprint "=== Jump Test 4: Jump Out of a Loop ==="
+for i _in_ ::[1..10] {
+    if i == 3 {
+        jump found
+    }
+    kprint i
+}
+label @found {
+    kprint "left the loop"
+}
+
+kprint "=== Jump Test 5: Inside a Label Call ==="
+label visit[] search(limit=n) {
+    k = 0
+    label @again {
+        k += 1
+    }
+    if k < n {
+        jump again
+    }
+    kprint k
+}
+search(limit=4)
+
+kprint "=== Jump Test 6: Inside a Generator ==="
+label visit[] ticks(limit=n) {
+    t = 0
+    label @tick {
+        t += 1
+        yield t
+    }
+    if t < n {
+        jump tick
+    }
+}
+for t _in_ ticks(limit=3) {
+    kprint t
+}
@@ -0,0 +1,14 @@
+// Macro calls with the wrong number of arguments are rejected before the
+// script runs. The error points at the '#' of the call on line 12, not at
+// the last argument.
+
+_define_ PAIR_SUM(p, q) [
+    p + q
+]
+
+kprint "never printed"
+
+kprint #PAIR_SUM(1, 2)
+total = #PAIR_SUM(1,
+    2,
+    3)
@@ -0,0 +1,80 @@
+// ============================================
+// MACRO HYGIENE TESTS
+// ============================================
+
+kprint "=== Macro Test 1: Parameters Stay Local ==="
+_define_ TWICE(i) [
+    i = i * 2
+    i
+]
+i = 5
+kprint #TWICE(21)
+kprint i
+
+kprint "=== Macro Test 2: Arguments Use The Caller's Names ==="
+_define_ SWAP_SUM(x, y) [
+    tmp = y
+    y = x
+    x = tmp
+    x - y
+]
+x = 1
+y = 10
+kprint #SWAP_SUM(y, x)
+kprint x
+kprint y
+
+kprint "=== Macro Test 3: Free Names Reach The Caller ==="
+_define_ BUMP() [
+    counter = counter + 1
+]
+counter = 0
+#BUMP()
+#BUMP()
+kprint counter
+
+kprint "=== Macro Test 4: Labels Do Not See Parameters ==="
+label visit[] show() {
+    kprint n
+}
+_define_ CALL_SHOW(n) [
+    show()
+    n
+]
+n = "caller"
+kprint #CALL_SHOW("macro")
+
+kprint "=== Macro Test 5: Nested Expansion ==="
+_define_ SQUARE(v) [
+    v * v
+]
+_define_ SUM_SQUARES(v, w) [
+    #SQUARE(v) + #SQUARE(w)
+]
+kprint #SUM_SQUARES(3, 4)
+
+kprint "=== Macro Test 6: Assignments Reach Bound Caller Variables ==="
+_define_ RESET() [
+    hits = 0
+]
+hits = 7
+#RESET()
+kprint hits
+_define_ MAYBE(c) [
+    if c {
+        total = 6
+    }
+    total
+]
+total = 1
+kprint #MAYBE(false)
+kprint #MAYBE(true)
+kprint total
+
+kprint "=== Macro Test 7: Temporaries Stay Local ==="
+_define_ KEEP_LAST(x, y) [
+    tmp = y
+    tmp
+]
+kprint #KEEP_LAST(1, 2)
+kprint tmp
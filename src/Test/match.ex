@@ -0,0 +1,92 @@
+// ============================================
+// MATCH EXPRESSION TESTS
+// ============================================
+
+kprint "=== Match Test 1: Literals And Wildcard ==="
+label visit[] describe(n=n) {
+    kprint match n {
+        0 -> "zero",
+        -1 -> "minus one",
+        "hi" | "hello" -> "greeting",
+        nil -> "nothing",
+        _ -> "other"
+    }
+}
+describe(n=0)
+describe(n=-1)
+describe(n="hello")
+describe(n=nil)
+describe(n=true)
+
+kprint "=== Match Test 2: Ranges And Guards ==="
+label visit[] grade(score=score) {
+    kprint match score {
+        ::[90..100] -> "A",
+        ::[75..<90] -> "B",
+        s if s < 0 -> "invalid",
+        _ -> "C"
+    }
+}
+grade(score=95)
+grade(score=80)
+grade(score=-4)
+grade(score=30)
+
+kprint "=== Match Test 3: Types And Bindings ==="
+record Point {
+    x = 0,
+    y = 0
+}
+label visit[] kind(v=v) {
+    kprint match v {
+        Int -> "int",
+        String -> "string",
+        p @ Point -> p.x + p.y,
+        Array | Axis -> "sequence",
+        other -> typeof(src=other)
+    }
+}
+kind(v=1)
+kind(v="s")
+kind(v=Point(x=2, y=5))
+kind(v=[&l, 1])
+kind(v=1.5)
+
+kprint "=== Match Test 4: Arrays ==="
+label visit[] shape(xs=xs) {
+    kprint match xs {
+        [] -> "empty",
+        [only] -> only,
+        [0, ...rest] -> array_len(src=rest),
+        [first, second, ...rest] -> first + second
+    }
+}
+shape(xs=array_new())
+shape(xs=[&l, 7])
+shape(xs=[&l, 0, 1, 2])
+shape(xs=[&l, 4, 5, 6])
+
+kprint "=== Match Test 5: Dictionaries ==="
+label visit[] check(cfg=cfg) {
+    kprint match cfg {
+        {mode: "prod", port: ::[1..1023]} -> "privileged prod port",
+        {mode: "prod", port: Int} -> "prod",
+        {mode: m, ...others} if dict_len(src=others) == 0 -> m,
+        {port: String} -> "port must be a number",
+        _ -> "invalid config"
+    }
+}
+check(cfg=[&d, "mode": "prod", "port": 80])
+check(cfg=[&d, "mode": "prod", "port": 8080])
+check(cfg=[&d, "mode": "dev"])
+check(cfg=[&d, "port": "80"])
+check(cfg=[&d, "host": "x"])
+
+kprint "=== Match Test 6: Value And Scope ==="
+x = 3
+y = match x { 1 -> "one", n -> n * 10 }
+kprint y
+kprint x
+
+kprint "=== Match Test 7: No Arm ==="
+z = match 5 { 1 -> "one" }
@@ -0,0 +1,37 @@
+// ============================================
+// MATH LIBRARY TESTS
+// ============================================
+
+kprint "=== Math Test 1: Exact Integer Results ==="
+kprint math_sqrt(src=144)
+kprint typeof(src=math_sqrt(src=144))
+kprint math_sqrt(src=2)
+kprint math_cbrt(src=-27)
+kprint math_pow(base=3, exp=4)
+kprint math_abs(src=-42)
+
+kprint "=== Math Test 2: BigInt Arithmetic ==="
+big = math_pow(base=2, exp=128)
+kprint big
+kprint typeof(src=big)
+kprint math_isqrt(src=big)
+kprint math_gcd(x=big, y=math_pow(base=6, exp=50))
+kprint math_pow_mod(base=4, exp=13, modulus=497)
+
+kprint "=== Math Test 3: Integer Helpers ==="
+kprint math_lcm(x=4, y=6)
+kprint math_clamp(src=15, lo=0, hi=10)
+kprint math_min(x=3, y=2.5)
+kprint math_max(x=3, y=2.5)
+kprint math_max(x=NAN, y=1)
+kprint math_max(x=1, y=NAN)
+kprint math_min(x=NAN, y=1)
+
+kprint "=== Math Test 4: Float Functions ==="
+kprint math_round(src=2.5)
+kprint math_round(src=2.5, mode="half_even")
+kprint math_floor(src=-2.5)
+kprint math_log(src=8, base=2)
+kprint math_sin(src=PI / 2)
+kprint math_atan2(y=1, x=1) * 4
+kprint INF
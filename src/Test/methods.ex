@@ -0,0 +1,54 @@
+// ============================================
+// METHOD CALL TESTS
+// ============================================
+
+kprint "=== Method Test 1: Arrays ==="
+xs = [&l, 3, 1, 2]
+print(src=xs.push(4))
+kprint xs.len()
+print(src=xs.sort().reverse())
+kprint xs.contains(2)
+kprint xs.slice(start=0, end=2).len()
+
+kprint "=== Method Test 2: Strings ==="
+s = "  Hello World  "
+kprint s.trim().upper()
+kprint s.trim().lower().replace("world", "there")
+kprint "a,b,c".len()
+words = "a,b,c"
+print(src=words.split(","))
+kprint words.split(",").join("-")
+
+kprint "=== Method Test 3: Dictionaries, Sets, Axes ==="
+ages = [&d, "bo": 2, "al": 1]
+print(src=ages.keys())
+kprint ages.contains("al")
+tags = [&s, 1, 2]
+print(src=tags.union([&s, 3]))
+v = [&a, 1, 2, 3]
+kprint v.dot(v)
+kprint v.sum()
+
+kprint "=== Method Test 4: Record Methods ==="
+record Counter {
+    count = 0,
+    step = 1
+}
+
+label visit[] Counter.tick(src=self) {
+    self.count = self.count + self.step
+}
+
+label visit[] Counter.bump(src=self, by=n) {
+    self.count = self.count + n
+}
+
+c = Counter(step=2)
+c.tick()
+c.tick()
+kprint c.count
+c.bump(10)
+kprint c.count
+boxes = [&l, Counter(), Counter()]
+boxes[1].bump(by=5)
+kprint boxes[1].count
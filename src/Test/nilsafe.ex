@@ -0,0 +1,37 @@
+// ============================================
+// NIL-SAFE ACCESS AND NIL-COALESCING TESTS
+// ============================================
+
+kprint "=== Nil Test 1: Optional Chaining ==="
+cfg = [&d, "db": [&d, "host": "localhost"]]
+kprint cfg?["db"]?["host"]
+kprint cfg?["cache"]?["host"] == nil
+xs = [&l, 1, 2]
+kprint xs?[5] == nil
+nothing = nil
+kprint nothing?[0] == nil
+
+kprint "=== Nil Test 2: Coalescing ==="
+kprint cfg?["cache"]?["host"] ?? "none"
+kprint cfg?["db"]?["host"] ?? "none"
+port = nil
+kprint port ?? 5432
+kprint false ?? 1
+
+kprint "=== Nil Test 3: Records ==="
+record Server {
+    name,
+    backup = nil
+}
+main = Server(name="a")
+kprint main?.backup?.name ?? "no backup"
+
+kprint "=== Nil Test 4: Propagation Out Of A Label ==="
+label visit[] lookup(table=t, key=k) {
+    kprint "looking up"
+    host = t?[k]?
+    kprint host["host"]
+}
+lookup(table=cfg, key="db")
+lookup(table=cfg, key="cache")
+kprint "after lookups"
@@ -0,0 +1,33 @@
+// ============================================
+// RANDOM NUMBER TESTS
+// ============================================
+
+kprint "=== Random Test 1: Seeded Runs Repeat ==="
+rand_seed(seed=42)
+first = rand_int(lo=1, hi=100)
+firstf = rand_float()
+rand_seed(seed=42)
+kprint first == rand_int(lo=1, hi=100)
+kprint firstf == rand_float()
+
+kprint "=== Random Test 2: Bounds ==="
+rand_seed(seed=7)
+ok = true
+for i _in_ ::[1..200] {
+    r = rand_int(lo=-3, hi=3)
+    if r < -3 | r > 3 {
+        ok = false
+    }
+}
+kprint ok
+f = rand_float()
+kprint f >= 0 && f < 1
+
+kprint "=== Random Test 3: Collections ==="
+deck = [&l, 1, 2, 3, 4, 5]
+picked = rand_choice(src=deck)
+kprint array_contains(src=deck, value=picked)
+mixed = array_shuffle(src=deck)
+kprint array_len(src=mixed)
+kprint array_sort(src=mixed) == deck
+kprint array_len(src=rand_bytes(count=16))
@@ -0,0 +1,55 @@
+// ============================================
+// RANGE TESTS
+// ============================================
+
+kprint "=== Range Test 1: Forms ==="
+n = 4
+print(src=::[0..n])
+kprint ::[0..n].len()
+kprint ::[0..<n].len()
+kprint ::[0..10:3].to_array().len()
+print(src=::[0..10:3])
+print(src=::[5..1])
+print(src=::[-3..3])
+kprint ::[-3..-1].contains(-2)
+
+kprint "=== Range Test 2: Loops ==="
+total = 0
+for i _in_ ::[1..<n] {
+    total += i
+}
+kprint total
+for i _in_ ::[10..0:-5] {
+    kprint i
+}
+for i _in_ ::[1..3].reverse() {
+    kprint i
+}
+
+kprint "=== Range Test 3: Large Ranges Stay Lazy ==="
+big = ::[1..1000000000000]
+kprint big.len()
+kprint big[-1]
+kprint big.contains(999999999999)
+kprint range_contains(src=::[0..10:2], value=3)
+top = 170141183460469231731687303715884105727
+huge = ::[0..top]
+kprint huge.len()
+kprint huge.contains(top)
+kprint huge[-1]
+backwards = huge.reverse()
+kprint backwards[0]
+kprint array_len(src=::[0..100000000000])
+kprint array_get(src=big, idx=-2)
+kprint array_find(src=big, value=42)
+kprint ::[0..-5].contains(-top - 1)
+
+kprint "=== Range Test 4: Slicing And Arrays ==="
+xs = [&l, "a", "b", "c", "d"]
+print(src=xs[::[1..2]])
+print(src=xs[::[3..0]])
+print(src=array_reverse(src=::[1..3]))
+kprint array_len(src=::[0..<5])
+
+kprint "=== Range Test 5: Too Large To Expand ==="
+array_sort(src=::[0..100000000000])
@@ -0,0 +1,42 @@
+// ============================================
+// RECORD TESTS
+// ============================================
+
+record Point {
+    x = 0,
+    y = 0
+}
+
+record Line {
+    start,
+    end,
+    name = "segment"
+}
+
+kprint "=== Record Test 1: Construction ==="
+p = Point(x=3, y=4)
+origin = Point()
+print(src=p)
+print(src=origin)
+kprint typeof(src=p)
+
+kprint "=== Record Test 2: Field Read And Write ==="
+kprint p.x
+p.x = 10
+kprint p.x
+kprint p.y
+
+kprint "=== Record Test 3: Nesting ==="
+ln = Line(start=origin, end=p)
+kprint ln.end.x
+ln.end.y = 7
+kprint ln.end.y
+kprint ln.name
+pts = [&l, Point(x=1, y=1), Point(x=2, y=2)]
+pts[1].x = 20
+kprint pts[1].x
+
+kprint "=== Record Test 4: Equality ==="
+kprint Point(x=1, y=2) == Point(x=1, y=2)
+kprint Point(x=1, y=2) == Point(x=2, y=1)
+kprint origin == Point()
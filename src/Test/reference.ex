@@ -0,0 +1,40 @@
+// ============================================
+// IN-PLACE COLLECTION MUTATION TESTS
+// ============================================
+
+kprint "=== Reference Test 1: Builtins update the variable ==="
+xs = [&l, 1, 2]
+array_push(src=xs, value=3)
+print(src=xs)
+kprint array_pop(src=xs)
+array_set(src=xs, idx=0, value=10)
+print(src=xs)
+
+kprint "=== Reference Test 2: Methods update the variable ==="
+xs.insert(0, 5)
+print(src=xs)
+tags = [&s, 1]
+tags.add(2)
+print(src=tags)
+
+kprint "=== Reference Test 3: Copies are not affected ==="
+ys = xs
+ys.push(99)
+print(src=xs)
+print(src=ys)
+
+kprint "=== Reference Test 4: Sort, reverse and shuffle return new arrays ==="
+sorted = array_sort(src=xs)
+print(src=sorted)
+print(src=xs)
+kprint xs.reverse() == xs
+
+kprint "=== Reference Test 5: Growing an array in a loop ==="
+big = array_new()
+i = 0
+while i < 20000 {
+    big.push(i)
+    i = i + 1
+}
+kprint big.len()
+kprint big[19999]
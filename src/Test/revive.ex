@@ -0,0 +1,65 @@
+// _kill_ keeps a tombstone, _revive_ brings the value back
+
+kprint "=== Revive Test 1: Last Value ==="
+port = 8080
+_kill_ port
+_revive_ port
+kprint port
+
+kprint "=== Revive Test 2: Transactional Edit ==="
+config = [&d, "host": "localhost", "port": 80]
+_kill_ config
+config = [&d, "host": "example.org", "port": "eighty"]
+if typeof(src=config["port"]) != "Int" {
+    _kill_ config
+    _revive_ config @ 1
+}
+kprint config["host"]
+
+kprint "=== Revive Test 3: Generations ==="
+level = 1
+_kill_ level
+_revive_ level
+level = 2
+_kill_ level
+_revive_ level
+level = 3
+_kill_ level
+_revive_ level @ 2
+kprint level
+h = history(name="level")
+kprint array_len(src=h)
+kprint h[0]["value"]
+kprint h[2]["value"]
+kprint h[0]["locked"]
+
+kprint "=== Revive Test 4: Kill Inside a Label ==="
+total = 42
+label visit[] reset() {
+    _kill_ total
+}
+reset()
+_revive_ total
+kprint total
+
+kprint "=== Revive Test 5: Locked Values ==="
+mode = "safe"
+_lock_ mode
+_kill_ mode
+mode = "edit"
+kprint mode
+_kill_ mode
+_revive_ mode @ 1
+kprint mode
+// The revived value is locked again, so killing it records the lock
+_kill_ mode
+modes = history(name="mode")
+kprint modes[0]["locked"]
+kprint modes[1]["locked"]
+_revive_ mode
+kprint mode
+
+kprint "=== Revive Test 6: Too Far Back ==="
+level = 4
+_kill_ level
+_revive_ level @ 9
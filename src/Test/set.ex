@@ -0,0 +1,30 @@
+// ============================================
+// SET TESTS
+// ============================================
+
+kprint "=== Set Test 1: Literal And Printing ==="
+s = [&s, 3, 1, 2, 3, 1]
+print(src=s)
+kprint set_len(src=s)
+kprint typeof(src=s)
+
+kprint "=== Set Test 2: Membership ==="
+kprint set_contains(src=s, value=2)
+kprint set_contains(src=s, value=9)
+print(src=set_add(src=s, value=0))
+print(src=set_remove(src=s, value=3))
+
+kprint "=== Set Test 3: Algebra ==="
+odd = [&s, 1, 3, 5]
+print(src=set_union(x=s, y=odd))
+print(src=set_intersection(x=s, y=odd))
+print(src=set_difference(x=s, y=odd))
+kprint set_is_subset(x=[&s, 1, 3], y=odd)
+kprint set_is_subset(x=s, y=odd)
+kprint [&s, 2, 1] == [&s, 1, 2]
+
+kprint "=== Set Test 4: Iteration ==="
+words = set_from_array(src=[&l, "pear", "apple", "pear", "fig"])
+for w _in_ words {
+    kprint w
+}
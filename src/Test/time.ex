@@ -0,0 +1,36 @@
+// ============================================
+// TIME LIBRARY TESTS
+// ============================================
+
+kprint "=== Time Test 1: Monotonic Clock ==="
+start = now_ns()
+sleep_ms(ms=15)
+took = elapsed(since=start)
+kprint took >= 15000000
+
+kprint "=== Time Test 2: Wall Clock ==="
+kprint unix_time() > 1700000000
+
+kprint "=== Time Test 3: UTC Breakdown ==="
+utc = time_utc(ts=1700000000)
+kprint utc["year"]
+kprint utc["month"]
+kprint utc["weekday"]
+kprint time_format(src=utc, fmt="%Y-%m-%d %H:%M:%S %Z")
+
+kprint "=== Time Test 4: Zoneinfo ==="
+winter = time_local(ts=1700000000, zone="America/New_York")
+summer = time_local(ts=1690000000, zone="America/New_York")
+kprint time_format(src=winter, fmt="%F %T %Z %:z")
+kprint time_format(src=summer, fmt="%F %T %Z %:z")
+
+kprint "=== Time Test 5: ISO-8601 Parsing ==="
+parsed = time_parse(src="2024-02-29T13:45:10.250+05:30")
+kprint parsed["timestamp"]
+kprint parsed["millisecond"]
+kprint time_format(src=parsed, fmt="%a %d %b %Y %I:%M %p")
+
+kprint "=== Time Test 6: Field Ranges ==="
+kprint time_format(src=[&d,"year": 2024, "hour": 23, "minute": 59], fmt="%F %T")
+// Fields out of range are rejected instead of wrapping around
+kprint time_format(src=[&d,"hour": -5], fmt="%H")
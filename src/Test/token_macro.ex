@@ -0,0 +1,61 @@
+// ============================================
+// TOKEN MACRO TESTS
+// ============================================
+
+kprint "=== Token Macro Test 1: Expressions ==="
+_macro_ SQUARE {
+    ($x:expr) -> [ $x * $x ]
+}
+kprint #SQUARE(2 + 1)
+kprint #SQUARE(3) + 1
+
+kprint "=== Token Macro Test 2: Several Rules And Repetition ==="
+_macro_ SUM {
+    () -> [ 0 ]
+    ($first:expr $(, $rest:expr)*) -> [ $first $(+ $rest)* ]
+}
+kprint #SUM()
+kprint #SUM(4)
+kprint #SUM(1, 2, 3, 4)
+
+_macro_ LIST {
+    ($($item:expr),*) -> [ [&l, $($item),*] ]
+}
+kprint array_len(src=#LIST(5, 6, 7))
+
+kprint "=== Token Macro Test 3: Stringify ==="
+_macro_ SHOW {
+    ($e:expr) -> [ kprint #$e  kprint $e ]
+}
+#SHOW(6 * 7)
+
+kprint "=== Token Macro Test 4: Identifier Pasting ==="
+_macro_ COUNTER {
+    ($name:ident, $start:expr) -> [
+        $name ## _count = $start
+        label visit[] bump_ ## $name() {
+            $name ## _count++
+        }
+    ]
+}
+#COUNTER(hits, 10)
+bump_hits()
+bump_hits()
+kprint hits_count
+
+kprint "=== Token Macro Test 5: Block Parameters ==="
+_macro_ REPEAT {
+    ($n:expr, $body:block) -> [
+        for _ _in_ ::[1..$n] {
+            $body
+        }
+    ]
+}
+total = 0
+#REPEAT(3, {
+    total += 2
+    kprint total
+})
+
+kprint "=== Token Macro Test 6: Nested Calls ==="
+kprint #SUM(#SQUARE(2), #SQUARE(3))
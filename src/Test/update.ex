@@ -0,0 +1,54 @@
+// ============================================
+// INCREMENT AND COMPOUND ASSIGNMENT TESTS
+// ============================================
+
+kprint "=== Update Test 1: Counters Stay Int ==="
+i = 0
+while i < 3 {
+    i++
+}
+kprint i
+kprint typeof(src=i)
+kprint i--
+kprint --i
+kprint ++i
+kprint 7 % 3
+kprint typeof(src=6 * 7)
+
+kprint "=== Update Test 2: Compound Operators ==="
+x = 10
+x += 5
+x -= 3
+x *= 2
+kprint x
+x %= 5
+kprint x
+y = 9
+y /= 2
+kprint y
+top = 170141183460469231731687303715884105726
+top++
+kprint top
+low = -top - 1
+kprint -(low + 1)
+
+kprint "=== Update Test 3: Indexed Targets ==="
+xs = [&l, 1, 2, 3]
+xs[0] += 10
+xs[-1]++
+print(src=xs)
+counts = [&d, "a": 1]
+counts["a"] *= 4
+kprint counts["a"]
+record Point {
+    x = 0,
+    y = 0
+}
+p = Point(x=1)
+p.x += 2
+kprint p.x
+
+kprint "=== Update Test 4: Locked Variables ==="
+limit = 3
+_const_ limit
+limit += 1
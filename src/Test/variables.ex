@@ -14,9 +14,15 @@ kprint "Edited Successfull "
 
 pi = 2.17
 
-_const_ pi
+_lock_ pi
 _unlock_ pi
 
 pi = 55
 kprint pi
 
+e = 2.71
+
+_const_ e
+// Constants cannot be unlocked
+_unlock_ e
+
@@ -0,0 +1,36 @@
+visible settings(
+    level = 1,
+    name = "app"
+)
+
+visible stats(
+    hits = 0
+)
+
+// Reading and writing the blocks a label visits
+label visit[settings, stats] report() {
+    hits = hits + 1
+    kprint name
+    kprint level
+    kprint hits
+}
+
+// A parameter with a block variable's name is the label's own
+label visit[] rename(value=level) {
+    kprint level
+}
+
+// Nested labels visit blocks for themselves
+label visit[stats] outer() {
+    label visit[stats] inner() {
+        kprint hits
+    }
+    inner()
+}
+
+report()
+report()
+rename(value=7)
+outer()
+
+// visible_denied.ex shows the problems this check rejects
@@ -0,0 +1,34 @@
+// Visible block permissions are checked before the script runs: nothing
+// below is printed, and every problem is reported at once.
+
+visible stats(
+    hits = 0
+)
+
+kprint "never printed"
+
+label visit[stats] count() {
+    hits = hits + 1
+}
+
+// Reads 'hits' without visit[stats]
+label visit[] peek() {
+    kprint hits
+}
+
+// Its own loop variable 'hits' is not the block's: allowed. It does not
+// excuse the read in peek, which is outside the loop
+label visit[] tally(items=xs) {
+    for hits _in_ xs {
+        kprint hits
+    }
+}
+
+// Names a block that is never declared
+label visit[missing] lost() {
+    kprint 1
+}
+
+count()
+peek()
+tally(items=::[1..2])
@@ -0,0 +1,68 @@
+// _watch_ runs a label before every change to a variable
+
+kprint "=== Watch Test 1: Assignment ==="
+label visit[] log(name=n, old=o, new=v) {
+    kprint n
+    kprint o
+    kprint v
+}
+count = 1
+_watch_ count -> log
+count = 2
+count += 3
+count++
+kprint count
+
+kprint "=== Watch Test 2: Index Assignment and Builtins ==="
+label visit[] sizes(old=o, new=v) {
+    kprint array_len(src=o)
+    kprint array_len(src=v)
+}
+items = [&l, 1, 2]
+_watch_ items -> sizes
+items[0] = 10
+items.push(3)
+kprint items[0]
+kprint array_len(src=items)
+_unwatch_ items -> sizes
+
+kprint "=== Watch Test 3: Kill and Revive ==="
+_kill_ count
+_revive_ count
+kprint count
+
+kprint "=== Watch Test 4: Veto ==="
+// veto() refuses the change; any other error raised by a watcher does too
+label visit[] positive(new=v) {
+    if v < 0 {
+        kprint "refused"
+        veto(reason="balance cannot go negative")
+    }
+}
+balance = 10
+_watch_ balance -> positive
+balance = 5
+_unwatch_ count
+count = 99
+kprint balance
+
+kprint "=== Watch Test 5: Watched From a Label ==="
+label visit[] spend() {
+    balance -= 2
+}
+spend()
+kprint balance
+
+kprint "=== Watch Test 6: A Vetoed Change Leaves the Value ==="
+// The label that made the change ends at the veto, running its defers
+label visit[] overdraw() {
+    defer {
+        kprint balance
+    }
+    balance = -1
+    kprint "not reached"
+}
+overdraw()
+kprint balance
+balance = 1
+kprint balance
@@ -0,0 +1,378 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::interpreter::{RuntimeError, RuntimeResult};
+use crate::parser::ast::Accessor;
+use crate::values::range::Range;
+use crate::values::values::{Record, RecordType, Value};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Convert Value -> dictionary key string
+pub(crate) fn to_dict_key(v: Value) -> RuntimeResult<String> {
+    Ok(match v {
+        Value::Int(i) => i.to_string(),
+        Value::String(s) => s,
+        Value::Float(f) => f.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Char(ch) => ch.to_string(),
+        Value::BigInt(bi) => bi,
+        _ => {
+            return Err(RuntimeError::custom(format!(
+                "Invalid key type for dictionary access: {}",
+                v.type_name()
+            )));
+        }
+    })
+}
+
+fn int_index(accessor: &Value, what: &str) -> RuntimeResult<i128> {
+    match accessor {
+        Value::Int(i) => Ok(*i),
+        _ => Err(RuntimeError::custom(format!(
+            "{} index must be integer, got {}",
+            what,
+            accessor.type_name()
+        ))),
+    }
+}
+
+// Resolve an Int accessor (negative counts from the end) against a length.
+// Ok(None) means the index is out of bounds.
+fn position(accessor: &Value, len: usize, what: &str) -> RuntimeResult<Option<usize>> {
+    let idx = int_index(accessor, what)?;
+
+    let l = len as i128;
+    let real = if idx < 0 { l + idx } else { idx };
+    Ok((0..l).contains(&real).then_some(real as usize))
+}
+
+fn out_of_bounds(accessor: &Value, len: impl std::fmt::Display, what: &str) -> RuntimeError {
+    let idx = if let Value::Int(i) = accessor { *i } else { 0 };
+    RuntimeError::custom(format!(
+        "Index {} out of bounds for {} of length {}",
+        idx,
+        what.to_lowercase(),
+        len
+    ))
+}
+
+fn resolve_position(accessor: Value, len: usize, what: &str) -> RuntimeResult<usize> {
+    position(&accessor, len, what)?.ok_or_else(|| out_of_bounds(&accessor, len, what))
+}
+
+fn select_range(arr: &[Value], range: &Range) -> RuntimeResult<Lookup> {
+    let mut picked = Vec::new();
+    for idx in range.iter() {
+        let idx = Value::Int(idx);
+        match position(&idx, arr.len(), "Array")? {
+            Some(actual) => picked.push(arr[actual].clone()),
+            None => return Ok(Lookup::Missing(out_of_bounds(&idx, arr.len(), "Array"))),
+        }
+    }
+    Ok(Lookup::Found(Value::array(picked)))
+}
+
+fn no_field(rec: &Record, field: &str) -> RuntimeError {
+    RuntimeError::custom(format!("Record '{}' has no field '{}'", rec.name, field))
+}
+
+// Result of one read step
+enum Lookup {
+    Found(Value),
+    Missing(RuntimeError),
+}
+
+// An access step with its index already evaluated
+enum PathKey {
+    Index(Value),
+    Field(String),
+}
+
+// How a write target reads in an error message: cfg["db"]["port"], p.x
+fn render_path(ds: &str, path: &[PathKey]) -> String {
+    let mut shown = ds.to_string();
+    for key in path {
+        match key {
+            PathKey::Field(field) => {
+                shown.push('.');
+                shown.push_str(field);
+            }
+            PathKey::Index(Value::String(s)) => shown.push_str(&format!("[{:?}]", s)),
+            PathKey::Index(Value::Char(c)) => shown.push_str(&format!("[{:?}]", c)),
+            PathKey::Index(Value::Int(i)) => shown.push_str(&format!("[{}]", i)),
+            PathKey::Index(Value::UInt(u)) => shown.push_str(&format!("[{}]", u)),
+            PathKey::Index(other) => shown.push_str(&format!("[<{}>]", other.type_name())),
+        }
+    }
+    shown
+}
+
+// Replaces the element at `path` inside `current` with `value`. Shared
+// collections are copied on first write (Rc::make_mut), unshared ones are
+// modified where they are.
+fn assign_path(current: &mut Value, path: &[PathKey], value: Value) -> RuntimeResult<()> {
+    let Some((first, rest)) = path.split_first() else {
+        *current = value;
+        return Ok(());
+    };
+
+    match first {
+        PathKey::Field(field) => match current {
+            Value::Record(rec) => {
+                let Some(slot) = rec.get_mut(field) else {
+                    return Err(no_field(rec, field));
+                };
+                assign_path(slot, rest, value)
+            }
+            other => Err(RuntimeError::custom(format!(
+                "Cannot assign field '{}' on type '{}'",
+                field,
+                other.type_name()
+            ))),
+        },
+
+        PathKey::Index(accessor) => match current {
+            Value::Array(arr) => {
+                let actual = resolve_position(accessor.clone(), arr.len(), "Array")?;
+                assign_path(&mut Rc::make_mut(arr)[actual], rest, value)
+            }
+
+            Value::Dictionary(dict) => {
+                let key = to_dict_key(accessor.clone())?;
+                let dict = Rc::make_mut(dict);
+                // The last step may add a new key; deeper steps need an existing one
+                if rest.is_empty() {
+                    dict.insert(key, value);
+                    return Ok(());
+                }
+                match dict.get_mut(&key) {
+                    Some(slot) => assign_path(slot, rest, value),
+                    None => Err(RuntimeError::custom(format!(
+                        "Key '{}' not found in dictionary",
+                        key
+                    ))),
+                }
+            }
+
+            other => Err(RuntimeError::custom(format!(
+                "Cannot assign by index on type '{}'",
+                other.type_name()
+            ))),
+        },
+    }
+}
+
+// The existing element at `path` inside `current`
+fn path_slot<'a>(current: &'a mut Value, path: &[PathKey]) -> RuntimeResult<&'a mut Value> {
+    let Some((first, rest)) = path.split_first() else {
+        return Ok(current);
+    };
+
+    let slot = match (first, current) {
+        (PathKey::Field(field), Value::Record(rec)) => {
+            let name = rec.name.clone();
+            rec.get_mut(field).ok_or_else(|| {
+                RuntimeError::custom(format!("Record '{}' has no field '{}'", name, field))
+            })?
+        }
+        (PathKey::Index(accessor), Value::Array(arr)) => {
+            let actual = resolve_position(accessor.clone(), arr.len(), "Array")?;
+            &mut Rc::make_mut(arr)[actual]
+        }
+        (PathKey::Index(accessor), Value::Dictionary(dict)) => {
+            let key = to_dict_key(accessor.clone())?;
+            Rc::make_mut(dict).get_mut(&key).ok_or_else(|| {
+                RuntimeError::custom(format!("Key '{}' not found in dictionary", key))
+            })?
+        }
+        (_, other) => {
+            return Err(RuntimeError::custom(format!(
+                "Cannot update a member of type '{}'",
+                other.type_name()
+            )));
+        }
+    };
+    path_slot(slot, rest)
+}
+
+impl Interpreter {
+    // Reads x[...].field... starting from the variable `ds`. A `?` step
+    // turns a nil value or a missing key/index/field into a nil result.
+    pub(crate) fn read_access(&mut self, ds: &str, member: &[Accessor]) -> RuntimeResult<Value> {
+        let mut current = self.environment.get(ds)?;
+
+        for item in member {
+            let (step, optional) = match item {
+                Accessor::Optional(inner) => (inner.as_ref(), true),
+                step => (step, false),
+            };
+
+            if optional && current == Value::Nil {
+                return Ok(Value::Nil);
+            }
+
+            current = match self.access_step(current, step)? {
+                Lookup::Found(value) => value,
+                Lookup::Missing(_) if optional => return Ok(Value::Nil),
+                Lookup::Missing(err) => return Err(err),
+            };
+        }
+
+        Ok(current)
+    }
+
+    // Applies one access step. Type errors are raised; an absent key,
+    // index or field is reported as Missing so the caller can decide.
+    fn access_step(&mut self, current: Value, step: &Accessor) -> RuntimeResult<Lookup> {
+        Ok(match step {
+            Accessor::Field(field) => match current {
+                Value::Record(rec) => match rec.get(field) {
+                    Some(value) => Lookup::Found(value.clone()),
+                    None => Lookup::Missing(no_field(&rec, field)),
+                },
+                other => {
+                    return Err(RuntimeError::custom(format!(
+                        "Cannot read field '{}' on type '{}'",
+                        field,
+                        other.type_name()
+                    )));
+                }
+            },
+
+            Accessor::Index(expr) => {
+                let accessor = self.eval(expr)?;
+
+                // xs[::[1..3]] selects the elements at each index of the range
+                if let (Value::Array(arr), Value::Range(range)) = (&current, &accessor) {
+                    return select_range(arr, range);
+                }
+
+                match current {
+                    Value::Dictionary(dict) => {
+                        let key = to_dict_key(accessor)?;
+                        match dict.get(&key) {
+                            Some(value) => Lookup::Found(value.clone()),
+                            None => Lookup::Missing(RuntimeError::custom(format!(
+                                "Key '{}' not found in dictionary",
+                                key
+                            ))),
+                        }
+                    }
+
+                    Value::Array(arr) => match position(&accessor, arr.len(), "Array")? {
+                        Some(actual) => Lookup::Found(arr[actual].clone()),
+                        None => Lookup::Missing(out_of_bounds(&accessor, arr.len(), "Array")),
+                    },
+
+                    Value::Range(range) => match range.at(int_index(&accessor, "Range")?) {
+                        Some(n) => Lookup::Found(Value::Int(n)),
+                        None => Lookup::Missing(out_of_bounds(&accessor, range.len(), "Range")),
+                    },
+
+                    Value::Axis(axis) => {
+                        if axis.rank() == 0 {
+                            return Err(RuntimeError::custom("Cannot index a rank-0 axis"));
+                        }
+                        match position(&accessor, axis.len(), "Axis")? {
+                            Some(actual) => Lookup::Found(axis.get(actual)),
+                            None => Lookup::Missing(out_of_bounds(&accessor, axis.len(), "Axis")),
+                        }
+                    }
+
+                    other => {
+                        return Err(RuntimeError::custom(format!(
+                            "Cannot access member on type '{}'",
+                            other.type_name()
+                        )));
+                    }
+                }
+            }
+
+            Accessor::Optional(inner) => return self.access_step(current, inner),
+        })
+    }
+
+    fn eval_path(&mut self, member: &[Accessor]) -> RuntimeResult<Vec<PathKey>> {
+        let mut path: Vec<PathKey> = Vec::with_capacity(member.len());
+        for item in member {
+            path.push(match item {
+                Accessor::Field(field) => PathKey::Field(field.clone()),
+                Accessor::Index(expr) => PathKey::Index(self.eval(expr)?),
+                Accessor::Optional(_) => {
+                    return Err(RuntimeError::custom("Cannot assign through '?[' or '?.'"));
+                }
+            });
+        }
+        Ok(path)
+    }
+
+    // Constant and locked variables are frozen all the way down: any write
+    // inside them fails, naming the path as `shown` renders it
+    pub(crate) fn check_frozen(&self, ds: &str, shown: impl FnOnce() -> String) -> RuntimeResult<()> {
+        if !self.environment.exists(ds) || self.environment.is_mutable(ds) {
+            return Ok(());
+        }
+        Err(if self.environment.is_constant(ds) {
+            RuntimeError::cannot_modify_constant(shown())
+        } else {
+            RuntimeError::cannot_modify_smart_locked(shown())
+        })
+    }
+
+    // Writes `value` at x[...].field... in the variable `ds`. Index
+    // expressions are evaluated first; the root is then updated in place.
+    pub(crate) fn assign_access(&mut self, ds: &str, member: &[Accessor], value: Value) -> RuntimeResult<()> {
+        let path = self.eval_path(member)?;
+
+        self.check_frozen(ds, || render_path(ds, &path))?;
+        self.update_in_place(ds, |_, root| assign_path(root, &path, value))
+    }
+
+    // Replaces the value at x[...].field... with `update(old)` and returns
+    // (old, new). Index expressions are evaluated once.
+    pub(crate) fn update_access<F>(&mut self, ds: &str, member: &[Accessor], update: F) -> RuntimeResult<(Value, Value)>
+    where
+        F: FnOnce(Value) -> RuntimeResult<Value>,
+    {
+        let path = self.eval_path(member)?;
+
+        self.check_frozen(ds, || render_path(ds, &path))?;
+        self.update_in_place(ds, |_, root| {
+            let slot = path_slot(root, &path)?;
+            let old = slot.clone();
+            let new = update(old.clone())?;
+            *slot = new.clone();
+            Ok((old, new))
+        })
+    }
+
+    // Point(x=1, y=2): named args fill fields, the rest fall back to defaults
+    pub(crate) fn construct_record(&mut self, rt: &RecordType, mut args: HashMap<String, Value>) -> RuntimeResult<Value> {
+        let mut fields: Vec<(String, Value)> = Vec::with_capacity(rt.fields.len());
+
+        for (field, default) in &rt.fields {
+            let value = match (args.remove(field), default) {
+                (Some(v), _) => v,
+                (None, Some(expr)) => self.eval(expr)?,
+                (None, None) => {
+                    return Err(RuntimeError::custom(format!(
+                        "Missing field '{}' for record '{}'",
+                        field, rt.name
+                    )));
+                }
+            };
+            fields.push((field.clone(), value));
+        }
+
+        if let Some(extra) = args.keys().next() {
+            return Err(RuntimeError::custom(format!(
+                "Record '{}' has no field '{}'",
+                rt.name, extra
+            )));
+        }
+
+        Ok(Value::Record(Record {
+            name: rt.name.clone(),
+            fields,
+        }))
+    }
+}
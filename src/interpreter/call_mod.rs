@@ -0,0 +1,325 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::interpreter::{RuntimeError, RuntimeErrorKind, RuntimeResult};
+use crate::library::call_buildin::is_mutating_builtin;
+use crate::library::methods::lookup_method;
+use crate::parser::ast::{Expr, Pattern, Stmt};
+use crate::values::values::{Function, Value};
+use std::collections::HashMap;
+
+impl Interpreter {
+    // Runs a callable label with already-evaluated named args. When `keep`
+    // names one of its parameters, that parameter's final value is returned
+    // alongside the result.
+    pub(crate) fn call_label(
+        &mut self,
+        function: &str,
+        func: &Function,
+        evaluated_args: &HashMap<String, Value>,
+        keep: Option<&str>,
+    ) -> RuntimeResult<(Value, Option<Value>)> {
+        if func.generator {
+            return Ok((self.make_generator(function, func, evaluated_args)?, None));
+        }
+
+        self.init_visible_blocks(function, func)?;
+
+        // Set the current function context (for access control)
+        let previous_context = self.current_function_context.clone();
+        self.current_function_context = Some(func.visible_blocks.clone());
+
+        // New scope for function execution
+        let depth = self.environment.depth();
+        self.environment.push_scope();
+
+        // Inject visible block variables into the function scope
+        if let Err(err) = self.inject_visible(&func.visible_blocks, false) {
+            let result = self.unwind_to(depth, Err(err));
+            self.current_function_context = previous_context;
+            return result;
+        }
+
+        // Map call-site args (evaluated_args) to internal parameter names
+        // func.params: external param names
+        // func.defaults: internal variable names or patterns
+        for (i, external_param) in func.params.iter().enumerate() {
+            let internal_name = &func.defaults[i];
+
+            if let Some(arg_value) = evaluated_args.get(external_param) {
+                if let Err(err) = self.bind_pattern(internal_name, arg_value.clone(), false) {
+                    let result = self.unwind_to(depth, Err(err));
+                    self.current_function_context = previous_context;
+                    return result;
+                }
+            } else {
+                // Missing required parameter
+                let result = self.unwind_to(depth, Err(RuntimeError::custom(format!(
+                    "Missing required parameter '{}' in function '{}'",
+                    external_param, function
+                ))));
+                self.current_function_context = previous_context;
+                return result;
+            }
+        }
+
+        // Execute function body. `expr?` on nil ends the label early; any
+        // other error leaves it after its scopes are closed.
+        if let Err(err) = self.run_body(&func.body) {
+            let result = self.unwind_to(depth, Err(err));
+            self.current_function_context = previous_context;
+            return result;
+        }
+
+        // Save back modifications to visible block vars
+        self.save_visible(&func.visible_blocks);
+
+        // Read back the parameter the caller asked to keep (a method's receiver)
+        let kept = keep
+            .and_then(|external| func.params.iter().position(|p| p == external))
+            .and_then(|i| match &func.defaults[i] {
+                Pattern::Name(name) => self.environment.get(name).ok(),
+                _ => None,
+            });
+
+        // Pop scope (running deferred work) + restore context
+        let result = self.unwind_to(depth, Ok((Value::Nil, kept)));
+        self.current_function_context = previous_context;
+
+        result
+
+    }
+
+    // Evaluates, on first use, the visible blocks a label visits
+    pub(crate) fn init_visible_blocks(&mut self, function: &str, func: &Function) -> RuntimeResult<()> {
+        for visible_block_name in &func.visible_blocks {
+            if !self.visible.contains_key(visible_block_name) {
+                return Err(RuntimeError::custom(format!(
+                    "Label '{}' references undefined visible block '{}'",
+                    function, visible_block_name
+                )));
+            }
+
+            let is_initialized = self
+                .initialized_visible
+                .get(visible_block_name)
+                .copied()
+                .unwrap_or(false);
+
+            if !is_initialized {
+                let block_def = self.visible_definitions.get(visible_block_name).cloned();
+
+                if let Some(block_def) = block_def {
+                    // temp scope for init expressions
+                    let value_map = self.scoped(|this| {
+                        let mut value_map: HashMap<String, Value> = HashMap::new();
+                        for (var_name, var_expr) in &block_def {
+                            let value = this.eval(var_expr)?;
+                            value_map.insert(var_name.clone(), value);
+                        }
+                        Ok(value_map)
+                    })?;
+
+                    self.visible.insert(visible_block_name.clone(), value_map);
+                    self.initialized_visible
+                        .insert(visible_block_name.clone(), true);
+                } else {
+                    return Err(RuntimeError::custom(format!(
+                        "Visible block '{}' is declared but has no definition",
+                        visible_block_name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Binds the variables of the visited blocks in the label's scope
+    pub(crate) fn inject_visible(&mut self, blocks: &[String], local: bool) -> RuntimeResult<()> {
+        for visible_block_name in blocks {
+            if let Some(variables) = self.visible.get(visible_block_name) {
+                for (var_name, value) in variables {
+                    if local {
+                        self.environment.define_local(var_name, value.clone());
+                    } else {
+                        self.environment.define(var_name, value.clone())?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Writes the label's copies of block variables back to their blocks
+    pub(crate) fn save_visible(&mut self, blocks: &[String]) {
+        for visible_block_name in blocks {
+            if let Some(variables) = self.visible.get_mut(visible_block_name) {
+                // clone keys to avoid borrow issues
+                let keys: Vec<String> = variables.keys().cloned().collect();
+                for var_name in keys {
+                    if let Ok(new_value) = self.environment.get(&var_name) {
+                        variables.insert(var_name, new_value);
+                    }
+                }
+            }
+        }
+    }
+
+    // Executes a label body, treating a nil propagated by `?` or a change
+    // vetoed by a watcher as its end
+    pub(crate) fn run_body(&mut self, body: &[Stmt]) -> RuntimeResult<()> {
+        match self.run_block(body) {
+            Err(err) if matches!(
+                err.kind,
+                RuntimeErrorKind::NilPropagated | RuntimeErrorKind::ChangeVetoed { .. }
+            ) => Ok(()),
+            other => other,
+        }
+    }
+
+    // Executes an expanded macro body. A trailing expression is its value;
+    // `?` is not caught here, it leaves the label the macro was used in.
+    pub(crate) fn run_macro_body(&mut self, body: &[Stmt]) -> RuntimeResult<Value> {
+        let Some((last, init)) = body.split_last() else {
+            return Ok(Value::Nil);
+        };
+        for stmt in init {
+            self.execute(stmt)?;
+        }
+        match last {
+            Stmt::Expression(expr) => self.eval(expr),
+            stmt => self.execute(stmt).map(|_| Value::Nil),
+        }
+    }
+
+    // Runs a mutating builtin on the variable `var` itself. The value is moved
+    // out of the environment for the call so its storage is not shared and
+    // Rc::make_mut can change it without copying.
+    pub(crate) fn call_builtin_on_variable(
+        &mut self,
+        name: &str,
+        var: &str,
+        args: &HashMap<String, Value>,
+    ) -> Option<RuntimeResult<Value>> {
+        if let Err(err) = self.check_frozen(var, || var.to_string()) {
+            return Some(Err(err));
+        }
+        self.update_in_place(var, |this, target| this.call_builtin_mut(name, target, args).transpose())
+            .transpose()
+    }
+
+    // recv.method(args): record receivers call the label `Type.method`,
+    // other values are routed to the matching library builtin.
+    pub(crate) fn call_method(
+        &mut self,
+        receiver: &Expr,
+        method: &str,
+        args: &[(Option<String>, Expr)],
+    ) -> RuntimeResult<Value> {
+        let recv = self.eval(receiver)?;
+
+        let mut positional: Vec<Value> = Vec::new();
+        let mut named: HashMap<String, Value> = HashMap::new();
+        for (name, expr) in args {
+            let value = self.eval(expr)?;
+            match name {
+                Some(name) => {
+                    named.insert(name.clone(), value);
+                }
+                None => positional.push(value),
+            }
+        }
+
+        if let Value::Record(rec) = &recv {
+            let label = format!("{}.{}", rec.name, method);
+            let func = match self.environment.get(&label) {
+                Ok(Value::Function(func)) => func,
+                _ => {
+                    return Err(RuntimeError::custom(format!(
+                        "Record '{}' has no method '{}'",
+                        rec.name, method
+                    )));
+                }
+            };
+
+            // Positional args fill the label's parameters after `src`
+            let params: Vec<&String> = func.params.iter().filter(|p| *p != "src").collect();
+            bind_positional(&mut named, positional, &params, &label)?;
+            named.insert("src".to_string(), recv.clone());
+
+            let (result, receiver_after) = self.call_label(&label, &func, &named, Some("src"))?;
+
+            // Changes the method made to `src` are written back to the
+            // receiver; a method that changed nothing also works on a constant
+            if let Some(updated) = receiver_after.filter(|after| *after != recv) {
+                match receiver {
+                    Expr::Variable { name } => {
+                        self.check_frozen(name, || name.clone())?;
+                        self.assign(name, updated)?
+                    }
+                    Expr::Access { ds, member } => self.assign_access(ds, member, updated)?,
+                    _ => {}
+                }
+            }
+            return Ok(result);
+        }
+
+        let Some(sig) = lookup_method(&recv, method) else {
+            return Err(RuntimeError::custom(format!(
+                "'{}' has no method '{}'",
+                recv.type_name(),
+                method
+            )));
+        };
+
+        bind_positional(&mut named, positional, sig.params, method)?;
+
+        // xs.push(v) on a variable mutates it in place (or fails when the
+        // variable is constant or locked)
+        let in_place = match receiver {
+            Expr::Variable { name } if sig.receiver == "src" && is_mutating_builtin(sig.builtin) => {
+                Some(name)
+            }
+            _ => None,
+        };
+
+        let result = match in_place {
+            Some(var) => {
+                // Release our copy so the variable holds the only reference
+                drop(recv);
+                self.call_builtin_on_variable(sig.builtin, var, &named)
+            }
+            None => {
+                named.insert(sig.receiver.to_string(), recv);
+                self.call_builtin(sig.builtin, &named)
+            }
+        };
+
+        match result {
+            Some(result) => result,
+            None => Err(RuntimeError::custom(format!(
+                "Method '{}' is missing an argument (expects {})",
+                method,
+                sig.params.join(", ")
+            ))),
+        }
+    }
+}
+
+fn bind_positional<S: AsRef<str>>(
+    named: &mut HashMap<String, Value>,
+    positional: Vec<Value>,
+    params: &[S],
+    method: &str,
+) -> RuntimeResult<()> {
+    if positional.len() > params.len() {
+        return Err(RuntimeError::custom(format!(
+            "Method '{}' takes {} positional argument(s), got {}",
+            method,
+            params.len(),
+            positional.len()
+        )));
+    }
+    for (param, value) in params.iter().zip(positional) {
+        named.insert(param.as_ref().to_string(), value);
+    }
+    Ok(())
+}
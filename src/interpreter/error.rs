@@ -1,4 +1,5 @@
 use std::fmt;
+use std::ops::{Deref, DerefMut};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum RuntimeErrorKind {
@@ -10,8 +11,10 @@ pub enum RuntimeErrorKind {
     CannotReassignConstant(String),
     CannotReassignSmartLocked(String),
     CannotDeleteConstant(String),
-    CannotDeleteSmartLocked(String),
     CannotDeleteUndefined(String),
+    // A write inside a constant or locked value, named by its path
+    CannotModifyConstant(String),
+    CannotModifySmartLocked(String),
     
     // Type errors
     TypeMismatch {
@@ -50,36 +53,68 @@ pub enum RuntimeErrorKind {
     SmartKillFailed(String),
     SmartReviveFailed(String),
     SmartConstFailed(String),
+
+    // Control flow: `expr?` met nil and is leaving the current label
+    NilPropagated,
+    // Control flow: `jump` is unwinding to the block that defines the label
+    Jump(String),
+    // `veto(reason=...)`, raised inside a watcher
+    Vetoed(String),
+    // Control flow: a watcher vetoed a change and the label that made it is
+    // leaving without it
+    ChangeVetoed {
+        name: String,
+        watcher: String,
+        reason: String,
+    },
     
     // General errors
     Custom(String),
 }
 
+// Boxed so that every RuntimeResult stays one pointer wide on the Err side
 #[derive(Debug, Clone)]
-pub struct RuntimeError {
+pub struct RuntimeError(Box<ErrorInfo>);
+
+#[derive(Debug, Clone)]
+pub struct ErrorInfo {
     pub kind: RuntimeErrorKind,
     pub line: Option<usize>,
     pub column: Option<usize>,
     pub context: Option<String>,
 }
 
+impl Deref for RuntimeError {
+    type Target = ErrorInfo;
+
+    fn deref(&self) -> &ErrorInfo {
+        &self.0
+    }
+}
+
+impl DerefMut for RuntimeError {
+    fn deref_mut(&mut self) -> &mut ErrorInfo {
+        &mut self.0
+    }
+}
+
 impl RuntimeError {
     pub fn new(kind: RuntimeErrorKind) -> Self {
-        Self {
+        Self(Box::new(ErrorInfo {
             kind,
             line: None,
             column: None,
             context: None,
-        }
+        }))
     }
 
     pub fn with_location(kind: RuntimeErrorKind, line: usize, column: usize) -> Self {
-        Self {
+        Self(Box::new(ErrorInfo {
             kind,
             line: Some(line),
             column: Some(column),
             context: None,
-        }
+        }))
     }
 
     pub fn with_context(mut self, context: impl Into<String>) -> Self {
@@ -108,12 +143,16 @@ impl RuntimeError {
         Self::new(RuntimeErrorKind::CannotReassignSmartLocked(name.into()))
     }
 
-    pub fn cannot_delete_constant(name: impl Into<String>) -> Self {
-        Self::new(RuntimeErrorKind::CannotDeleteConstant(name.into()))
+    pub fn cannot_modify_constant(path: impl Into<String>) -> Self {
+        Self::new(RuntimeErrorKind::CannotModifyConstant(path.into()))
+    }
+
+    pub fn cannot_modify_smart_locked(path: impl Into<String>) -> Self {
+        Self::new(RuntimeErrorKind::CannotModifySmartLocked(path.into()))
     }
 
-    pub fn cannot_delete_smart_locked(name: impl Into<String>) -> Self {
-        Self::new(RuntimeErrorKind::CannotDeleteSmartLocked(name.into()))
+    pub fn cannot_delete_constant(name: impl Into<String>) -> Self {
+        Self::new(RuntimeErrorKind::CannotDeleteConstant(name.into()))
     }
 
     pub fn type_mismatch(expected: impl Into<String>, got: impl Into<String>, operation: impl Into<String>) -> Self {
@@ -136,6 +175,22 @@ impl RuntimeError {
         Self::new(RuntimeErrorKind::DivisionByZero)
     }
 
+    pub fn nil_propagated() -> Self {
+        Self::new(RuntimeErrorKind::NilPropagated)
+    }
+
+    pub fn jump(label: impl Into<String>) -> Self {
+        Self::new(RuntimeErrorKind::Jump(label.into()))
+    }
+
+    // The label a `jump` in flight is heading for
+    pub fn jump_target(&self) -> Option<&str> {
+        match &self.kind {
+            RuntimeErrorKind::Jump(label) => Some(label),
+            _ => None,
+        }
+    }
+
     pub fn custom(message: impl Into<String>) -> Self {
         Self::new(RuntimeErrorKind::Custom(message.into()))
     }
@@ -173,12 +228,15 @@ impl fmt::Display for RuntimeError {
             RuntimeErrorKind::CannotDeleteConstant(name) => {
                 write!(f, "Cannot delete constant variable '{}'", name)?;
             }
-            RuntimeErrorKind::CannotDeleteSmartLocked(name) => {
-                write!(f, "Cannot delete smart-locked variable '{}'", name)?;
-            }
             RuntimeErrorKind::CannotDeleteUndefined(name) => {
                 write!(f, "Cannot delete undefined variable '{}'", name)?;
             }
+            RuntimeErrorKind::CannotModifyConstant(path) => {
+                write!(f, "Cannot modify constant value '{}'", path)?;
+            }
+            RuntimeErrorKind::CannotModifySmartLocked(path) => {
+                write!(f, "Cannot modify smart-locked value '{}'", path)?;
+            }
             RuntimeErrorKind::TypeMismatch { expected, got, operation } => {
                 write!(f, "Type mismatch in '{}': expected {}, got {}", operation, expected, got)?;
             }
@@ -227,6 +285,18 @@ impl fmt::Display for RuntimeError {
             RuntimeErrorKind::SmartConstFailed(msg) => {
                 write!(f, "Smart const failed: {}", msg)?;
             }
+            RuntimeErrorKind::NilPropagated => {
+                write!(f, "'?' found nil outside of a label")?;
+            }
+            RuntimeErrorKind::Jump(label) => {
+                write!(f, "jump to '{}' left the blocks that can see it", label)?;
+            }
+            RuntimeErrorKind::Vetoed(reason) => {
+                write!(f, "veto outside of a watcher: {}", reason)?;
+            }
+            RuntimeErrorKind::ChangeVetoed { name, watcher, reason } => {
+                write!(f, "Change to '{}' vetoed by '{}': {}", name, watcher, reason)?;
+            }
             RuntimeErrorKind::Custom(msg) => {
                 write!(f, "{}", msg)?;
             }
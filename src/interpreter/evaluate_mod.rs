@@ -1,7 +1,11 @@
 use crate::interpreter::interpreter::Interpreter;
 use crate::interpreter::{RuntimeError, RuntimeErrorKind, RuntimeResult};
 use crate::lexer::TokenKind;
+use crate::library::call_buildin::is_mutating_builtin;
 use crate::parser::ast::Expr;
+use crate::values::axis::Axis;
+use crate::values::range::Range;
+use crate::values::set::{Set, SetKey};
 use crate::values::values::Value;
 use std::collections::HashMap;
 
@@ -10,15 +14,19 @@ impl Interpreter {
         match expr {
             Expr::_Literal_(lit) => Ok(self.literal_to_value(lit)),
             Expr::Grouping(inner) => self.eval(inner),
-            Expr::MacroCall { var, body } => {
-                for item in var.iter() {
-                    self.eval(item)?;
-                }
-                for stmt in body.iter() {
-                    self.execute(stmt)?;
+            Expr::MacroCall { params, args, body } => {
+                // Arguments are evaluated where the macro is used
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    values.push(self.eval(arg)?);
                 }
 
-                Ok(Value::Bool(true))
+                self.scoped(|this| {
+                    for (param, value) in params.iter().zip(values) {
+                        this.environment.define_local(param, value);
+                    }
+                    this.run_macro_body(body)
+                })
             }
 
 
@@ -28,7 +36,7 @@ impl Interpreter {
                     let value = self.eval(element)?;
                     arr.push(value);
                 }
-                Ok(Value::Array(arr))
+                Ok(Value::array(arr))
             }
 
             Expr::Axis { elements } => {
@@ -37,120 +45,24 @@ impl Interpreter {
                     let value = self.eval(element)?;
                     axis.push(value);
                 }
-                Ok(Value::Axis(axis))
+                Ok(Value::Axis(Axis::from_values(axis)?))
             }
 
-            #[allow(clippy::needless_return)]
-            Expr::Access { ds, member } => {
-                // Start from the root value
-                let mut current = self.environment.get(ds)?;
-
-                // Helper: convert Value -> dictionary key string
-                let to_dict_key = |v: Value| -> RuntimeResult<String> {
-                    Ok(match v {
-                        Value::Int(i) => i.to_string(),
-                        Value::String(s) => s,
-                        Value::Float(f) => f.to_string(),
-                        Value::Bool(b) => b.to_string(),
-                        Value::Char(ch) => ch.to_string(),
-                        Value::BigInt(bi) => bi, // assuming BigInt is stored as String in your Value
-                        _ => {
-                            return Err(RuntimeError::custom(format!(
-                                "Invalid key type for dictionary access: {}",
-                                v.type_name()
-                            )));
-                        }
-                    })
-                };
-
-                // Helper: resolve negative indexing
-                let resolve_index = |idx: i128, len: usize, what: &str| -> RuntimeResult<usize> {
-                    if len == 0 {
-                        return Err(RuntimeError::custom(format!("Index {} out of bounds for {} of length 0", idx, what)));
-                    }
-
-                    if idx < 0 {
-                        let l = len as i128;
-                        if idx.abs() > l {
-                            return Err(RuntimeError::custom(format!(
-                                "Index {} out of bounds for {} of length {}",
-                                idx, what, len
-                            )));
-                        }
-                        Ok((l + idx) as usize)
-                    } else {
-                        let u = idx as usize;
-                        if u >= len {
-                            return Err(RuntimeError::custom(format!(
-                                "Index {} out of bounds for {} of length {}",
-                                idx, what, len
-                            )));
-                        }
-                        Ok(u)
-                    }
-                };
-
-                for item in member.iter() {
-                    let accessor = self.eval(item)?;
-
-                    current = match current {
-                        Value::Dictionary(dict) => {
-                            let key = to_dict_key(accessor)?;
-                            dict.get(&key).cloned().ok_or_else(|| {
-                                RuntimeError::custom(format!("Key '{}' not found in dictionary", key))
-                            })?
-                        }
-
-                        Value::Array(arr) => {
-                            let idx = match accessor {
-                                Value::Int(i) => i,
-                                _ => {
-                                    return Err(RuntimeError::custom(format!(
-                                        "Array index must be integer, got {}",
-                                        accessor.type_name()
-                                    )));
-                                }
-                            };
-
-                            let actual = resolve_index(idx, arr.len(), "array")?;
-                            arr.get(actual).cloned().ok_or_else(|| {
-                                RuntimeError::custom(format!(
-                                    "Index {} out of bounds for array of length {}",
-                                    idx, arr.len()
-                                ))
-                            })?
-                        }
-
-                        Value::Axis(axis) => {
-                            let idx = match accessor {
-                                Value::Int(i) => i,
-                                _ => {
-                                    return Err(RuntimeError::custom(format!(
-                                        "Axis index must be integer, got {}",
-                                        accessor.type_name()
-                                    )));
-                                }
-                            };
-
-                            let actual = resolve_index(idx, axis.len(), "axis")?;
-                            axis.get(actual).cloned().ok_or_else(|| {
-                                RuntimeError::custom(format!(
-                                    "Index {} out of bounds for axis of length {}",
-                                    idx, axis.len()
-                                ))
-                            })?
-                        }
-
-                        other => {
-                            return Err(RuntimeError::custom(format!(
-                                "Cannot access member on type '{}'",
-                                other.type_name()
-                            )));
-                        }
-                    };
+            Expr::Set { elements } => {
+                let mut set = Set::new();
+                for element in elements {
+                    let value = self.eval(element)?;
+                    set.insert(SetKey::from_value(value)?);
                 }
+                Ok(Value::set(set))
+            }
+
+            Expr::Access { ds, member } => self.read_access(ds, member),
 
-                Ok(current)
+            Expr::AssignAccess { ds, member, val } => {
+                let val = self.eval(val)?;
+                self.assign_access(ds, member, val)?;
+                Ok(Value::Nil)
             }
 
 
@@ -182,15 +94,17 @@ impl Interpreter {
                     dict_map.insert(key_str, value);
                 }
 
-                Ok(Value::Dictionary(dict_map))
+                Ok(Value::dict(dict_map))
             }
 
-            Expr::Iterable { value } => {
-                let mut out = Vec::new();
-                for e in value {
-                    out.push(Value::Int(*e));
-                }
-                Ok(Value::Array(out))
+            Expr::Range { start, end, step, inclusive } => {
+                let start = self.range_bound(start, "start")?;
+                let end = self.range_bound(end, "end")?;
+                let step = match step {
+                    Some(step) => Some(self.range_bound(step, "step")?),
+                    None => None,
+                };
+                Ok(Value::Range(Range::new(start, end, step, *inclusive)?))
             }
 
             Expr::Unary { operator, right } => {
@@ -199,7 +113,11 @@ impl Interpreter {
                 match operator.kind {
                     TokenKind::Minus => match value {
                         Value::Float(n) => Ok(Value::Float(-n)),
-                        Value::Int(n) => Ok(Value::Int(-n)),
+                        Value::Int(n) => n
+                            .checked_neg()
+                            .map(Value::Int)
+                            .ok_or_else(|| RuntimeError::new(RuntimeErrorKind::IntegerOverflow)),
+                        Value::Axis(ax) => Ok(Value::Axis(ax.map(|x| -x, ax.integral))),
                         _ => Err(RuntimeError::new(RuntimeErrorKind::InvalidUnaryOperation {
                             operator: "-".to_string(),
                             operand_type: value.type_name().to_string(),
@@ -213,6 +131,19 @@ impl Interpreter {
                 }
             }
 
+            // The right side of `??` is only evaluated when the left is nil
+            Expr::Binary { left, operator, right } if operator.kind == TokenKind::QuestionQuestion => {
+                match self.eval(left)? {
+                    Value::Nil => self.eval(right),
+                    value => Ok(value),
+                }
+            }
+
+            Expr::Propagate(inner) => match self.eval(inner)? {
+                Value::Nil => Err(RuntimeError::nil_propagated()),
+                value => Ok(value),
+            },
+
             Expr::Binary {
                 left,
                 operator,
@@ -222,23 +153,11 @@ impl Interpreter {
                 let right_val = self.eval(right)?;
 
                 match operator.kind {
-                    TokenKind::Plus => Self::add(left_val, right_val),
-                    TokenKind::Minus => Self::num_op(left_val, right_val, |a, b| a - b, "-"),
-                    TokenKind::Star => Self::num_op(left_val, right_val, |a, b| a * b, "*"),
-                    TokenKind::Slash => {
-                        // Check for division by zero
-                        let is_zero = match &right_val {
-                            Value::Int(0) => true,
-                            Value::Float(f) if *f == 0.0 => true,
-                            _ => false,
-                        };
-
-                        if is_zero {
-                            return Err(RuntimeError::division_by_zero());
-                        }
-
-                        Self::num_op(left_val, right_val, |a, b| a / b, "/")
-                    }
+                    TokenKind::Plus
+                    | TokenKind::Minus
+                    | TokenKind::Star
+                    | TokenKind::Slash
+                    | TokenKind::Percent => Self::arith(operator.kind, left_val, right_val),
                     TokenKind::EqualEqual => Ok(Value::Bool(left_val == right_val)),
                     TokenKind::BangEqual => Ok(Value::Bool(left_val != right_val)),
                     TokenKind::Greater => Self::cmp(left_val, right_val, |a, b| a > b, ">"),
@@ -266,9 +185,33 @@ impl Interpreter {
                 }
             }
 
+            Expr::CompoundAssign { ds, member, operator, val, postfix } => {
+                let rhs = self.eval(val)?;
+                let (old, new) = if member.is_empty() {
+                    let old = self.environment.get(ds)?;
+                    let new = Self::arith(*operator, old.clone(), rhs)?;
+                    self.assign(ds, new.clone())?;
+                    (old, new)
+                } else {
+                    self.update_access(ds, member, |old| Self::arith(*operator, old, rhs))?
+                };
+                Ok(if *postfix { old } else { new })
+            }
+
             Expr::AllocateVariable { name, val } => {
                 let val = self.eval(val)?;
-                self.environment.define(name, val)?;
+                self.assign(name, val)?;
+                Ok(Value::Nil)
+            }
+
+            Expr::Match { subject, arms } => {
+                let value = self.eval(subject)?;
+                self.eval_match(value, arms)
+            }
+
+            Expr::Destructure { pattern, val } => {
+                let val = self.eval(val)?;
+                self.bind_pattern(pattern, val, false)?;
                 Ok(Value::Nil)
             }
 
@@ -318,15 +261,44 @@ impl Interpreter {
                 // --------------------------------------------
                 // 1) Evaluate call-site arguments FIRST
                 // --------------------------------------------
+                // A mutating builtin called on a variable updates it in place,
+                // so `src` is left out here and taken from the environment later
+                let in_place = match args.iter().find(|(arg_name, _)| arg_name == "src") {
+                    Some((_, Expr::Variable { name }))
+                        if is_mutating_builtin(function) && self.environment.exists(name) =>
+                    {
+                        Some(name.clone())
+                    }
+                    _ => None,
+                };
+
                 let mut evaluated_args: HashMap<String, Value> = HashMap::new();
                 for (arg_name, arg_expr) in args {
+                    if in_place.is_some() && arg_name == "src" {
+                        continue;
+                    }
                     let arg_value = self.eval(arg_expr)?;
                     evaluated_args.insert(arg_name.clone(), arg_value);
                 }
 
+                // array_* builtins take a generator as the array of what it yields
+                if function.starts_with("array_")
+                    && let Some(Value::Generator(generator)) = evaluated_args.get("src").cloned()
+                {
+                    let items = self.drain_generator(&generator, None)?;
+                    evaluated_args.insert("src".to_string(), Value::array(items));
+                }
+
                 // --------------------------------------------
                 // 2) Builtin/standard function check FIRST
                 // --------------------------------------------
+                if let Some(var) = &in_place {
+                    if let Some(result) = self.call_builtin_on_variable(function, var, &evaluated_args) {
+                        return result;
+                    }
+                    evaluated_args.insert("src".to_string(), self.environment.get(var)?);
+                }
+
                 if let Some(result) = self.call_builtin(function, &evaluated_args) {
                     return result;
                 }
@@ -337,107 +309,11 @@ impl Interpreter {
                 let func_value = self.environment.get(function)?;
 
                 match func_value {
-                    Value::Function(func) => {
-                        // === INITIALIZE VISIBLE BLOCKS FOR THIS FUNCTION ===
-                        for visible_block_name in &func.visible_blocks {
-                            if !self.visible.contains_key(visible_block_name) {
-                                return Err(RuntimeError::custom(format!(
-                                    "Label '{}' references undefined visible block '{}'",
-                                    function, visible_block_name
-                                )));
-                            }
-
-                            let is_initialized = self
-                                .initialized_visible
-                                .get(visible_block_name)
-                                .copied()
-                                .unwrap_or(false);
-
-                            if !is_initialized {
-                                let block_def = self.visible_definitions.get(visible_block_name).cloned();
-
-                                if let Some(block_def) = block_def {
-                                    // temp scope for init expressions
-                                    self.environment.push_scope();
-
-                                    let mut value_map: HashMap<String, Value> = HashMap::new();
-                                    for (var_name, var_expr) in &block_def {
-                                        let value = self.eval(var_expr)?;
-                                        value_map.insert(var_name.clone(), value);
-                                    }
-
-                                    self.environment.pop_scope();
-
-                                    self.visible.insert(visible_block_name.clone(), value_map);
-                                    self.initialized_visible
-                                        .insert(visible_block_name.clone(), true);
-                                } else {
-                                    return Err(RuntimeError::custom(format!(
-                                        "Visible block '{}' is declared but has no definition",
-                                        visible_block_name
-                                    )));
-                                }
-                            }
-                        }
-
-                        // Set the current function context (for access control)
-                        let previous_context = self.current_function_context.clone();
-                        self.current_function_context = Some(func.visible_blocks.clone());
-
-                        // New scope for function execution
-                        self.environment.push_scope();
-
-                        // Inject visible block variables into the function scope
-                        for visible_block_name in &func.visible_blocks {
-                            if let Some(variables) = self.visible.get(visible_block_name) {
-                                for (var_name, value) in variables {
-                                    self.environment.define(var_name, value.clone())?;
-                                }
-                            }
-                        }
-
-                        // Map call-site args (evaluated_args) to internal parameter names
-                        // func.params: external param names
-                        // func.defaults: internal variable names (as you described)
-                        for (i, external_param) in func.params.iter().enumerate() {
-                            let internal_name = &func.defaults[i];
-
-                            if let Some(arg_value) = evaluated_args.get(external_param) {
-                                self.environment.define(internal_name, arg_value.clone())?;
-                            } else {
-                                // Missing required parameter
-                                self.environment.pop_scope();
-                                self.current_function_context = previous_context;
-                                return Err(RuntimeError::custom(format!(
-                                    "Missing required parameter '{}' in function '{}'",
-                                    external_param, function
-                                )));
-                            }
-                        }
-
-                        // Execute function body
-                        for stmt in &func.body {
-                            self.execute(stmt)?;
-                        }
+                    Value::RecordType(rt) => self.construct_record(&rt, evaluated_args),
 
-                        // Save back modifications to visible block vars
-                        for visible_block_name in &func.visible_blocks {
-                            if let Some(variables) = self.visible.get_mut(visible_block_name) {
-                                // clone keys to avoid borrow issues
-                                let keys: Vec<String> = variables.keys().cloned().collect();
-                                for var_name in keys {
-                                    if let Ok(new_value) = self.environment.get(&var_name) {
-                                        variables.insert(var_name, new_value);
-                                    }
-                                }
-                            }
-                        }
-
-                        // Pop scope + restore context
-                        self.environment.pop_scope();
-                        self.current_function_context = previous_context;
-
-                        Ok(Value::Nil)
+                    Value::Function(func) => {
+                        let (result, _) = self.call_label(function, &func, &evaluated_args, None)?;
+                        Ok(result)
                     }
 
                     _ => Err(RuntimeError::custom(format!(
@@ -448,8 +324,20 @@ impl Interpreter {
                 }
             }
 
+            Expr::MethodCall { receiver, method, args } => self.call_method(receiver, method, args),
+        }
+    }
 
-            _ => Err(RuntimeError::custom("Unsupported expression")),
+    fn range_bound(&mut self, expr: &Expr, what: &str) -> RuntimeResult<i128> {
+        match self.eval(expr)? {
+            Value::Int(i) => Ok(i),
+            Value::UInt(u) => i128::try_from(u)
+                .map_err(|_| RuntimeError::custom(format!("Range {} is out of range", what))),
+            other => Err(RuntimeError::custom(format!(
+                "Range {} must be Int, got {}",
+                what,
+                other.type_name()
+            ))),
         }
     }
 }
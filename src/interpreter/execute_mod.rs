@@ -1,7 +1,8 @@
 use crate::interpreter::interpreter::Interpreter;
 use crate::parser::ast::Stmt;
-use crate::values::values::{Value, Function, ControlFlow};
+use crate::values::values::{Value, Function, ControlFlow, RecordType, Deferred};
 use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::generator::contains_yield;
 use std::collections::HashMap;
 
 impl Interpreter
@@ -13,31 +14,47 @@ impl Interpreter
                 Ok(())
             }
 
-            Stmt::SmartLock { variable } => {
-                let value = self.environment.get(variable)?;
-                self.environment.define_smart_lock(variable, value)?;
-                Ok(())
-            }
+            // These act on the binding the name resolves to, not on the
+            // current scope
+            Stmt::SmartLock { variable } => self.environment.lock(variable),
 
-            Stmt::SmartUnlock { variable } => {
-                let value = self.environment.get(variable)?;
-                self.environment.define_smart_unclock(variable, value)?;
-                Ok(())
-            }
+            Stmt::SmartUnlock { variable } => self.environment.unlock(variable),
 
-            Stmt::SmartKill { variable } => {
-                self.environment.delete_variable(variable)?;
-                Ok(())
+            Stmt::SmartKill { variable } => self.kill(variable),
+
+            Stmt::SmartRevive { variable, generation } => {
+                let generation = match generation {
+                    Some(expr) => match self.eval(expr)? {
+                        Value::Int(n) if n >= 0 => n as usize,
+                        Value::UInt(n) => n as usize,
+                        other => {
+                            return Err(RuntimeError::custom(format!(
+                                "_revive_ @ expects a non-negative integer, got {}",
+                                other.type_name()
+                            )));
+                        }
+                    },
+                    None => 0,
+                };
+                self.revive(variable, generation)
             }
 
-            Stmt::SmartRevive { variable } => {
-                self.environment.define(variable, Value::Nil)?;
+            Stmt::SmartConst { variable } => self.environment.make_constant(variable),
+
+            Stmt::SmartWatch { variable, label } => self.watch(variable, label),
+
+            Stmt::SmartUnwatch { variable, label } => self.environment.unwatch(variable, label.as_deref()),
+
+            Stmt::SmartScoped { variable } => {
+                if !self.environment.exists(variable) {
+                    return Err(RuntimeError::undefined_variable(variable));
+                }
+                self.environment.defer(Deferred::Kill(variable.clone()));
                 Ok(())
             }
 
-            Stmt::SmartConst { variable } => {
-                let value = self.environment.get(variable)?;
-                self.environment.define_constant(variable, value)?;
+            Stmt::Defer { body } => {
+                self.environment.defer(Deferred::Block(body.clone()));
                 Ok(())
             }
 
@@ -52,9 +69,7 @@ impl Interpreter
 
                 if condition_value.truthy() {
                     // Execute then branch
-                    for stmt in then_branch {
-                        self.execute(stmt)?;
-                    }
+                    self.run_block(then_branch)?;
                 } else {
                     // Check elif branches
                     let mut executed = false;
@@ -63,9 +78,7 @@ impl Interpreter
                         let elif_value = self.eval(elif_condition)?;
 
                         if elif_value.truthy() {
-                            for stmt in elif_body {
-                                self.execute(stmt)?;
-                            }
+                            self.run_block(elif_body)?;
                             executed = true;
                             break;
                         }
@@ -75,9 +88,7 @@ impl Interpreter
                     #[allow(clippy::collapsible_if)]
                     if !executed {
                         if let Some(else_body) = else_branch {
-                            for stmt in else_body {
-                                self.execute(stmt)?;
-                            }
+                            self.run_block(else_body)?;
                         }
                     }
                 }
@@ -100,41 +111,27 @@ impl Interpreter
                             name: label_name.clone(),
                             params,
                             defaults: args,
-                            body,
+                            generator: contains_yield(&body),
+                            body: body.into(),
                             visible_blocks: visible,
                         });
-                        self.environment.define(&label_name, func)?;
+                        self.assign(&label_name, func)?;
                     } else {
                         // Store control flow label in environment
                         let ctrl = Value::ControlFlow(ControlFlow {
                             name: label_name.clone(),
                             body,
                         });
-                        self.environment.define(&label_name, ctrl)?;
+                        self.assign(&label_name, ctrl)?;
                     }
                 }
                 Ok(())
             }
-            Stmt::Jump { jump } => {
-                // Get the target label from environment
-                let target_value = self.environment.get(jump)?;
-
-                match target_value {
-                    Value::ControlFlow(ctrl) => {
-                        // Execute the control flow label's body
-                        self.environment.push_scope();
-                        for stmt in &ctrl.body {
-                            self.execute(stmt)?;
-                        }
-                        self.environment.pop_scope();
-                        Ok(())
-                    }
-                    _ => Err(RuntimeError::custom(format!(
-                        "'{}' is not a valid jump target (must be a control flow label)",
-                        jump
-                    ))),
-                }
-            }
+            // Unwinds to the block defining the label, see run_block
+            Stmt::Jump { jump, .. } => Err(RuntimeError::jump(jump)),
+
+            // Inside a generator `yield` is handled by resume_generator
+            Stmt::Yield { .. } => Err(RuntimeError::custom("'yield' used outside of a label")),
 
             Stmt::Pass => {
                 // Do nothing - pass statement
@@ -143,54 +140,35 @@ impl Interpreter
 
             Stmt::For {
                 iterator,
+                value,
                 iterable,
                 body,
             } => {
                 // Evaluate iterable expression
                 let iter_val = self.eval(iterable)?;
+                let keyed = matches!(iter_val, Value::Dictionary(_));
+                let mut items = self.loop_iter(iter_val)?;
 
-                match iter_val {
-                    Value::Array(items) => {
-                        // For-loop runs in its own scope (optional but clean)
-                        self.environment.push_scope();
-
-                        for item in items {
-                            // Each iteration can get its own nested scope (optional).
-                            // If you want iterator variable to be updated in same scope, remove this push/pop.
-                            self.environment.push_scope();
-
-                            // Bind iterator variable
-                            self.environment.define(iterator, item)?;
+                // For-loop runs in its own scope, and each iteration in a
+                // nested one
+                self.scoped(|this| {
+                    while let Some((key, item)) = this.loop_next(&mut items)? {
+                        this.scoped(|this| {
+                            // Bind iterator variable(s)
+                            this.bind_loop_vars(iterator, value.as_ref(), keyed, key, item)?;
 
                             // Execute body
-                            for stmt in body {
-                                self.execute(stmt)?;
-                            }
-
-                            self.environment.pop_scope();
-                        }
-
-                        self.environment.pop_scope();
-                        Ok(())
+                            this.run_block(body)
+                        })?;
                     }
-
-                    _ => Err(RuntimeError::custom(format!(
-                        "For-loop expects an Array iterable, got {}",
-                        iter_val.type_name()
-                    ))),
-                }
+                    Ok(())
+                })
             }
 
             Stmt::While { condition, body } => {
                 // Keep looping while condition is truthy
                 while self.eval(condition)?.truthy() {
-                    self.environment.push_scope();
-
-                    for stmt in body {
-                        self.execute(stmt)?;
-                    }
-
-                    self.environment.pop_scope();
+                    self.scoped(|this| this.run_block(body))?;
                 }
                 Ok(())
             }
@@ -198,13 +176,7 @@ impl Interpreter
             Stmt::DoWhile { body, condition } => {
                 // Execute body at least once
                 loop {
-                    self.environment.push_scope();
-
-                    for stmt in body {
-                        self.execute(stmt)?;
-                    }
-
-                    self.environment.pop_scope();
+                    self.scoped(|this| this.run_block(body))?;
 
                     // Check condition after executing body
                     if !self.eval(condition)?.truthy() {
@@ -214,6 +186,15 @@ impl Interpreter
                 Ok(())
             }
 
+            Stmt::Record { name, fields } => {
+                let rt = Value::RecordType(RecordType {
+                    name: name.clone(),
+                    fields: fields.clone(),
+                });
+                self.assign(name, rt)?;
+                Ok(())
+            }
+
             Stmt::Visible { _name_, _block_ } => {
                 // Visible blocks are NOT executed in global scope
                 // They are only DEFINED here and will be initialized
@@ -0,0 +1,342 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::interpreter::{RuntimeError, RuntimeErrorKind, RuntimeResult};
+use crate::parser::ast::Stmt;
+use crate::interpreter::jump_mod::label_in;
+use crate::values::generator::{stmt_yields, Frame, GeneratorRef};
+use crate::values::values::{Function, Scope, Value};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+impl Interpreter {
+    // Calling a label that yields binds its visible block variables and
+    // its arguments and returns a generator; the body only starts running
+    // on the first resume.
+    pub(crate) fn make_generator(
+        &mut self,
+        function: &str,
+        func: &Function,
+        evaluated_args: &HashMap<String, Value>,
+    ) -> RuntimeResult<Value> {
+        self.init_visible_blocks(function, func)?;
+
+        let depth = self.environment.depth();
+        self.environment.push_scope();
+        if let Err(err) = self.inject_visible(&func.visible_blocks, true) {
+            self.environment.unwind_to(depth);
+            return Err(err);
+        }
+
+        for (external_param, internal_name) in func.params.iter().zip(&func.defaults) {
+            match evaluated_args.get(external_param) {
+                Some(arg_value) => {
+                    if let Err(err) = self.bind_pattern(internal_name, arg_value.clone(), true) {
+                        self.environment.unwind_to(depth);
+                        return Err(err);
+                    }
+                }
+                None => {
+                    self.environment.unwind_to(depth);
+                    return Err(RuntimeError::custom(format!(
+                        "Missing required parameter '{}' in function '{}'",
+                        external_param, function
+                    )));
+                }
+            }
+        }
+
+        let scopes = self.environment.split_off(depth);
+        let generator = GeneratorRef::new(
+            function,
+            Rc::clone(&func.body),
+            scopes,
+            func.visible_blocks.clone(),
+            Rc::clone(&self.abandoned),
+        );
+        self.generators.retain(|live| live.strong_count() > 0);
+        self.generators.push(Rc::downgrade(&generator.0));
+        Ok(Value::Generator(generator))
+    }
+
+    // A generator that never finishes still closes its scopes, running
+    // what its body deferred: when it is dropped (at the next statement
+    // boundary) or, if it is still around, when the script ends.
+    pub(crate) fn close_abandoned(&mut self) -> RuntimeResult<()> {
+        let mut result = Ok(());
+        loop {
+            let next = self.abandoned.borrow_mut().pop();
+            let Some((scopes, visible_blocks)) = next else {
+                return result;
+            };
+            let closed = self.close_scopes(scopes, visible_blocks);
+            if result.is_ok() {
+                result = closed;
+            }
+        }
+    }
+
+    pub(crate) fn close_generators(&mut self) -> RuntimeResult<()> {
+        let mut result = Ok(());
+        for generator in std::mem::take(&mut self.generators) {
+            let Some(generator) = generator.upgrade() else {
+                continue;
+            };
+            let (scopes, visible_blocks) = {
+                let mut state = generator.borrow_mut();
+                if state.done {
+                    continue;
+                }
+                state.done = true;
+                state.frames.clear();
+                (std::mem::take(&mut state.scopes), state.visible_blocks.clone())
+            };
+            let closed = self.close_scopes(scopes, visible_blocks);
+            if result.is_ok() {
+                result = closed;
+            }
+        }
+        let closed = self.close_abandoned();
+        result.and(closed)
+    }
+
+    fn close_scopes(&mut self, scopes: Vec<Scope>, visible_blocks: Vec<String>) -> RuntimeResult<()> {
+        let previous_context = self.current_function_context.replace(visible_blocks);
+        let depth = self.environment.depth();
+        self.environment.extend(scopes);
+        let result = self.unwind_to(depth, Ok(()));
+        self.current_function_context = previous_context;
+        result
+    }
+
+    // Runs the generator up to its next `yield`. Ok(None) once it is finished.
+    pub(crate) fn resume_generator(&mut self, generator: &GeneratorRef) -> RuntimeResult<Option<Value>> {
+        let (mut frames, scopes) = {
+            let mut state = generator.0.borrow_mut();
+            if state.done {
+                return Ok(None);
+            }
+            if state.running {
+                return Err(RuntimeError::custom(format!(
+                    "Generator '{}' is already running",
+                    state.name
+                )));
+            }
+            state.running = true;
+            (std::mem::take(&mut state.frames), std::mem::take(&mut state.scopes))
+        };
+        let visible_blocks = generator.0.borrow().visible_blocks.clone();
+
+        // The generator's own scopes sit on top of the caller's while it
+        // runs, with its visible blocks open to it as in a label call
+        let previous_context = self.current_function_context.replace(visible_blocks.clone());
+        let depth = self.environment.depth();
+        self.environment.extend(scopes);
+        let result = self.run_frames(&mut frames);
+        if result.is_ok() {
+            self.save_visible(&visible_blocks);
+        }
+        // A generator that is done closes its scopes here, running what
+        // they deferred; a suspended one takes them along
+        let result = match result {
+            Ok(Some(value)) => Ok(Some(value)),
+            finished => self.unwind_to(depth, finished),
+        };
+        let scopes = self.environment.split_off(depth);
+        self.current_function_context = previous_context;
+
+        let mut state = generator.0.borrow_mut();
+        state.running = false;
+        match result {
+            Ok(Some(value)) => {
+                state.frames = frames;
+                state.scopes = scopes;
+                Ok(Some(value))
+            }
+            // Finished, or left early with `?` on nil
+            Ok(None) => {
+                state.done = true;
+                Ok(None)
+            }
+            Err(err) if err.kind == RuntimeErrorKind::NilPropagated => {
+                state.done = true;
+                Ok(None)
+            }
+            Err(err) => {
+                state.done = true;
+                Err(err)
+            }
+        }
+    }
+
+    // Up to `limit` further values (all of them when None)
+    pub(crate) fn drain_generator(&mut self, generator: &GeneratorRef, limit: Option<usize>) -> RuntimeResult<Vec<Value>> {
+        let mut items = Vec::new();
+        while limit.is_none_or(|n| items.len() < n) {
+            match self.resume_generator(generator)? {
+                Some(item) => items.push(item),
+                None => break,
+            }
+        }
+        Ok(items)
+    }
+
+    fn run_frames(&mut self, frames: &mut Vec<Frame>) -> RuntimeResult<Option<Value>> {
+        loop {
+            let err = match self.step_frames(frames) {
+                Err(err) => err,
+                done => return done,
+            };
+            let Some(target) = err.jump_target().map(str::to_string) else {
+                return Err(err);
+            };
+            if !self.jump_frames(frames, &target)? {
+                return Err(err);
+            }
+        }
+    }
+
+    // A `jump` inside a generator: leaves frames (closing their scopes)
+    // until one whose block defines the label, then enters the label body
+    // as a new frame so it may yield too. False if no frame defines it.
+    fn jump_frames(&mut self, frames: &mut Vec<Frame>, target: &str) -> RuntimeResult<bool> {
+        while let Some(frame) = frames.last_mut() {
+            if let Frame::Block { body, pc, .. } = frame
+                && let Some((at, label_body)) = label_in(body, target)
+            {
+                let label_body = Rc::from(label_body);
+                *pc = at + 1;
+                self.environment.push_scope();
+                frames.push(Frame::Block { body: label_body, pc: 0, scoped: true });
+                return Ok(true);
+            }
+            match frames.pop() {
+                Some(Frame::Block { scoped: true, .. }) | Some(Frame::For { .. }) => self.pop_scope()?,
+                _ => {}
+            }
+        }
+        Ok(false)
+    }
+
+    fn step_frames(&mut self, frames: &mut Vec<Frame>) -> RuntimeResult<Option<Value>> {
+        while let Some(frame) = frames.last_mut() {
+            match frame {
+                Frame::Block { body, pc, scoped } => {
+                    let body = Rc::clone(body);
+                    let Some(stmt) = body.get(*pc) else {
+                        if *scoped {
+                            self.pop_scope()?;
+                        }
+                        frames.pop();
+                        continue;
+                    };
+                    *pc += 1;
+
+                    if !stmt_yields(stmt) {
+                        self.execute(stmt)?;
+                        continue;
+                    }
+
+                    match stmt {
+                        Stmt::Yield { value } => return Ok(Some(self.eval(value)?)),
+
+                        Stmt::If {
+                            condition,
+                            then_branch,
+                            elif_branches,
+                            else_branch,
+                        } => {
+                            let mut branch = None;
+                            if self.eval(condition)?.truthy() {
+                                branch = Some(then_branch);
+                            } else {
+                                for (elif_condition, elif_body) in elif_branches {
+                                    if self.eval(elif_condition)?.truthy() {
+                                        branch = Some(elif_body);
+                                        break;
+                                    }
+                                }
+                                branch = branch.or(else_branch.as_ref());
+                            }
+
+                            if let Some(branch) = branch {
+                                frames.push(Frame::Block {
+                                    body: Rc::from(branch.as_slice()),
+                                    pc: 0,
+                                    scoped: false,
+                                });
+                            }
+                        }
+
+                        Stmt::While { condition, body } => frames.push(Frame::While {
+                            condition: condition.clone(),
+                            body: Rc::from(body.as_slice()),
+                        }),
+
+                        Stmt::DoWhile { body, condition } => frames.push(Frame::DoWhile {
+                            condition: condition.clone(),
+                            body: Rc::from(body.as_slice()),
+                            started: false,
+                        }),
+
+                        Stmt::For {
+                            iterator,
+                            value,
+                            iterable,
+                            body,
+                        } => {
+                            let iter_val = self.eval(iterable)?;
+                            let keyed = matches!(iter_val, Value::Dictionary(_));
+                            let items = self.loop_iter(iter_val)?;
+                            self.environment.push_scope();
+                            frames.push(Frame::For {
+                                iterator: iterator.clone(),
+                                value: value.clone(),
+                                keyed,
+                                items,
+                                body: Rc::from(body.as_slice()),
+                            });
+                        }
+
+                        other => self.execute(other)?,
+                    }
+                }
+
+                Frame::While { condition, body } => {
+                    if self.eval(condition)?.truthy() {
+                        let body = Rc::clone(body);
+                        self.environment.push_scope();
+                        frames.push(Frame::Block { body, pc: 0, scoped: true });
+                    } else {
+                        frames.pop();
+                    }
+                }
+
+                Frame::DoWhile { condition, body, started } => {
+                    if !*started || self.eval(condition)?.truthy() {
+                        *started = true;
+                        let body = Rc::clone(body);
+                        self.environment.push_scope();
+                        frames.push(Frame::Block { body, pc: 0, scoped: true });
+                    } else {
+                        frames.pop();
+                    }
+                }
+
+                Frame::For { iterator, value, keyed, items, body } => match self.loop_next(items)? {
+                    Some((key, item)) => {
+                        self.environment.push_scope();
+                        self.bind_loop_vars(iterator, value.as_ref(), *keyed, key, item)?;
+                        let body = Rc::clone(body);
+                        frames.push(Frame::Block { body, pc: 0, scoped: true });
+                    }
+                    None => {
+                        // Close the scope the loop opened around its iterations
+                        self.pop_scope()?;
+                        frames.pop();
+                    }
+                },
+            }
+        }
+
+        Ok(None)
+    }
+}
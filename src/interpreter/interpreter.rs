@@ -1,8 +1,14 @@
 use crate::interpreter::error::{RuntimeError, RuntimeErrorKind, RuntimeResult};
+use crate::lexer::TokenKind;
 use crate::parser::ast::{Expr, Literal, Stmt};
-use crate::values::values::{ControlFlow, Environment, Function, Value};
-use std::collections::HashMap;
+use crate::values::axis::{check_exact, Axis};
+use crate::values::generator::{Abandoned, Generator};
+use crate::values::values::{Environment, Value};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Weak;
 
+use crate::library::rand_utils::Rng;
 use crate::stdvars::stander_variables::define_std_vars;
 
 #[derive(Debug)]
@@ -16,6 +22,15 @@ pub struct Interpreter {
     pub(crate) visible_definitions: HashMap<String, Vec<(String, Expr)>>,
     // Track the current function context (to enforce visible block access)
     pub(crate) current_function_context: Option<Vec<String>>, // Current function's allowed visible blocks
+    // Pseudo-random generator shared by the rand_* builtins (seeded on first use)
+    pub(crate) rng: Option<Rng>,
+    // Variables whose watchers are running; changes made by a watcher
+    // itself are not reported again
+    pub(crate) notifying: HashSet<String>,
+    // Generators made so far, closed at the end of the script if still
+    // suspended, and the scopes of those dropped before then
+    pub(crate) generators: Vec<Weak<RefCell<Generator>>>,
+    pub(crate) abandoned: Abandoned,
 }
 
 impl Interpreter {
@@ -30,14 +45,21 @@ impl Interpreter {
             initialized_visible: HashMap::new(),
             visible_definitions: HashMap::new(),
             current_function_context: None,
+            rng: None,
+            notifying: HashSet::new(),
+            generators: Vec::new(),
+            abandoned: Abandoned::default(),
         }
     }
 
     pub fn interpret(&mut self, statements: &[Stmt]) -> RuntimeResult<()> {
-        for stmt in statements {
-            self.execute(stmt)?;
-        }
-        Ok(())
+        let result = self.run_block(statements);
+        // A script is the outermost block: what it deferred at the top
+        // level runs when it ends, after what its unfinished generators did
+        let result = self.unwind_to(1, result);
+        let closed = self.close_generators();
+        let finished = self.run_deferred();
+        result.and(closed).and(finished)
     }
 
 
@@ -55,11 +77,15 @@ impl Interpreter {
 
     pub(crate) fn add(left: Value, right: Value) -> RuntimeResult<Value> {
         match (&left, &right) {
-            (Value::Int(a), Value::Int(b)) => Ok(Value::Int(a + b)),
+            (Value::Int(a), Value::Int(b)) => a
+                .checked_add(*b)
+                .map(Value::Int)
+                .ok_or_else(|| RuntimeError::new(RuntimeErrorKind::IntegerOverflow)),
             (Value::Float(a), Value::Float(b)) => Ok(Value::Float(a + b)),
             (Value::Int(a), Value::Float(b)) => Ok(Value::Float(*a as f64 + b)),
             (Value::Float(a), Value::Int(b)) => Ok(Value::Float(a + *b as f64)),
             (Value::String(a), Value::String(b)) => Ok(Value::String(format!("{}{}", a, b))),
+            (Value::Axis(_), _) | (_, Value::Axis(_)) => Self::axis_op(left, right, |a, b| a + b, true, "+"),
             _ => Err(RuntimeError::invalid_binary_op(
                 "+",
                 left.type_name(),
@@ -68,6 +94,52 @@ impl Interpreter {
         }
     }
 
+    // The arithmetic operators shared by binary expressions and compound
+    // assignment (`x += 1`, `xs[i]++`)
+    pub(crate) fn arith(op: TokenKind, left: Value, right: Value) -> RuntimeResult<Value> {
+        match op {
+            TokenKind::Plus => Self::add(left, right),
+            TokenKind::Minus => Self::int_or_num(left, right, i128::checked_sub, |a, b| a - b, "-"),
+            TokenKind::Star => Self::int_or_num(left, right, i128::checked_mul, |a, b| a * b, "*"),
+            TokenKind::Slash | TokenKind::Percent => {
+                // Check for division by zero
+                let is_zero = match &right {
+                    Value::Int(0) => true,
+                    Value::Float(f) if *f == 0.0 => true,
+                    Value::Axis(ax) => ax.data.contains(&0.0),
+                    _ => false,
+                };
+
+                if is_zero {
+                    return Err(RuntimeError::division_by_zero());
+                }
+
+                if op == TokenKind::Slash {
+                    Self::num_op(left, right, |a, b| a / b, "/")
+                } else {
+                    Self::int_or_num(left, right, i128::checked_rem, |a, b| a % b, "%")
+                }
+            }
+            _ => Err(RuntimeError::custom(format!(
+                "Unsupported arithmetic operator: {:?}",
+                op
+            ))),
+        }
+    }
+
+    // Int op Int stays an Int (raising on overflow); anything else goes through num_op
+    fn int_or_num<F>(left: Value, right: Value, int_op: fn(i128, i128) -> Option<i128>, op: F, op_str: &str) -> RuntimeResult<Value>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        match (&left, &right) {
+            (Value::Int(a), Value::Int(b)) => int_op(*a, *b)
+                .map(Value::Int)
+                .ok_or_else(|| RuntimeError::new(RuntimeErrorKind::IntegerOverflow)),
+            _ => Self::num_op(left, right, op, op_str),
+        }
+    }
+
     pub(crate) fn num_op<F>(left: Value, right: Value, op: F, op_str: &str) -> RuntimeResult<Value>
     where
         F: Fn(f64, f64) -> f64,
@@ -77,6 +149,32 @@ impl Interpreter {
             (Value::Int(a), Value::Int(b)) => Ok(Value::Float(op(*a as f64, *b as f64))),
             (Value::Int(a), Value::Float(b)) => Ok(Value::Float(op(*a as f64, *b))),
             (Value::Float(a), Value::Int(b)) => Ok(Value::Float(op(*a, *b as f64))),
+            (Value::Axis(_), _) | (_, Value::Axis(_)) => {
+                let keep_integral = op_str != "/";
+                Self::axis_op(left, right, op, keep_integral, op_str)
+            }
+            _ => Err(RuntimeError::invalid_binary_op(
+                op_str,
+                left.type_name(),
+                right.type_name(),
+            )),
+        }
+    }
+
+    // Element-wise arithmetic where at least one side is an Axis; scalars
+    // broadcast across every element.
+    pub(crate) fn axis_op<F>(left: Value, right: Value, op: F, keep_integral: bool, op_str: &str) -> RuntimeResult<Value>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        check_exact(&left)?;
+        check_exact(&right)?;
+        let as_axis = |v: &Value| match v {
+            Value::Axis(ax) => Some(ax.clone()),
+            other => Axis::scalar(other),
+        };
+        match (as_axis(&left), as_axis(&right)) {
+            (Some(a), Some(b)) => Ok(a.broadcast(&b, op, keep_integral, op_str)?.into_value()),
             _ => Err(RuntimeError::invalid_binary_op(
                 op_str,
                 left.type_name(),
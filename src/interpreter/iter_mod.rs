@@ -0,0 +1,152 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::interpreter::{RuntimeError, RuntimeResult};
+use crate::library::dict_utils::sorted_keys;
+use crate::parser::ast::Pattern;
+use crate::values::generator::GeneratorRef;
+use crate::values::values::{Record, Value};
+
+// What a `for` loop walks over. Built-in values yield (key or index,
+// element) pairs; a record whose type defines a `next` label is a
+// user-defined iterable:
+//
+//   record Countdown { n = 3, value = nil }
+//   label visit[] Countdown.next(src=self) { ... self.value = ... }
+//
+// Each step calls `next` on the record, which must set its `value` field
+// to the next element, or to nil when it is finished. Generators (labels
+// that `yield`) are resumed once per element.
+pub(crate) enum LoopIter {
+    Pairs(Box<dyn Iterator<Item = (Value, Value)>>),
+    Record { state: Record, index: i128 },
+    Generator { generator: GeneratorRef, index: i128 },
+}
+
+fn indexed<I>(items: I) -> LoopIter
+where
+    I: Iterator<Item = Value> + 'static,
+{
+    LoopIter::Pairs(Box::new(
+        items.enumerate().map(|(i, item)| (Value::Int(i as i128), item)),
+    ))
+}
+
+impl Interpreter {
+    pub(crate) fn loop_iter(&mut self, iterable: Value) -> RuntimeResult<LoopIter> {
+        Ok(match iterable {
+            Value::Array(items) => indexed((0..items.len()).map(move |i| items[i].clone())),
+
+            // Sets iterate in their sorted order
+            Value::Set(set) => indexed(set.iter().map(|k| k.to_value()).collect::<Vec<_>>().into_iter()),
+
+            // Ranges produce their elements one at a time
+            Value::Range(range) => indexed(range.iter().map(Value::Int)),
+
+            Value::String(s) => indexed(s.chars().map(Value::Char).collect::<Vec<_>>().into_iter()),
+
+            // An Axis iterates over its first dimension
+            Value::Axis(axis) => {
+                if axis.rank() == 0 {
+                    return Err(RuntimeError::custom("Cannot iterate a rank-0 axis"));
+                }
+                indexed((0..axis.len()).map(move |i| axis.get(i)))
+            }
+
+            // Dictionaries yield (key, value) in sorted key order
+            Value::Dictionary(dict) => {
+                let keys = sorted_keys(&dict);
+                LoopIter::Pairs(Box::new(keys.into_iter().map(move |k| {
+                    let value = dict[&k].clone();
+                    (Value::String(k), value)
+                })))
+            }
+
+            Value::Generator(generator) => LoopIter::Generator { generator, index: 0 },
+
+            Value::Record(rec) if self.environment.exists(&format!("{}.next", rec.name)) => {
+                LoopIter::Record { state: rec, index: 0 }
+            }
+
+            other => {
+                return Err(RuntimeError::custom(format!(
+                    "For-loop cannot iterate over {}",
+                    other.type_name()
+                )));
+            }
+        })
+    }
+
+    // A lone name over a dictionary gets the key, otherwise the element
+    pub(crate) fn bind_loop_vars(
+        &mut self,
+        iterator: &Pattern,
+        value: Option<&Pattern>,
+        keyed: bool,
+        key: Value,
+        item: Value,
+    ) -> RuntimeResult<()> {
+        match value {
+            Some(value) => {
+                self.bind_pattern(iterator, key, false)?;
+                self.bind_pattern(value, item, false)
+            }
+            None if keyed => self.bind_pattern(iterator, key, false),
+            None => self.bind_pattern(iterator, item, false),
+        }
+    }
+
+    pub(crate) fn loop_next(&mut self, iter: &mut LoopIter) -> RuntimeResult<Option<(Value, Value)>> {
+        match iter {
+            LoopIter::Pairs(pairs) => Ok(pairs.next()),
+            LoopIter::Record { state, index } => {
+                let Some(item) = self.record_next(state)? else {
+                    return Ok(None);
+                };
+                *index += 1;
+                Ok(Some((Value::Int(*index - 1), item)))
+            }
+            LoopIter::Generator { generator, index } => {
+                let Some(item) = self.resume_generator(generator)? else {
+                    return Ok(None);
+                };
+                *index += 1;
+                Ok(Some((Value::Int(*index - 1), item)))
+            }
+        }
+    }
+
+    // Calls `Type.next` on the iterator record and reads back its `value`
+    fn record_next(&mut self, state: &mut Record) -> RuntimeResult<Option<Value>> {
+        let label = format!("{}.next", state.name);
+        let func = match self.environment.get(&label)? {
+            Value::Function(func) => func,
+            other => {
+                return Err(RuntimeError::custom(format!(
+                    "'{}' must be a label, got {}",
+                    label,
+                    other.type_name()
+                )));
+            }
+        };
+
+        let args = [("src".to_string(), Value::Record(state.clone()))].into_iter().collect();
+        let (_, updated) = self.call_label(&label, &func, &args, Some("src"))?;
+        match updated {
+            Some(Value::Record(rec)) => *state = rec,
+            _ => {
+                return Err(RuntimeError::custom(format!(
+                    "'{}' must keep 'src' a {} record",
+                    label, state.name
+                )));
+            }
+        }
+
+        let item = state.get("value").cloned().ok_or_else(|| {
+            RuntimeError::custom(format!(
+                "Iterable record '{}' needs a 'value' field",
+                state.name
+            ))
+        })?;
+
+        Ok((item != Value::Nil).then_some(item))
+    }
+}
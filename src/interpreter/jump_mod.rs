@@ -0,0 +1,45 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::interpreter::RuntimeResult;
+use crate::parser::ast::Stmt;
+use crate::parser::jumps::flow_labels;
+
+// The statement index and body of the control flow label `name` in `body`
+pub(crate) fn label_in<'a>(body: &'a [Stmt], name: &str) -> Option<(usize, &'a [Stmt])> {
+    body.iter()
+        .enumerate()
+        .find_map(|(i, stmt)| flow_labels(stmt).find(|(label, _)| *label == name).map(|(_, inner)| (i, inner)))
+}
+
+impl Interpreter {
+    // Executes a block. `jump` travels up as an error until it reaches the
+    // block that defines its label; that block runs the label body in a new
+    // scope and carries on after the label. A jump made from inside the
+    // label body comes back here the same way, so a state machine of labels
+    // jumping to each other loops here instead of nesting.
+    pub(crate) fn run_block(&mut self, body: &[Stmt]) -> RuntimeResult<()> {
+        let mut pc = 0;
+        let mut entering: Option<&[Stmt]> = None;
+
+        loop {
+            let step = match entering.take() {
+                Some(label_body) => self.scoped(|this| this.run_block(label_body)),
+                None => match body.get(pc) {
+                    Some(stmt) => {
+                        pc += 1;
+                        self.execute(stmt).and_then(|()| self.close_abandoned())
+                    }
+                    None => return Ok(()),
+                },
+            };
+
+            if let Err(err) = step {
+                let (at, label_body) = err
+                    .jump_target()
+                    .and_then(|target| label_in(body, target))
+                    .ok_or(err)?;
+                pc = at + 1;
+                entering = Some(label_body);
+            }
+        }
+    }
+}
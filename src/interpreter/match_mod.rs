@@ -0,0 +1,115 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::interpreter::pattern_mod::{pattern_fields, sequence_len, split_sequence};
+use crate::interpreter::{RuntimeError, RuntimeResult};
+use crate::parser::ast::{MatchArm, MatchPattern};
+use crate::values::values::Value;
+
+impl Interpreter {
+    // Value of the first arm whose pattern matches and whose guard holds.
+    // Each arm gets its own scope, so bindings do not leak out of it.
+    pub(crate) fn eval_match(&mut self, value: Value, arms: &[MatchArm]) -> RuntimeResult<Value> {
+        for arm in arms {
+            if let Some(result) = self.scoped(|this| this.try_arm(arm, &value))? {
+                return Ok(result);
+            }
+        }
+
+        Err(RuntimeError::custom(format!(
+            "No match arm for a value of type {}",
+            value.type_name()
+        )))
+    }
+
+    fn try_arm(&mut self, arm: &MatchArm, value: &Value) -> RuntimeResult<Option<Value>> {
+        if !self.match_pattern(&arm.pattern, value.clone())? {
+            return Ok(None);
+        }
+        if let Some(guard) = &arm.guard
+            && !self.eval(guard)?.truthy()
+        {
+            return Ok(None);
+        }
+        self.eval(&arm.body).map(Some)
+    }
+
+    // Tests `value` against `pattern`, binding names in the current scope
+    fn match_pattern(&mut self, pattern: &MatchPattern, value: Value) -> RuntimeResult<bool> {
+        match pattern {
+            MatchPattern::Wildcard => Ok(true),
+
+            MatchPattern::Bind(name, inner) => {
+                if let Some(inner) = inner
+                    && !self.match_pattern(inner, value.clone())?
+                {
+                    return Ok(false);
+                }
+                self.environment.define_local(name, value);
+                Ok(true)
+            }
+
+            MatchPattern::Literal(expr) => Ok(self.eval(expr)? == value),
+
+            MatchPattern::Range(expr) => {
+                let Value::Range(range) = self.eval(expr)? else {
+                    return Err(RuntimeError::custom("Range pattern must be a range"));
+                };
+                Ok(match value {
+                    Value::Int(n) => range.contains(n),
+                    Value::UInt(n) => i128::try_from(n).is_ok_and(|n| range.contains(n)),
+                    _ => false,
+                })
+            }
+
+            MatchPattern::Type(name) => Ok(value.type_name() == name),
+
+            MatchPattern::Array { items, rest } => {
+                let Some(len) = sequence_len(&value) else {
+                    return Ok(false);
+                };
+                let count = items.len() as u128;
+                if len < count || (rest.is_none() && len != count) {
+                    return Ok(false);
+                }
+
+                let (parts, leftover) = split_sequence(value, items.len(), rest.is_some());
+                for (item, part) in items.iter().zip(parts) {
+                    if !self.match_pattern(item, part)? {
+                        return Ok(false);
+                    }
+                }
+                if let (Some(name), Some(leftover)) = (rest, leftover) {
+                    self.environment.define_local(name, leftover);
+                }
+                Ok(true)
+            }
+
+            MatchPattern::Dict { entries, rest } => {
+                let Some(mut fields) = pattern_fields(value) else {
+                    return Ok(false);
+                };
+
+                for (key, target) in entries {
+                    let Some(part) = fields.remove(key) else {
+                        return Ok(false);
+                    };
+                    if !self.match_pattern(target, part)? {
+                        return Ok(false);
+                    }
+                }
+                if let Some(name) = rest {
+                    self.environment.define_local(name, Value::dict(fields));
+                }
+                Ok(true)
+            }
+
+            MatchPattern::Alt(alternatives) => {
+                for alternative in alternatives {
+                    if self.match_pattern(alternative, value.clone())? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+        }
+    }
+}
@@ -1,6 +1,16 @@
+#[allow(clippy::module_inception)]
 pub mod interpreter;
 pub mod execute_mod;
 pub mod evaluate_mod;
+pub mod access_mod;
+pub mod call_mod;
+pub mod iter_mod;
+pub mod generator_mod;
+pub mod pattern_mod;
+pub mod match_mod;
+pub mod watch_mod;
+pub mod scope_mod;
+pub mod jump_mod;
 pub mod error;
 
 // optional re-exports
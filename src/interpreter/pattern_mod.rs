@@ -0,0 +1,111 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::interpreter::{RuntimeError, RuntimeResult};
+use crate::parser::ast::Pattern;
+use crate::values::values::Value;
+use std::collections::HashMap;
+
+impl Interpreter {
+    // Binds every name in `pattern` to the matching part of `value`.
+    // `local` binds in the innermost scope without touching outer names.
+    pub(crate) fn bind_pattern(&mut self, pattern: &Pattern, value: Value, local: bool) -> RuntimeResult<()> {
+        match pattern {
+            Pattern::Name(name) => {
+                if local {
+                    self.environment.define_local(name, value);
+                    Ok(())
+                } else {
+                    self.assign(name, value)
+                }
+            }
+
+            Pattern::Array { items, rest } => {
+                let len = sequence_len(&value).ok_or_else(|| {
+                    RuntimeError::custom(format!("Array pattern cannot destructure {}", value.type_name()))
+                })?;
+                let count = items.len() as u128;
+                if len < count || (rest.is_none() && len != count) {
+                    let expected = if rest.is_some() {
+                        format!("at least {}", items.len())
+                    } else {
+                        items.len().to_string()
+                    };
+                    return Err(RuntimeError::custom(format!(
+                        "Array pattern expects {} elements, got {}",
+                        expected, len
+                    )));
+                }
+
+                let (parts, leftover) = split_sequence(value, items.len(), rest.is_some());
+                for (item, part) in items.iter().zip(parts) {
+                    self.bind_pattern(item, part, local)?;
+                }
+                match (rest, leftover) {
+                    (Some(name), Some(leftover)) => self.bind_pattern(&Pattern::Name(name.clone()), leftover, local),
+                    _ => Ok(()),
+                }
+            }
+
+            Pattern::Dict { entries, rest } => {
+                let type_name = value.type_name().to_string();
+                let mut fields = pattern_fields(value).ok_or_else(|| {
+                    RuntimeError::custom(format!("Dictionary pattern cannot destructure {}", type_name))
+                })?;
+
+                for (key, target) in entries {
+                    let part = fields.remove(key).ok_or_else(|| {
+                        RuntimeError::custom(format!("Dictionary pattern expects key '{}', which is missing", key))
+                    })?;
+                    self.bind_pattern(target, part, local)?;
+                }
+
+                match rest {
+                    Some(name) => self.bind_pattern(&Pattern::Name(name.clone()), Value::dict(fields), local),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+// Number of elements an array pattern sees, None when it cannot apply
+pub(crate) fn sequence_len(value: &Value) -> Option<u128> {
+    match value {
+        Value::Array(items) => Some(items.len() as u128),
+        Value::Range(range) => Some(range.len()),
+        Value::Axis(axis) if axis.rank() > 0 => Some(axis.len() as u128),
+        _ => None,
+    }
+}
+
+// The first `count` elements of an array, range or axis, plus what is left
+// when the pattern has a `...rest`. The caller has checked the length.
+pub(crate) fn split_sequence(value: Value, count: usize, has_rest: bool) -> (Vec<Value>, Option<Value>) {
+    match value {
+        Value::Array(items) => {
+            let leftover = has_rest.then(|| Value::array(items[count..].to_vec()));
+            (items[..count].to_vec(), leftover)
+        }
+        // The rest of a range or an axis keeps its type
+        Value::Range(range) => {
+            let parts = range.iter().take(count).map(Value::Int).collect();
+            let leftover = has_rest.then(|| Value::Range(range.skip(count as u128)));
+            (parts, leftover)
+        }
+        Value::Axis(axis) => {
+            let len = axis.len();
+            let parts = (0..count).map(|i| axis.get(i)).collect();
+            let leftover = has_rest.then(|| Value::Axis(axis.slice(0, count, len)));
+            (parts, leftover)
+        }
+        _ => (Vec::new(), None),
+    }
+}
+
+// Entries a dictionary pattern sees: a dictionary's, or a record's fields
+pub(crate) fn pattern_fields(value: Value) -> Option<HashMap<String, Value>> {
+    match value {
+        Value::Dictionary(dict) => Some((*dict).clone()),
+        Value::Record(rec) => Some(rec.fields.into_iter().collect()),
+        _ => None,
+    }
+}
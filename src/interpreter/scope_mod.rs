@@ -0,0 +1,56 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::interpreter::RuntimeResult;
+use crate::values::values::Deferred;
+
+// Scopes close through here rather than through Environment directly, so
+// the work `defer` and `_scoped_` queued on them runs however the block
+// ends: normally, with an error, or with `?` leaving a label. Queued work
+// runs innermost scope first and, within a scope, in reverse order.
+impl Interpreter {
+    // Runs `body` in a new scope and closes it afterwards
+    pub(crate) fn scoped<T>(&mut self, body: impl FnOnce(&mut Self) -> RuntimeResult<T>) -> RuntimeResult<T> {
+        let depth = self.environment.depth();
+        self.environment.push_scope();
+        let result = body(self);
+        self.unwind_to(depth, result)
+    }
+
+    pub(crate) fn pop_scope(&mut self) -> RuntimeResult<()> {
+        let result = self.run_deferred();
+        self.environment.pop_scope();
+        result
+    }
+
+    // Closes every scope above `depth`. `result` is how the block ended;
+    // its error wins over one raised by the deferred work.
+    pub(crate) fn unwind_to<T>(&mut self, depth: usize, result: RuntimeResult<T>) -> RuntimeResult<T> {
+        let mut result = result;
+        while self.environment.depth() > depth.max(1) {
+            let closed = self.pop_scope();
+            if let Err(err) = closed
+                && result.is_ok()
+            {
+                result = Err(err);
+            }
+        }
+        result
+    }
+
+    // Runs the innermost scope's queued work. Every item runs even when an
+    // earlier one fails; the first error is returned.
+    pub(crate) fn run_deferred(&mut self) -> RuntimeResult<()> {
+        let mut result = Ok(());
+        for deferred in self.environment.take_deferred() {
+            let ran = match deferred {
+                Deferred::Block(body) => self.scoped(|this| this.run_body(&body)),
+                // Already killed (or gone) by the time the scope closes
+                Deferred::Kill(name) if !self.environment.exists(&name) => Ok(()),
+                Deferred::Kill(name) => self.kill(&name),
+            };
+            if result.is_ok() {
+                result = ran;
+            }
+        }
+        result
+    }
+}
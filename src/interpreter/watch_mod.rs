@@ -0,0 +1,122 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::interpreter::{RuntimeError, RuntimeErrorKind, RuntimeResult};
+use crate::values::values::Value;
+use std::collections::HashMap;
+
+// `_watch_ x -> label` runs `label(name=, old=, new=)` before every change
+// to x: assignments, index updates, mutating builtins, `_kill_` and
+// `_revive_`. The change only happens once every watcher has returned, so
+// a watcher vetoes it by raising an error. `veto(reason=...)` is the
+// deliberate way: the label that made the change ends there, like with
+// `?`, and the script goes on. Watcher labels declare just the parameters
+// they want out of name, old and new.
+impl Interpreter {
+    pub(crate) fn watch(&mut self, name: &str, label: &str) -> RuntimeResult<()> {
+        if !matches!(self.environment.get(label), Ok(Value::Function(_))) {
+            return Err(RuntimeError::custom(format!(
+                "Cannot watch '{}' with '{}', which is not a label",
+                name, label
+            )));
+        }
+        self.environment.watch(name, label)
+    }
+
+    // Environment::define, reported to the binding's watchers first
+    pub(crate) fn assign(&mut self, name: &str, value: Value) -> RuntimeResult<()> {
+        let watchers = self.watchers_of(name);
+        // A change define would refuse is not reported
+        let refused = self.environment.exists(name) && !self.environment.is_mutable(name);
+        if !watchers.is_empty() && !refused {
+            let old = self.environment.get(name).unwrap_or(Value::Nil);
+            self.notify(name, &watchers, old, value.clone())?;
+        }
+        self.environment.define(name, value)
+    }
+
+    // Changes the variable `name` in place (index assignment, mutating
+    // builtins). With watchers, the variable keeps its old value while they
+    // run and only takes the new one if none of them objects.
+    pub(crate) fn update_in_place<T>(
+        &mut self,
+        name: &str,
+        update: impl FnOnce(&mut Self, &mut Value) -> RuntimeResult<T>,
+    ) -> RuntimeResult<T> {
+        let watchers = self.watchers_of(name);
+        let old = if watchers.is_empty() {
+            None
+        } else {
+            self.environment.get(name).ok()
+        };
+
+        let mut target = self.environment.take(name)?;
+        let result = update(self, &mut target);
+        let Some(old) = old.filter(|_| result.is_ok()) else {
+            self.environment.restore(name, target);
+            return result;
+        };
+
+        self.environment.restore(name, old.clone());
+        self.notify(name, &watchers, old, target.clone())?;
+        self.environment.restore(name, target);
+        result
+    }
+
+    pub(crate) fn kill(&mut self, name: &str) -> RuntimeResult<()> {
+        let watchers = self.watchers_of(name);
+        if !watchers.is_empty() && self.environment.is_mutable(name) {
+            let old = self.environment.get(name)?;
+            self.notify(name, &watchers, old, Value::Nil)?;
+        }
+        self.environment.delete_variable(name)
+    }
+
+    pub(crate) fn revive(&mut self, name: &str, generation: usize) -> RuntimeResult<()> {
+        let watchers = self.watchers_of(name);
+        if !watchers.is_empty() {
+            let revived = self.environment.generation(name, generation)?;
+            self.notify(name, &watchers, Value::Nil, revived.value)?;
+        }
+        self.environment.revive(name, generation)
+    }
+
+    fn watchers_of(&self, name: &str) -> Vec<String> {
+        if self.notifying.contains(name) {
+            return Vec::new();
+        }
+        self.environment.watchers(name)
+    }
+
+    fn notify(&mut self, name: &str, watchers: &[String], old: Value, new: Value) -> RuntimeResult<()> {
+        let args = HashMap::from([
+            ("name".to_string(), Value::String(name.to_string())),
+            ("old".to_string(), old),
+            ("new".to_string(), new),
+        ]);
+
+        self.notifying.insert(name.to_string());
+        let result = watchers.iter().try_for_each(|label| {
+            self.run_watcher(name, label, &args).map_err(|mut err| {
+                if let RuntimeErrorKind::Vetoed(reason) = &err.kind {
+                    err.kind = RuntimeErrorKind::ChangeVetoed {
+                        name: name.to_string(),
+                        watcher: label.clone(),
+                        reason: reason.clone(),
+                    };
+                }
+                err
+            })
+        });
+        self.notifying.remove(name);
+        result
+    }
+
+    fn run_watcher(&mut self, name: &str, label: &str, args: &HashMap<String, Value>) -> RuntimeResult<()> {
+        let Ok(Value::Function(func)) = self.environment.get(label) else {
+            return Err(RuntimeError::custom(format!(
+                "Watcher '{}' of '{}' is not a label",
+                label, name
+            )));
+        };
+        self.call_label(label, &func, args, None).map(|_| ())
+    }
+}
@@ -3,6 +3,7 @@ use crate::lexer::tokens::{Literal, NumberLit, Token, TokenKind};
 
 pub struct Lexer {
     src: Vec<char>,
+    #[allow(unused)]
     source: String,
 
     start: usize,
@@ -28,6 +29,7 @@ impl Lexer {
         }
     }
 
+    #[allow(unused)]
     pub fn source(&self) -> &str {
         &self.source
     }
@@ -110,12 +112,19 @@ impl Lexer {
         match c {
             '(' => self.add_token(TokenKind::LeftParen),
             ')' => self.add_token(TokenKind::RightParen),
-            '?' => self.add_token(TokenKind::IdentityOperator),
+            '?' => {
+                if self.match_char('?') {
+                    self.add_token(TokenKind::QuestionQuestion);
+                } else {
+                    self.add_token(TokenKind::IdentityOperator);
+                }
+            }
             '{' => self.add_token(TokenKind::LeftBrace),
             '}' => self.add_token(TokenKind::RightBrace),
             '[' => self.add_token(TokenKind::LeftBracket),
             ']' => self.add_token(TokenKind::RightBracket),
             '#' => self.add_token(TokenKind::Hash),
+            '$' => self.add_token(TokenKind::Dollar),
             ',' => self.add_token(TokenKind::Comma),
 
             '&' => {
@@ -137,6 +146,8 @@ impl Lexer {
             '+' => {
                 if self.match_char('+') {
                     self.add_token(TokenKind::PlusPlus);
+                } else if self.match_char('=') {
+                    self.add_token(TokenKind::PlusEqual);
                 } else {
                     self.add_token(TokenKind::Plus);
                 }
@@ -145,6 +156,8 @@ impl Lexer {
             '-' => {
                 if self.match_char('-') {
                     self.add_token(TokenKind::MinusMinus);
+                } else if self.match_char('=') {
+                    self.add_token(TokenKind::MinusEqual);
                 } else if self.match_char('>') {
                     self.add_token(TokenKind::Arrow);
                 } else if self.peek().is_ascii_digit() && self.should_parse_as_negative_literal() {
@@ -196,8 +209,22 @@ impl Lexer {
                 }
             }
 
-            '*' => self.add_token(TokenKind::Star),
-            '%' => self.add_token(TokenKind::Percent),
+            '*' => {
+                if self.match_char('=') {
+                    self.add_token(TokenKind::StarEqual);
+                } else {
+                    self.add_token(TokenKind::Star);
+                }
+            }
+
+            '%' => {
+                if self.match_char('=') {
+                    self.add_token(TokenKind::PercentEqual);
+                } else {
+                    self.add_token(TokenKind::Percent);
+                }
+            }
+
             '.' => self.add_token(TokenKind::Dot),
             '@' => self.add_token(TokenKind::At),
 
@@ -215,6 +242,8 @@ impl Lexer {
                         }
                         self.advance();
                     }
+                } else if self.match_char('=') {
+                    self.add_token(TokenKind::SlashEqual);
                 } else {
                     self.add_token(TokenKind::Slash);
                 }
@@ -272,15 +301,13 @@ impl Lexer {
             return Err(self.err("Unterminated character literal"));
         }
 
-        let ch: char;
-
-        if self.peek() == '\\' {
+        let ch: char = if self.peek() == '\\' {
             self.advance(); // '\'
             if self.is_at_end() {
                 return Err(self.err("Unterminated escape sequence in character literal"));
             }
             let escaped = self.advance();
-            ch = match escaped {
+            match escaped {
                 'n' => '\n',
                 't' => '\t',
                 'r' => '\r',
@@ -326,13 +353,13 @@ impl Lexer {
                     char::from_u32(cp).ok_or_else(|| self.err("Invalid unicode code point"))?
                 }
                 _ => return Err(self.err(format!("Unknown escape sequence: \\{}", escaped))),
-            };
+            }
         } else {
             if self.peek() == '\n' || self.peek() == '\r' {
                 return Err(self.err("Character literal cannot contain newline"));
             }
-            ch = self.advance();
-        }
+            self.advance()
+        };
 
         if self.is_at_end() || self.peek() != '\'' {
             return Err(self.err("Expected closing ' after character literal"));
@@ -465,7 +492,7 @@ impl Lexer {
                     return Err(self.err("Multiple '.' characters in number"));
                 }
                 if !self.peek_next().is_ascii_digit() {
-                    return Err(self.err("Dot must be followed by digit in number"));
+                    break; // dot operator
                 }
                 has_dot = true;
                 text.push(self.advance());
@@ -514,7 +541,7 @@ impl Lexer {
                     let valid = match radix {
                         16 => is_hex(ch),
                         2 => ch == '0' || ch == '1',
-                        8 => ch >= '0' && ch <= '7',
+                        8 => ('0'..='7').contains(&ch),
                         _ => false,
                     };
                     if valid {
@@ -607,7 +634,11 @@ impl Lexer {
             "nil" => (TokenKind::Nil, None),
 
             "enum" => (TokenKind::Enum, None),
+            "record" => (TokenKind::Record, None),
+            "yield" => (TokenKind::Yield, None),
+            "defer" => (TokenKind::Defer, None),
             "switch" => (TokenKind::Switch, None),
+            "match" => (TokenKind::Match, None),
             "case" => (TokenKind::Case, None),
             "default" => (TokenKind::Default, None),
 
@@ -625,6 +656,9 @@ impl Lexer {
             "_kill_" => (TokenKind::VKill, None),
             "_revive_" => (TokenKind::VRevive, None),
             "_const_" => (TokenKind::VConst, None),
+            "_watch_" => (TokenKind::VWatch, None),
+            "_unwatch_" => (TokenKind::VUnwatch, None),
+            "_scoped_" => (TokenKind::VScoped, None),
 
             "label" => (TokenKind::Label, None),
             "jump" => (TokenKind::Jump, None),
@@ -642,7 +676,11 @@ impl Lexer {
 
             "_in_" => (TokenKind::In, None),
             "_define_"=> (TokenKind::DEFINE, None),
+            "_macro_" => (TokenKind::MACRO, None),
+            "_ifdef_" => (TokenKind::IFDEF, None),
             "_ifndef_" => (TokenKind::IFNDEF, None),
+            "_elif_" => (TokenKind::ELIF, None),
+            "_else_" => (TokenKind::ELSE, None),
             "_endif_" => (TokenKind::ENDIF, None),
             "_undef_" => (TokenKind::UNDEF, None),
 
@@ -665,6 +703,7 @@ impl Lexer {
             lexeme: text,
             line: self.line,
             literal,
+            expansion: None,
         });
     }
 }
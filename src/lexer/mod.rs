@@ -1,4 +1,5 @@
 pub mod errors;
+#[allow(clippy::module_inception)]
 pub mod lexer;
 pub mod tokens;
 
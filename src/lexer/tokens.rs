@@ -1,4 +1,5 @@
 use std::fmt;
+use std::rc::Rc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(clippy::upper_case_acronyms)]
@@ -11,6 +12,7 @@ pub enum TokenKind {
     LeftBracket,
     RightBracket,
     Hash,
+    Dollar,
     Comma,
     Dot,
     Plus,
@@ -32,8 +34,14 @@ pub enum TokenKind {
     BangEqual,
     MinusMinus,
     PlusPlus,
+    PlusEqual,    // +=
+    MinusEqual,   // -=
+    StarEqual,    // *=
+    SlashEqual,   // /=
+    PercentEqual, // %=
     PipePipe,
     IdentityOperator, // ?
+    QuestionQuestion, // ??
     Ampersand,
     ColonColon, // ::
     Arrow,      // ->
@@ -47,7 +55,11 @@ pub enum TokenKind {
 
     // switch/enum
     Enum,
+    Record,
+    Yield,
+    Defer,
     Switch,
+    Match,
     Case,
     Default,
 
@@ -77,6 +89,9 @@ pub enum TokenKind {
     VKill,
     VRevive,
     VConst,
+    VWatch,
+    VUnwatch,
+    VScoped,
     Label,
     Jump,
     If,
@@ -88,7 +103,11 @@ pub enum TokenKind {
     For,
     In,
     DEFINE,
+    MACRO,
+    IFDEF,
     IFNDEF,
+    ELIF,
+    ELSE,
     ENDIF,
     UNDEF,
     Visit,
@@ -125,6 +144,18 @@ pub struct Token {
     pub lexeme: String,
     pub line: usize, // 1-based
     pub literal: Option<Literal>,
+    // Set on tokens produced by a token macro
+    pub expansion: Option<Rc<Expansion>>,
+}
+
+// The token macro expansion a token came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expansion {
+    pub macro_name: String,
+    pub defined_at: usize,
+    pub called_at: usize,
+    // 1 for a call written in the source, +1 for each macro in between
+    pub depth: usize,
 }
 
 impl Token {
@@ -134,6 +165,7 @@ impl Token {
             lexeme: lexeme.into(),
             line,
             literal: None,
+            expansion: None,
         }
     }
 
@@ -148,6 +180,7 @@ impl Token {
             lexeme: lexeme.into(),
             line,
             literal: Some(lit),
+            expansion: None,
         }
     }
 }
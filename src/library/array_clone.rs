@@ -7,6 +7,6 @@ impl Interpreter {
     // array_clone(src=array) -> array
     pub(crate) fn array_clone(src: Value) -> RuntimeResult<Value> {
         let arr = expect_array(src, "array_clone")?;
-        Ok(Value::Array(arr))
+        Ok(Value::array(arr))
     }
 }
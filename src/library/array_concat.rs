@@ -9,6 +9,6 @@ impl Interpreter {
         let mut left = expect_array(a, "array_concat")?;
         let right = expect_array(b, "array_concat")?;
         left.extend(right);
-        Ok(Value::Array(left))
+        Ok(Value::array(left))
     }
 }
@@ -6,7 +6,10 @@ use crate::library::array_utils::expect_array;
 impl Interpreter {
     // array_contains(src=array, value=any) -> Bool
     pub(crate) fn array_contains(src: Value, value: Value) -> RuntimeResult<Value> {
+        if let Value::Range(_) = src {
+            return Self::range_contains(src, value);
+        }
         let arr = expect_array(src, "array_contains")?;
-        Ok(Value::Bool(arr.iter().any(|v| *v == value)))
+        Ok(Value::Bool(arr.contains(&value)))
     }
 }
@@ -6,6 +6,12 @@ use crate::library::array_utils::expect_array;
 impl Interpreter {
     // array_find(src=array, value=any) -> Int (index) or Nil
     pub(crate) fn array_find(src: Value, value: Value) -> RuntimeResult<Value> {
+        if let (Value::Range(range), Value::Int(n)) = (&src, &value) {
+            return Ok(match range.position(*n) {
+                Some(i) => i128::try_from(i).map_or_else(|_| Value::BigInt(i.to_string()), Value::Int),
+                None => Value::Nil,
+            });
+        }
         let arr = expect_array(src, "array_find")?;
         for (i, v) in arr.iter().enumerate() {
             if *v == value {
@@ -1,12 +1,12 @@
 use crate::interpreter::interpreter::Interpreter;
 use crate::values::values::Value;
 use crate::interpreter::error::RuntimeResult;
-use crate::library::array_utils::{expect_array, expect_int};
+use crate::library::array_utils::{expect_array_mut, expect_int};
 
 impl Interpreter {
     // array_insert(src=array, idx=int, value=any) -> array
-    pub(crate) fn array_insert(src: Value, idx: Value, value: Value) -> RuntimeResult<Value> {
-        let mut arr = expect_array(src, "array_insert")?;
+    pub(crate) fn array_insert(src: &mut Value, idx: Value, value: Value) -> RuntimeResult<Value> {
+        let arr = expect_array_mut(src, "array_insert")?;
         let i = expect_int(idx, "array_insert", "idx")?;
 
         let len = arr.len() as i128;
@@ -19,6 +19,6 @@ impl Interpreter {
         }
 
         arr.insert(u as usize, value);
-        Ok(Value::Array(arr))
+        Ok(src.clone())
     }
 }
@@ -5,6 +5,9 @@ use crate::library::array_utils::expect_array;
 
 impl Interpreter {
     pub(crate) fn array_is_empty(src: Value) -> RuntimeResult<Value> {
+        if let Value::Range(range) = src {
+            return Ok(Value::Bool(range.is_empty()));
+        }
         let arr = expect_array(src, "array_is_empty")?;
         Ok(Value::Bool(arr.is_empty()))
     }
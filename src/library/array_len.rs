@@ -6,6 +6,9 @@ use crate::library::array_utils::expect_array;
 impl Interpreter {
     // array_len(src=array) -> Int
     pub(crate) fn array_len(src: Value) -> RuntimeResult<Value> {
+        if let Value::Range(_) = src {
+            return Self::range_len(src);
+        }
         let arr = expect_array(src, "array_len")?;
         Ok(Value::Int(arr.len() as i128))
     }
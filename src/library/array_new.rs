@@ -5,6 +5,6 @@ use crate::interpreter::error::RuntimeResult;
 impl Interpreter {
     // array_new() -> []
     pub(crate) fn array_new() -> RuntimeResult<Value> {
-        Ok(Value::Array(Vec::new()))
+        Ok(Value::array(Vec::new()))
     }
 }
@@ -1,12 +1,12 @@
 use crate::interpreter::interpreter::Interpreter;
 use crate::values::values::Value;
 use crate::interpreter::error::{RuntimeError, RuntimeResult};
-use crate::library::array_utils::expect_array;
+use crate::library::array_utils::expect_array_mut;
 
 impl Interpreter {
     // array_pop(src=array) -> Value
-    pub(crate) fn array_pop(src: Value) -> RuntimeResult<Value> {
-        let mut arr = expect_array(src, "array_pop")?;
+    pub(crate) fn array_pop(src: &mut Value) -> RuntimeResult<Value> {
+        let arr = expect_array_mut(src, "array_pop")?;
         arr.pop().ok_or_else(|| RuntimeError::custom("array_pop on empty array"))
     }
 }
@@ -8,6 +8,6 @@ impl Interpreter {
     pub(crate) fn array_reverse(src: Value) -> RuntimeResult<Value> {
         let mut arr = expect_array(src, "array_reverse")?;
         arr.reverse();
-        Ok(Value::Array(arr))
+        Ok(Value::array(arr))
     }
 }
@@ -0,0 +1,16 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_array;
+
+impl Interpreter {
+    // array_shuffle(src=array) -> array (Fisher-Yates)
+    pub(crate) fn array_shuffle(&mut self, src: Value) -> RuntimeResult<Value> {
+        let mut arr = expect_array(src, "array_shuffle")?;
+        for i in (1..arr.len()).rev() {
+            let j = self.rng().below(i as u128 + 1) as usize;
+            arr.swap(i, j);
+        }
+        Ok(Value::array(arr))
+    }
+}
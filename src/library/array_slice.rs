@@ -21,6 +21,6 @@ impl Interpreter {
         if ee > len { ee = len; }
         if ee < ss { ee = ss; }
 
-        Ok(Value::Array(arr[ss as usize..ee as usize].to_vec()))
+        Ok(Value::array(arr[ss as usize..ee as usize].to_vec()))
     }
 }
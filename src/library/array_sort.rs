@@ -11,12 +11,12 @@ impl Interpreter {
         // Ensure homogeneous comparable types (simple version)
         if arr.iter().all(|v| matches!(v, Value::Int(_))) {
             arr.sort_by_key(|v| if let Value::Int(i) = v { *i } else { 0 });
-            return Ok(Value::Array(arr));
+            return Ok(Value::array(arr));
         }
 
         if arr.iter().all(|v| matches!(v, Value::UInt(_))) {
             arr.sort_by_key(|v| if let Value::UInt(u) = v { *u } else { 0 });
-            return Ok(Value::Array(arr));
+            return Ok(Value::array(arr));
         }
 
         if arr.iter().all(|v| matches!(v, Value::Float(_))) {
@@ -25,7 +25,7 @@ impl Interpreter {
                 let bf = if let Value::Float(x) = b { *x } else { 0.0 };
                 af.partial_cmp(&bf).unwrap_or(std::cmp::Ordering::Equal)
             });
-            return Ok(Value::Array(arr));
+            return Ok(Value::array(arr));
         }
 
         if arr.iter().all(|v| matches!(v, Value::String(_))) {
@@ -34,7 +34,7 @@ impl Interpreter {
                 let bs_ = if let Value::String(s) = b { s } else { "" };
                 as_.cmp(bs_)
             });
-            return Ok(Value::Array(arr));
+            return Ok(Value::array(arr));
         }
 
         Err(RuntimeError::custom(
@@ -1,10 +1,29 @@
 
 use crate::interpreter::error::{RuntimeError, RuntimeResult};
 use crate::values::values::Value;
+use std::rc::Rc;
 
+// Ranges are accepted and expanded into their elements, up to a limit
 pub fn expect_array(value: Value, fname: &str) -> RuntimeResult<Vec<Value>> {
     match value {
-        Value::Array(v) => Ok(v),
+        Value::Array(v) => Ok(Rc::unwrap_or_clone(v)),
+        Value::Range(range) => range.to_values(fname),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects Array, got {}",
+            fname,
+            other.type_name()
+        ))),
+    }
+}
+
+// Mutable access for in-place builtins; copies the storage only if it is shared
+pub fn expect_array_mut<'a>(value: &'a mut Value, fname: &str) -> RuntimeResult<&'a mut Vec<Value>> {
+    // A range being changed becomes the Array of its elements
+    if let Value::Range(range) = value {
+        *value = Value::array(range.to_values(fname)?);
+    }
+    match value {
+        Value::Array(v) => Ok(Rc::make_mut(v)),
         other => Err(RuntimeError::custom(format!(
             "{} expects Array, got {}",
             fname,
@@ -37,3 +56,15 @@ pub fn resolve_index(idx: i128, len: usize, fname: &str) -> RuntimeResult<usize>
     }
     Ok(real as usize)
 }
+
+pub fn expect_string(value: Value, fname: &str, arg: &str) -> RuntimeResult<String> {
+    match value {
+        Value::String(s) => Ok(s),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects String for '{}', got {}",
+            fname,
+            arg,
+            other.type_name()
+        ))),
+    }
+}
@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::axis_utils::expect_axis;
+
+impl Interpreter {
+    // axis_dot(x=axis, y=axis) -> number for two vectors, matrix product otherwise
+    pub(crate) fn axis_dot(x: Value, y: Value) -> RuntimeResult<Value> {
+        let a = expect_axis(x, "axis_dot")?;
+        let b = expect_axis(y, "axis_dot")?;
+        Ok(a.matmul(&b, "axis_dot")?.into_value())
+    }
+}
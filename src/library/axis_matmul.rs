@@ -0,0 +1,20 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::axis_utils::expect_axis;
+
+impl Interpreter {
+    // axis_matmul(x=matrix, y=matrix) -> matrix
+    pub(crate) fn axis_matmul(x: Value, y: Value) -> RuntimeResult<Value> {
+        let a = expect_axis(x, "axis_matmul")?;
+        let b = expect_axis(y, "axis_matmul")?;
+        if a.rank() != 2 || b.rank() != 2 {
+            return Err(RuntimeError::custom(format!(
+                "axis_matmul expects two matrices, got rank {} and {}",
+                a.rank(),
+                b.rank()
+            )));
+        }
+        Ok(Value::Axis(a.matmul(&b, "axis_matmul")?))
+    }
+}
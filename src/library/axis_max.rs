@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::axis_utils::{expect_axis, expect_dim};
+
+impl Interpreter {
+    // axis_max(src=axis, dim=Int?) -> number, or axis of maxima along dim
+    pub(crate) fn axis_max(src: Value, dim: Option<Value>) -> RuntimeResult<Value> {
+        let ax = expect_axis(src, "axis_max")?;
+        let dim = expect_dim(dim, &ax, "axis_max")?;
+        if ax.is_empty() {
+            return Err(RuntimeError::custom("axis_max of an empty axis"));
+        }
+        let max = |lane: &[f64]| lane.iter().copied().fold(f64::NAN, f64::max);
+        Ok(ax.reduce(dim, max, ax.integral).into_value())
+    }
+}
@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::axis_utils::{expect_axis, expect_dim};
+
+impl Interpreter {
+    // axis_mean(src=axis, dim=Int?) -> Float, or axis of means along dim
+    pub(crate) fn axis_mean(src: Value, dim: Option<Value>) -> RuntimeResult<Value> {
+        let ax = expect_axis(src, "axis_mean")?;
+        let dim = expect_dim(dim, &ax, "axis_mean")?;
+        if ax.is_empty() {
+            return Err(RuntimeError::custom("axis_mean of an empty axis"));
+        }
+        let mean = |lane: &[f64]| lane.iter().sum::<f64>() / lane.len() as f64;
+        Ok(ax.reduce(dim, mean, false).into_value())
+    }
+}
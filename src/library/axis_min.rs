@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::axis_utils::{expect_axis, expect_dim};
+
+impl Interpreter {
+    // axis_min(src=axis, dim=Int?) -> number, or axis of minima along dim
+    pub(crate) fn axis_min(src: Value, dim: Option<Value>) -> RuntimeResult<Value> {
+        let ax = expect_axis(src, "axis_min")?;
+        let dim = expect_dim(dim, &ax, "axis_min")?;
+        if ax.is_empty() {
+            return Err(RuntimeError::custom("axis_min of an empty axis"));
+        }
+        let min = |lane: &[f64]| lane.iter().copied().fold(f64::NAN, f64::min);
+        Ok(ax.reduce(dim, min, ax.integral).into_value())
+    }
+}
@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::{expect_array, expect_int};
+use crate::library::axis_utils::expect_axis;
+
+impl Interpreter {
+    // axis_reshape(src=axis, shape=[&l, ...]) -> axis; one dimension may be -1
+    pub(crate) fn axis_reshape(src: Value, shape: Value) -> RuntimeResult<Value> {
+        let ax = expect_axis(src, "axis_reshape")?;
+        let dims = expect_array(shape, "axis_reshape")?
+            .into_iter()
+            .map(|d| expect_int(d, "axis_reshape", "shape"))
+            .collect::<RuntimeResult<Vec<i128>>>()?;
+        Ok(Value::Axis(ax.reshape(&dims)?))
+    }
+}
@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::axis_utils::expect_axis;
+
+impl Interpreter {
+    // axis_shape(src=axis) -> Array of Int
+    pub(crate) fn axis_shape(src: Value) -> RuntimeResult<Value> {
+        let ax = expect_axis(src, "axis_shape")?;
+        Ok(Value::array(ax.shape.iter().map(|d| Value::Int(*d as i128)).collect()))
+    }
+}
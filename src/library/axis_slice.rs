@@ -0,0 +1,29 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_int;
+use crate::library::axis_utils::{expect_axis, expect_dim};
+
+impl Interpreter {
+    // axis_slice(src=axis, start=Int, end=Int, dim=Int?) -> axis
+    // Takes [start, end) along dim (default 0); bounds clamp like array_slice.
+    pub(crate) fn axis_slice(src: Value, start: Value, end: Value, dim: Option<Value>) -> RuntimeResult<Value> {
+        let ax = expect_axis(src, "axis_slice")?;
+        if ax.rank() == 0 {
+            return Err(RuntimeError::custom("axis_slice cannot slice a rank-0 axis"));
+        }
+        let dim = expect_dim(dim, &ax, "axis_slice")?.unwrap_or(0);
+        let s = expect_int(start, "axis_slice", "start")?;
+        let e = expect_int(end, "axis_slice", "end")?;
+
+        let len = ax.shape[dim] as i128;
+        let clamp = |i: i128| -> usize {
+            let real = if i < 0 { len + i } else { i };
+            real.clamp(0, len) as usize
+        };
+        let ss = clamp(s);
+        let ee = clamp(e).max(ss);
+
+        Ok(Value::Axis(ax.slice(dim, ss, ee)))
+    }
+}
@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::axis_utils::{expect_axis, expect_dim};
+
+impl Interpreter {
+    // axis_sum(src=axis, dim=Int?) -> number, or axis summed along dim
+    pub(crate) fn axis_sum(src: Value, dim: Option<Value>) -> RuntimeResult<Value> {
+        let ax = expect_axis(src, "axis_sum")?;
+        let dim = expect_dim(dim, &ax, "axis_sum")?;
+        Ok(ax.reduce(dim, |lane| lane.iter().sum(), ax.integral).into_value())
+    }
+}
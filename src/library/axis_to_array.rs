@@ -0,0 +1,21 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::axis_utils::expect_axis;
+
+impl Interpreter {
+    // axis_to_array(src=axis) -> nested Array of numbers
+    pub(crate) fn axis_to_array(src: Value) -> RuntimeResult<Value> {
+        let ax = expect_axis(src, "axis_to_array")?;
+        if ax.rank() == 0 {
+            return Ok(ax.into_value());
+        }
+        let rows = (0..ax.len())
+            .map(|i| match ax.get(i) {
+                Value::Axis(row) => Self::axis_to_array(Value::Axis(row)),
+                number => Ok(number),
+            })
+            .collect::<RuntimeResult<Vec<Value>>>()?;
+        Ok(Value::array(rows))
+    }
+}
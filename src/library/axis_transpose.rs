@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::axis_utils::expect_axis;
+
+impl Interpreter {
+    // axis_transpose(src=axis) -> axis with its dimensions reversed
+    pub(crate) fn axis_transpose(src: Value) -> RuntimeResult<Value> {
+        let ax = expect_axis(src, "axis_transpose")?;
+        Ok(Value::Axis(ax.transpose()))
+    }
+}
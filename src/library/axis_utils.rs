@@ -0,0 +1,35 @@
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_int;
+use crate::values::axis::Axis;
+use crate::values::values::Value;
+use std::rc::Rc;
+
+// Accepts an Axis, or an Array that can be converted into one.
+pub fn expect_axis(value: Value, fname: &str) -> RuntimeResult<Axis> {
+    match value {
+        Value::Axis(ax) => Ok(ax),
+        Value::Array(arr) => Axis::from_values(Rc::unwrap_or_clone(arr)),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects Axis, got {}",
+            fname,
+            other.type_name()
+        ))),
+    }
+}
+
+// Resolves an optional `dim` argument; negative values count from the last dimension.
+pub fn expect_dim(value: Option<Value>, ax: &Axis, fname: &str) -> RuntimeResult<Option<usize>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    let d = expect_int(value, fname, "dim")?;
+    let rank = ax.rank() as i128;
+    let real = if d < 0 { rank + d } else { d };
+    if real < 0 || real >= rank {
+        return Err(RuntimeError::custom(format!(
+            "{} dim {} out of range for rank {}",
+            fname, d, rank
+        )));
+    }
+    Ok(Some(real as usize))
+}
@@ -1,6 +1,7 @@
 use crate::interpreter::interpreter::Interpreter;
 use crate::values::values::Value;
 use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::set::render_set;
 use std::collections::HashMap;
 
 
@@ -39,7 +40,7 @@ impl Interpreter {
                         Value::Dictionary(map) => {
                             print!("{{");
                             let mut first = true;
-                            for (k, v) in map {
+                            for (k, v) in map.iter() {
                                 if !first {
                                     print!(", ");
                                 }
@@ -49,15 +50,21 @@ impl Interpreter {
                             print!("}}");
                         }
 
-                        Value::Axis(arr) => {
-                            print!("axis(");
-                            for (i, val) in arr.iter().enumerate() {
+                        Value::Axis(ax) => print!("{}", ax.render()),
+                        Value::Set(set) => print!("{}", render_set(set)),
+                        Value::Range(range) => print!("{}", range.render()),
+                        Value::Generator(g) => print!("<generator {}>", g.name()),
+
+                        Value::RecordType(rt) => print!("<record {}>", rt.name),
+                        Value::Record(rec) => {
+                            print!("{}{{", rec.name);
+                            for (i, (k, v)) in rec.fields.iter().enumerate() {
                                 if i > 0 {
                                     print!(", ");
                                 }
-                                print!("{:?}", val);
+                                print!("{}: {:?}", k, v);
                             }
-                            print!(")");
+                            print!("}}");
                         }
                     }
                 }
@@ -98,6 +105,13 @@ impl Interpreter {
                 Some(Self::cast_type(value, target_type))
             }
 
+            // Mutating builtins work on a copy of `src` here; call sites that
+            // can update a variable in place go through call_builtin_mut
+            name if is_mutating_builtin(name) => {
+                let mut src = args.get("src")?.clone();
+                self.call_builtin_mut(name, &mut src, args)
+            }
+
             "array_new" => Some(Self::array_new()),
             "array_len" => Some(Self::array_len(args.get("src")?.clone())),
             "array_is_empty" => Some(Self::array_is_empty(args.get("src")?.clone())),
@@ -106,57 +120,299 @@ impl Interpreter {
                 args.get("src")?.clone(),
                 args.get("idx")?.clone(),
             )),
-            "array_set" => Some(Self::array_set(
+
+
+
+            "array_clone" => Some(Self::array_clone(args.get("src")?.clone())),
+            "array_reverse" => Some(Self::array_reverse(args.get("src")?.clone())),
+            "array_sort" => Some(Self::array_sort(args.get("src")?.clone())),
+            "array_shuffle" => Some(self.array_shuffle(args.get("src")?.clone())),
+
+            "array_slice" => Some(Self::array_slice(
                 args.get("src")?.clone(),
-                args.get("idx")?.clone(),
-                args.get("value")?.clone(),
+                args.get("start")?.clone(),
+                args.get("end")?.clone(),
             )),
 
-            "array_push" => Some(Self::array_push(
+            "array_concat" => Some(Self::array_concat(
+                args.get("a")?.clone(),
+                args.get("b")?.clone(),
+            )),
+
+
+            "array_find" => Some(Self::array_find(
                 args.get("src")?.clone(),
                 args.get("value")?.clone(),
             )),
-            "array_pop" => Some(Self::array_pop(args.get("src")?.clone())),
 
-            "array_insert" => Some(Self::array_insert(
+            "array_contains" => Some(Self::array_contains(
                 args.get("src")?.clone(),
-                args.get("idx")?.clone(),
                 args.get("value")?.clone(),
             )),
-            "array_remove" => Some(Self::array_remove(
+
+            "now_ns" => Some(Self::now_ns()),
+            "elapsed" => Some(Self::elapsed(args.get("since")?.clone())),
+            "unix_time" => Some(Self::unix_time()),
+            "sleep_ms" => Some(Self::sleep_ms(args.get("ms")?.clone())),
+
+            "time_utc" => Some(Self::time_utc(args.get("ts").cloned())),
+            "time_local" => Some(Self::time_local(
+                args.get("ts").cloned(),
+                args.get("zone").cloned(),
+            )),
+
+            "time_format" => Some(Self::time_format(
                 args.get("src")?.clone(),
-                args.get("idx")?.clone(),
+                args.get("fmt")?.clone(),
             )),
+            "time_parse" => Some(Self::time_parse(args.get("src")?.clone())),
 
-            "array_clear" => Some(Self::array_clear(args.get("src")?.clone())),
-            "array_clone" => Some(Self::array_clone(args.get("src")?.clone())),
+            "math_abs" => Some(Self::math_abs(args.get("src")?.clone())),
+            "math_sqrt" => Some(Self::math_sqrt(args.get("src")?.clone())),
+            "math_cbrt" => Some(Self::math_cbrt(args.get("src")?.clone())),
+            "math_exp" => Some(Self::math_exp(args.get("src")?.clone())),
+            "math_log" => Some(Self::math_log(
+                args.get("src")?.clone(),
+                args.get("base").cloned(),
+            )),
+            "math_log10" => Some(Self::math_log10(args.get("src")?.clone())),
+            "math_pow" => Some(Self::math_pow(
+                args.get("base")?.clone(),
+                args.get("exp")?.clone(),
+            )),
 
-            "array_slice" => Some(Self::array_slice(
+            "math_sin" => Some(Self::math_sin(args.get("src")?.clone())),
+            "math_cos" => Some(Self::math_cos(args.get("src")?.clone())),
+            "math_tan" => Some(Self::math_tan(args.get("src")?.clone())),
+            "math_asin" => Some(Self::math_asin(args.get("src")?.clone())),
+            "math_acos" => Some(Self::math_acos(args.get("src")?.clone())),
+            "math_atan" => Some(Self::math_atan(args.get("src")?.clone())),
+            "math_atan2" => Some(Self::math_atan2(
+                args.get("y")?.clone(),
+                args.get("x")?.clone(),
+            )),
+
+            "math_floor" => Some(Self::math_floor(args.get("src")?.clone())),
+            "math_ceil" => Some(Self::math_ceil(args.get("src")?.clone())),
+            "math_trunc" => Some(Self::math_trunc(args.get("src")?.clone())),
+            "math_round" => Some(Self::math_round(
+                args.get("src")?.clone(),
+                args.get("mode").cloned(),
+            )),
+
+            "math_min" => Some(Self::math_min(
+                args.get("x")?.clone(),
+                args.get("y")?.clone(),
+            )),
+            "math_max" => Some(Self::math_max(
+                args.get("x")?.clone(),
+                args.get("y")?.clone(),
+            )),
+            "math_gcd" => Some(Self::math_gcd(
+                args.get("x")?.clone(),
+                args.get("y")?.clone(),
+            )),
+            "math_lcm" => Some(Self::math_lcm(
+                args.get("x")?.clone(),
+                args.get("y")?.clone(),
+            )),
+            "math_isqrt" => Some(Self::math_isqrt(args.get("src")?.clone())),
+            "math_pow_mod" => Some(Self::math_pow_mod(
+                args.get("base")?.clone(),
+                args.get("exp")?.clone(),
+                args.get("modulus")?.clone(),
+            )),
+            "math_clamp" => Some(Self::math_clamp(
+                args.get("src")?.clone(),
+                args.get("lo")?.clone(),
+                args.get("hi")?.clone(),
+            )),
+
+            "rand_seed" => Some(self.rand_seed(args.get("seed").cloned())),
+            "rand_int" => Some(self.rand_int(
+                args.get("lo")?.clone(),
+                args.get("hi")?.clone(),
+            )),
+            "rand_float" => Some(self.rand_float()),
+            "rand_choice" => Some(self.rand_choice(args.get("src")?.clone())),
+            "rand_bytes" => Some(self.rand_bytes(args.get("count")?.clone())),
+
+            "axis_shape" => Some(Self::axis_shape(args.get("src")?.clone())),
+            "axis_reshape" => Some(Self::axis_reshape(
+                args.get("src")?.clone(),
+                args.get("shape")?.clone(),
+            )),
+            "axis_transpose" => Some(Self::axis_transpose(args.get("src")?.clone())),
+            "axis_dot" => Some(Self::axis_dot(
+                args.get("x")?.clone(),
+                args.get("y")?.clone(),
+            )),
+            "axis_matmul" => Some(Self::axis_matmul(
+                args.get("x")?.clone(),
+                args.get("y")?.clone(),
+            )),
+            "axis_sum" => Some(Self::axis_sum(args.get("src")?.clone(), args.get("dim").cloned())),
+            "axis_mean" => Some(Self::axis_mean(args.get("src")?.clone(), args.get("dim").cloned())),
+            "axis_min" => Some(Self::axis_min(args.get("src")?.clone(), args.get("dim").cloned())),
+            "axis_max" => Some(Self::axis_max(args.get("src")?.clone(), args.get("dim").cloned())),
+            "axis_slice" => Some(Self::axis_slice(
                 args.get("src")?.clone(),
                 args.get("start")?.clone(),
                 args.get("end")?.clone(),
+                args.get("dim").cloned(),
             )),
+            "axis_to_array" => Some(Self::axis_to_array(args.get("src")?.clone())),
 
-            "array_concat" => Some(Self::array_concat(
-                args.get("a")?.clone(),
-                args.get("b")?.clone(),
+            "set_len" => Some(Self::set_len(args.get("src")?.clone())),
+            "set_contains" => Some(Self::set_contains(
+                args.get("src")?.clone(),
+                args.get("value")?.clone(),
+            )),
+            "set_union" => Some(Self::set_union(
+                args.get("x")?.clone(),
+                args.get("y")?.clone(),
+            )),
+            "set_intersection" => Some(Self::set_intersection(
+                args.get("x")?.clone(),
+                args.get("y")?.clone(),
             )),
+            "set_difference" => Some(Self::set_difference(
+                args.get("x")?.clone(),
+                args.get("y")?.clone(),
+            )),
+            "set_is_subset" => Some(Self::set_is_subset(
+                args.get("x")?.clone(),
+                args.get("y")?.clone(),
+            )),
+            "set_to_array" => Some(Self::set_to_array(args.get("src")?.clone())),
+            "set_from_array" => Some(Self::set_from_array(args.get("src")?.clone())),
 
-            "array_reverse" => Some(Self::array_reverse(args.get("src")?.clone())),
-            "array_sort" => Some(Self::array_sort(args.get("src")?.clone())),
+            "range_len" => Some(Self::range_len(args.get("src")?.clone())),
+            "range_contains" => Some(Self::range_contains(
+                args.get("src")?.clone(),
+                args.get("value")?.clone(),
+            )),
+            "range_reverse" => Some(Self::range_reverse(args.get("src")?.clone())),
+            "range_to_array" => Some(Self::range_to_array(args.get("src")?.clone())),
 
-            "array_find" => Some(Self::array_find(
+            "next" => Some(self.gen_next(args.get("src")?.clone())),
+            "gen_take" => Some(self.gen_take(
+                args.get("src")?.clone(),
+                args.get("n")?.clone(),
+            )),
+
+            "history" => Some(self.var_history(args.get("name")?.clone())),
+            "veto" => Some(Self::veto(args.get("reason")?.clone())),
+
+            "str_len" => Some(Self::str_len(args.get("src")?.clone())),
+            "str_empty" => Some(Self::str_empty(args.get("src")?.clone())),
+            "str_upper" => Some(Self::str_upper(args.get("src")?.clone())),
+            "str_lower" => Some(Self::str_lower(args.get("src")?.clone())),
+            "str_trim" => Some(Self::str_trim(args.get("src")?.clone())),
+            "str_reverse" => Some(Self::str_reverse(args.get("src")?.clone())),
+            "str_clone" => Some(Self::str_clone(args.get("src")?.clone())),
+            "str_capitalize" => Some(Self::str_capitalize(args.get("src")?.clone())),
+            "str_concat" => Some(Self::str_concat(
+                args.get("src")?.clone(),
+                args.get("value")?.clone(),
+            )),
+            "str_contains" => Some(Self::str_contains(
+                args.get("src")?.clone(),
+                args.get("value")?.clone(),
+            )),
+            "str_starts_with" => Some(Self::str_starts_with(
+                args.get("src")?.clone(),
+                args.get("value")?.clone(),
+            )),
+            "str_ends_with" => Some(Self::str_ends_with(
+                args.get("src")?.clone(),
+                args.get("value")?.clone(),
+            )),
+            "str_find" => Some(Self::str_find(
+                args.get("src")?.clone(),
+                args.get("value")?.clone(),
+            )),
+            "str_count" => Some(Self::str_count(
                 args.get("src")?.clone(),
                 args.get("value")?.clone(),
             )),
+            "str_compare" => Some(Self::str_compare(
+                args.get("src")?.clone(),
+                args.get("value")?.clone(),
+            )),
+            "str_replace" => Some(Self::str_replace(
+                args.get("src")?.clone(),
+                args.get("from")?.clone(),
+                args.get("to")?.clone(),
+            )),
+            "str_split" => Some(Self::str_split(
+                args.get("src")?.clone(),
+                args.get("sep")?.clone(),
+            )),
+            "str_join" => Some(Self::str_join(
+                args.get("src")?.clone(),
+                args.get("sep")?.clone(),
+            )),
+            "str_slice" => Some(Self::str_slice(
+                args.get("src")?.clone(),
+                args.get("start")?.clone(),
+                args.get("end")?.clone(),
+            )),
+            "str_hash" => Some(Self::str_hash(args.get("src")?.clone())),
 
-            "array_contains" => Some(Self::array_contains(
+            "dict_len" => Some(Self::dict_len(args.get("src")?.clone())),
+            "dict_keys" => Some(Self::dict_keys(args.get("src")?.clone())),
+            "dict_values" => Some(Self::dict_values(args.get("src")?.clone())),
+            "dict_contains" => Some(Self::dict_contains(
                 args.get("src")?.clone(),
+                args.get("key")?.clone(),
+            )),
+
+
+            _ => None,
+        }
+    }
+
+    // Builtins that change `src`; `target` is modified in place and the
+    // remaining arguments are read from `args`.
+    pub(crate) fn call_builtin_mut(&mut self, name: &str, target: &mut Value, args: &HashMap<String, Value>) -> Option<RuntimeResult<Value>> {
+        match name {
+            "array_set" => Some(Self::array_set(
+                target,
+                args.get("idx")?.clone(),
                 args.get("value")?.clone(),
             )),
+            "array_push" => Some(Self::array_push(target, args.get("value")?.clone())),
+            "array_pop" => Some(Self::array_pop(target)),
+            "array_insert" => Some(Self::array_insert(
+                target,
+                args.get("idx")?.clone(),
+                args.get("value")?.clone(),
+            )),
+            "array_remove" => Some(Self::array_remove(target, args.get("idx")?.clone())),
+            "array_clear" => Some(Self::array_clear(target)),
 
+            "set_add" => Some(Self::set_add(target, args.get("value")?.clone())),
+            "set_remove" => Some(Self::set_remove(target, args.get("value")?.clone())),
+
+            "dict_remove" => Some(Self::dict_remove(target, args.get("key")?.clone())),
 
             _ => None,
         }
     }
 }
+
+pub(crate) fn is_mutating_builtin(name: &str) -> bool {
+    matches!(
+        name,
+        "array_set"
+            | "array_push"
+            | "array_pop"
+            | "array_insert"
+            | "array_remove"
+            | "array_clear"
+            | "set_add"
+            | "set_remove"
+            | "dict_remove"
+    )
+}
@@ -0,0 +1,14 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::interpreter::access_mod::to_dict_key;
+use crate::library::dict_utils::expect_dict;
+
+impl Interpreter {
+    // dict_contains(src=dict, key=any) -> Bool
+    pub(crate) fn dict_contains(src: Value, key: Value) -> RuntimeResult<Value> {
+        let dict = expect_dict(src, "dict_contains")?;
+        let k = to_dict_key(key)?;
+        Ok(Value::Bool(dict.contains_key(&k)))
+    }
+}
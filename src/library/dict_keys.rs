@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::dict_utils::{expect_dict, sorted_keys};
+
+impl Interpreter {
+    // dict_keys(src=dict) -> Array of keys, sorted
+    pub(crate) fn dict_keys(src: Value) -> RuntimeResult<Value> {
+        let dict = expect_dict(src, "dict_keys")?;
+        Ok(Value::array(sorted_keys(&dict).into_iter().map(Value::String).collect()))
+    }
+}
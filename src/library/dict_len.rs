@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::dict_utils::expect_dict;
+
+impl Interpreter {
+    // dict_len(src=dict) -> Int
+    pub(crate) fn dict_len(src: Value) -> RuntimeResult<Value> {
+        let dict = expect_dict(src, "dict_len")?;
+        Ok(Value::Int(dict.len() as i128))
+    }
+}
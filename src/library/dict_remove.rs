@@ -0,0 +1,15 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::interpreter::access_mod::to_dict_key;
+use crate::library::dict_utils::expect_dict_mut;
+
+impl Interpreter {
+    // dict_remove(src=dict, key=any) -> dict (missing keys are ignored)
+    pub(crate) fn dict_remove(src: &mut Value, key: Value) -> RuntimeResult<Value> {
+        let dict = expect_dict_mut(src, "dict_remove")?;
+        let k = to_dict_key(key)?;
+        dict.remove(&k);
+        Ok(src.clone())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::values::Value;
+use std::rc::Rc;
+use std::collections::HashMap;
+
+pub fn expect_dict(value: Value, fname: &str) -> RuntimeResult<HashMap<String, Value>> {
+    match value {
+        Value::Dictionary(d) => Ok(Rc::unwrap_or_clone(d)),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects Dictionary, got {}",
+            fname,
+            other.type_name()
+        ))),
+    }
+}
+
+// Mutable access for in-place builtins; copies the storage only if it is shared
+pub fn expect_dict_mut<'a>(value: &'a mut Value, fname: &str) -> RuntimeResult<&'a mut HashMap<String, Value>> {
+    match value {
+        Value::Dictionary(v) => Ok(Rc::make_mut(v)),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects Dictionary, got {}",
+            fname,
+            other.type_name()
+        ))),
+    }
+}
+
+// Dictionary keys in sorted order, so results do not depend on hashing
+pub fn sorted_keys(dict: &HashMap<String, Value>) -> Vec<String> {
+    let mut keys: Vec<String> = dict.keys().cloned().collect();
+    keys.sort();
+    keys
+}
@@ -0,0 +1,16 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::dict_utils::{expect_dict, sorted_keys};
+
+impl Interpreter {
+    // dict_values(src=dict) -> Array of values, in sorted key order
+    pub(crate) fn dict_values(src: Value) -> RuntimeResult<Value> {
+        let mut dict = expect_dict(src, "dict_values")?;
+        let values = sorted_keys(&dict)
+            .iter()
+            .filter_map(|k| dict.remove(k))
+            .collect();
+        Ok(Value::array(values))
+    }
+}
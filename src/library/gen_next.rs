@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::gen_utils::expect_generator;
+
+impl Interpreter {
+    // next(src=generator) -> any, nil once the generator is finished
+    pub(crate) fn gen_next(&mut self, src: Value) -> RuntimeResult<Value> {
+        let generator = expect_generator(src, "next")?;
+        Ok(self.resume_generator(&generator)?.unwrap_or(Value::Nil))
+    }
+}
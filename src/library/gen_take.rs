@@ -0,0 +1,18 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_int;
+use crate::library::gen_utils::expect_generator;
+
+impl Interpreter {
+    // gen_take(src=generator, n=Int) -> Array of up to n further values
+    pub(crate) fn gen_take(&mut self, src: Value, n: Value) -> RuntimeResult<Value> {
+        let generator = expect_generator(src, "gen_take")?;
+        let n = expect_int(n, "gen_take", "n")?;
+        if n < 0 {
+            return Err(RuntimeError::custom("gen_take expects a non-negative 'n'"));
+        }
+        let items = self.drain_generator(&generator, Some(n as usize))?;
+        Ok(Value::array(items))
+    }
+}
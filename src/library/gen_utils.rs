@@ -0,0 +1,14 @@
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::generator::GeneratorRef;
+use crate::values::values::Value;
+
+pub fn expect_generator(value: Value, fname: &str) -> RuntimeResult<GeneratorRef> {
+    match value {
+        Value::Generator(g) => Ok(g),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects Generator, got {}",
+            fname,
+            other.type_name()
+        ))),
+    }
+}
@@ -0,0 +1,15 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::{expect_integer, expect_number, integer_result, is_integer};
+
+impl Interpreter {
+    // math_abs(src=number) -> same numeric type
+    pub(crate) fn math_abs(src: Value) -> RuntimeResult<Value> {
+        if is_integer(&src) {
+            let n = expect_integer(src.clone(), "math_abs", "src")?;
+            return Ok(integer_result(n.abs(), &[&src]));
+        }
+        Ok(Value::Float(expect_number(src, "math_abs", "src")?.abs()))
+    }
+}
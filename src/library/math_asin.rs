@@ -0,0 +1,11 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::float_fn;
+
+impl Interpreter {
+    // math_asin(src=number) -> Float (arcsine)
+    pub(crate) fn math_asin(src: Value) -> RuntimeResult<Value> {
+        float_fn(src, "math_asin", f64::asin)
+    }
+}
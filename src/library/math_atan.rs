@@ -0,0 +1,11 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::float_fn;
+
+impl Interpreter {
+    // math_atan(src=number) -> Float (arctangent)
+    pub(crate) fn math_atan(src: Value) -> RuntimeResult<Value> {
+        float_fn(src, "math_atan", f64::atan)
+    }
+}
@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::expect_number;
+
+impl Interpreter {
+    // math_atan2(y=number, x=number) -> Float (angle of the point (x, y))
+    pub(crate) fn math_atan2(y: Value, x: Value) -> RuntimeResult<Value> {
+        let y = expect_number(y, "math_atan2", "y")?;
+        let x = expect_number(x, "math_atan2", "x")?;
+        Ok(Value::Float(y.atan2(x)))
+    }
+}
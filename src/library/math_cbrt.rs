@@ -0,0 +1,22 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::{exact_or_float, expect_number, is_integer};
+
+impl Interpreter {
+    // math_cbrt(src=number) -> Int for perfect cubes, Float otherwise
+    pub(crate) fn math_cbrt(src: Value) -> RuntimeResult<Value> {
+        let int_input = is_integer(&src);
+        let x = expect_number(src.clone(), "math_cbrt", "src")?;
+        let root = x.cbrt().round();
+
+        // Only trust the rounded root if it cubes back exactly
+        if int_input && let Value::Int(i) = src
+            && let Some(cube) = (root as i128).checked_pow(3)
+            && cube == i
+        {
+            return Ok(Value::Int(root as i128));
+        }
+        Ok(exact_or_float(x.cbrt(), false))
+    }
+}
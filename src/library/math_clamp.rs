@@ -0,0 +1,22 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::math_utils::{compare_numbers, is_nan};
+use std::cmp::Ordering;
+
+impl Interpreter {
+    // math_clamp(src=number, lo=number, hi=number) -> src limited to [lo, hi]
+    pub(crate) fn math_clamp(src: Value, lo: Value, hi: Value) -> RuntimeResult<Value> {
+        // A NaN src comes back unchanged; NaN bounds make no range
+        if is_nan(&lo) || is_nan(&hi) || compare_numbers(&lo, &hi, "math_clamp")? == Ordering::Greater {
+            return Err(RuntimeError::custom("math_clamp expects lo <= hi"));
+        }
+        if compare_numbers(&src, &lo, "math_clamp")? == Ordering::Less {
+            return Ok(lo);
+        }
+        if compare_numbers(&src, &hi, "math_clamp")? == Ordering::Greater {
+            return Ok(hi);
+        }
+        Ok(src)
+    }
+}
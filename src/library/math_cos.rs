@@ -0,0 +1,11 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::float_fn;
+
+impl Interpreter {
+    // math_cos(src=number) -> Float (cosine)
+    pub(crate) fn math_cos(src: Value) -> RuntimeResult<Value> {
+        float_fn(src, "math_cos", f64::cos)
+    }
+}
@@ -0,0 +1,11 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::float_fn;
+
+impl Interpreter {
+    // math_exp(src=number) -> Float (e^src)
+    pub(crate) fn math_exp(src: Value) -> RuntimeResult<Value> {
+        float_fn(src, "math_exp", f64::exp)
+    }
+}
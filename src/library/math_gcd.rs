@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::{expect_integer, integer_result};
+
+impl Interpreter {
+    // math_gcd(x=integer, y=integer) -> greatest common divisor (non-negative)
+    pub(crate) fn math_gcd(a: Value, b: Value) -> RuntimeResult<Value> {
+        let x = expect_integer(a.clone(), "math_gcd", "x")?;
+        let y = expect_integer(b.clone(), "math_gcd", "y")?;
+        Ok(integer_result(x.gcd(&y), &[&a, &b]))
+    }
+}
@@ -0,0 +1,15 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::math_utils::{expect_integer, integer_result};
+
+impl Interpreter {
+    // math_isqrt(src=integer) -> floor of the square root
+    pub(crate) fn math_isqrt(src: Value) -> RuntimeResult<Value> {
+        let n = expect_integer(src.clone(), "math_isqrt", "src")?;
+        let root = n.isqrt().ok_or_else(|| {
+            RuntimeError::custom(format!("math_isqrt of negative number {}", n))
+        })?;
+        Ok(integer_result(root, &[&src]))
+    }
+}
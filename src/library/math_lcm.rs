@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::{expect_integer, integer_result, BigInt};
+
+impl Interpreter {
+    // math_lcm(x=integer, y=integer) -> least common multiple (non-negative)
+    pub(crate) fn math_lcm(a: Value, b: Value) -> RuntimeResult<Value> {
+        let x = expect_integer(a.clone(), "math_lcm", "x")?;
+        let y = expect_integer(b.clone(), "math_lcm", "y")?;
+        if x.is_zero() || y.is_zero() {
+            return Ok(integer_result(BigInt::zero(), &[&a, &b]));
+        }
+        let (q, _) = x.abs().div_rem(&x.gcd(&y)).unwrap_or((BigInt::zero(), BigInt::zero()));
+        Ok(integer_result(q.mul(&y.abs()), &[&a, &b]))
+    }
+}
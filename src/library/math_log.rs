@@ -0,0 +1,15 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::expect_number;
+
+impl Interpreter {
+    // math_log(src=number, base=number?) -> Float (natural log without a base)
+    pub(crate) fn math_log(src: Value, base: Option<Value>) -> RuntimeResult<Value> {
+        let x = expect_number(src, "math_log", "src")?;
+        match base {
+            None | Some(Value::Nil) => Ok(Value::Float(x.ln())),
+            Some(b) => Ok(Value::Float(x.log(expect_number(b, "math_log", "base")?))),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::float_fn;
+
+impl Interpreter {
+    // math_log10(src=number) -> Float (base-10 logarithm)
+    pub(crate) fn math_log10(src: Value) -> RuntimeResult<Value> {
+        float_fn(src, "math_log10", f64::log10)
+    }
+}
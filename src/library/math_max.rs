@@ -0,0 +1,18 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::{compare_numbers, is_nan};
+use std::cmp::Ordering;
+
+impl Interpreter {
+    // math_max(x=number, y=number) -> the larger argument, keeping its type
+    pub(crate) fn math_max(a: Value, b: Value) -> RuntimeResult<Value> {
+        let order = compare_numbers(&b, &a, "math_max")?;
+        // A NaN operand is skipped whichever side it is on, as in f64::max
+        if is_nan(&a) || (order == Ordering::Greater && !is_nan(&b)) {
+            Ok(b)
+        } else {
+            Ok(a)
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::{compare_numbers, is_nan};
+use std::cmp::Ordering;
+
+impl Interpreter {
+    // math_min(x=number, y=number) -> the smaller argument, keeping its type
+    pub(crate) fn math_min(a: Value, b: Value) -> RuntimeResult<Value> {
+        let order = compare_numbers(&b, &a, "math_min")?;
+        // A NaN operand is skipped whichever side it is on, as in f64::min
+        if is_nan(&a) || (order == Ordering::Less && !is_nan(&b)) {
+            Ok(b)
+        } else {
+            Ok(a)
+        }
+    }
+}
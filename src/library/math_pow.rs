@@ -0,0 +1,23 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::{expect_integer, expect_number, integer_result, is_integer};
+
+impl Interpreter {
+    // math_pow(base=number, exp=number) -> exact integer for integer base and
+    // non-negative integer exponent, Float otherwise
+    pub(crate) fn math_pow(base: Value, exp: Value) -> RuntimeResult<Value> {
+        if is_integer(&base) && is_integer(&exp) {
+            let e = expect_integer(exp.clone(), "math_pow", "exp")?;
+            if let Some(e) = e.to_u128().and_then(|e| u64::try_from(e).ok())
+                && e <= 1 << 20
+            {
+                let b = expect_integer(base.clone(), "math_pow", "base")?;
+                return Ok(integer_result(b.pow(e), &[&base]));
+            }
+        }
+        let b = expect_number(base, "math_pow", "base")?;
+        let e = expect_number(exp, "math_pow", "exp")?;
+        Ok(Value::Float(b.powf(e)))
+    }
+}
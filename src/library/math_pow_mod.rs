@@ -0,0 +1,19 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::math_utils::{expect_integer, integer_result};
+
+impl Interpreter {
+    // math_pow_mod(base=integer, exp=integer, modulus=integer) -> base^exp mod modulus
+    pub(crate) fn math_pow_mod(base: Value, exp: Value, modulus: Value) -> RuntimeResult<Value> {
+        let b = expect_integer(base.clone(), "math_pow_mod", "base")?;
+        let e = expect_integer(exp.clone(), "math_pow_mod", "exp")?;
+        let m = expect_integer(modulus.clone(), "math_pow_mod", "modulus")?;
+
+        if e.is_negative() {
+            return Err(RuntimeError::custom("math_pow_mod expects a non-negative exponent"));
+        }
+        let r = b.pow_mod(&e, &m).ok_or_else(RuntimeError::division_by_zero)?;
+        Ok(integer_result(r, &[&base, &exp, &modulus]))
+    }
+}
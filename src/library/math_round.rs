@@ -0,0 +1,44 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_string;
+use crate::library::math_utils::{expect_number, is_integer};
+
+impl Interpreter {
+    // math_round(src=number, mode=String?) -> integers unchanged, Float rounded
+    // Modes: "half_up" (default, ties away from zero), "half_even",
+    // "half_down" (ties toward zero), "floor", "ceil", "trunc"
+    pub(crate) fn math_round(src: Value, mode: Option<Value>) -> RuntimeResult<Value> {
+        let mode = match mode {
+            None | Some(Value::Nil) => "half_up".to_string(),
+            Some(m) => expect_string(m, "math_round", "mode")?,
+        };
+
+        if is_integer(&src) {
+            return Ok(src);
+        }
+        let x = expect_number(src, "math_round", "src")?;
+
+        let rounded = match mode.as_str() {
+            "half_up" => x.round(),
+            "half_even" => x.round_ties_even(),
+            "half_down" => {
+                if (x - x.trunc()).abs() == 0.5 {
+                    x.trunc()
+                } else {
+                    x.round()
+                }
+            }
+            "floor" => x.floor(),
+            "ceil" => x.ceil(),
+            "trunc" => x.trunc(),
+            other => {
+                return Err(RuntimeError::custom(format!(
+                    "math_round: unknown rounding mode '{}'",
+                    other
+                )))
+            }
+        };
+        Ok(Value::Float(rounded))
+    }
+}
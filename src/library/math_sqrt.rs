@@ -0,0 +1,23 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::math_utils::{expect_integer, expect_number, integer_result, is_integer};
+
+impl Interpreter {
+    // math_sqrt(src=number) -> Int for perfect squares, Float otherwise
+    pub(crate) fn math_sqrt(src: Value) -> RuntimeResult<Value> {
+        if is_integer(&src) {
+            let n = expect_integer(src.clone(), "math_sqrt", "src")?;
+            if n.is_negative() {
+                return Ok(Value::Float(f64::NAN));
+            }
+            let root = n
+                .isqrt()
+                .ok_or_else(|| RuntimeError::custom("math_sqrt: invalid argument"))?;
+            if root.mul(&root) == n {
+                return Ok(integer_result(root, &[&src]));
+            }
+        }
+        Ok(Value::Float(expect_number(src, "math_sqrt", "src")?.sqrt()))
+    }
+}
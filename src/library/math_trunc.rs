@@ -0,0 +1,14 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::math_utils::{expect_number, is_integer};
+
+impl Interpreter {
+    // math_trunc(src=number) -> integers unchanged, Float rounds toward zero
+    pub(crate) fn math_trunc(src: Value) -> RuntimeResult<Value> {
+        if is_integer(&src) {
+            return Ok(src);
+        }
+        Ok(Value::Float(expect_number(src, "math_trunc", "src")?.trunc()))
+    }
+}
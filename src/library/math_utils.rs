@@ -0,0 +1,581 @@
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::values::Value;
+use std::cmp::Ordering;
+
+// ---------------------------------------------------------------------------
+// Argument helpers
+// ---------------------------------------------------------------------------
+
+pub fn expect_number(value: Value, fname: &str, arg: &str) -> RuntimeResult<f64> {
+    match value {
+        Value::Int(i) => Ok(i as f64),
+        Value::UInt(u) => Ok(u as f64),
+        Value::Float(f) => Ok(f),
+        Value::BigInt(s) => Ok(BigInt::parse(&s)
+            .map(|b| b.to_f64())
+            .unwrap_or(f64::NAN)),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects a number for '{}', got {}",
+            fname,
+            arg,
+            other.type_name()
+        ))),
+    }
+}
+
+pub fn is_integer(value: &Value) -> bool {
+    matches!(value, Value::Int(_) | Value::UInt(_) | Value::BigInt(_))
+}
+
+pub fn is_nan(value: &Value) -> bool {
+    matches!(value, Value::Float(f) if f.is_nan())
+}
+
+pub fn expect_integer(value: Value, fname: &str, arg: &str) -> RuntimeResult<BigInt> {
+    match value {
+        Value::Int(i) => Ok(BigInt::from_i128(i)),
+        Value::UInt(u) => Ok(BigInt::from_u128(u)),
+        Value::BigInt(s) => BigInt::parse(&s).ok_or_else(|| {
+            RuntimeError::custom(format!("{} got a malformed BigInt for '{}': {}", fname, arg, s))
+        }),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects Int, UInt or BigInt for '{}', got {}",
+            fname,
+            arg,
+            other.type_name()
+        ))),
+    }
+}
+
+// Converts an integer result back into the narrowest Value that the
+// argument types allow: UInt when every argument was a UInt, otherwise Int,
+// widening to BigInt only when the result does not fit.
+pub fn integer_result(n: BigInt, args: &[&Value]) -> Value {
+    let all_uint = !args.is_empty() && args.iter().all(|v| matches!(v, Value::UInt(_)));
+    if all_uint && let Some(u) = n.to_u128() {
+        return Value::UInt(u);
+    }
+    match n.to_i128() {
+        Some(i) => Value::Int(i),
+        None => Value::BigInt(n.to_string()),
+    }
+}
+
+// Orders two numbers of any numeric type; integers compare exactly and
+// NaN compares equal to everything, so callers handle it with `is_nan`.
+pub fn compare_numbers(a: &Value, b: &Value, fname: &str) -> RuntimeResult<Ordering> {
+    if is_integer(a) && is_integer(b) {
+        let x = expect_integer(a.clone(), fname, "x")?;
+        let y = expect_integer(b.clone(), fname, "y")?;
+        return Ok(x.cmp(&y));
+    }
+    let x = expect_number(a.clone(), fname, "x")?;
+    let y = expect_number(b.clone(), fname, "y")?;
+    Ok(x.partial_cmp(&y).unwrap_or(Ordering::Equal))
+}
+
+// Applies a float function, so trig/log/exp accept every numeric type.
+pub fn float_fn(src: Value, fname: &str, f: fn(f64) -> f64) -> RuntimeResult<Value> {
+    Ok(Value::Float(f(expect_number(src, fname, "src")?)))
+}
+
+// Float result that collapses to Int when the inputs were integers and the
+// value is exactly representable.
+pub fn exact_or_float(f: f64, int_input: bool) -> Value {
+    if int_input && f.is_finite() && f.fract() == 0.0 && f.abs() < 2f64.powi(53) {
+        Value::Int(f as i128)
+    } else {
+        Value::Float(f)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Minimal arbitrary-precision integer (sign + base 2^32 magnitude)
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BigInt {
+    negative: bool,
+    mag: Vec<u32>, // little-endian, no trailing zeros
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        Self {
+            negative: false,
+            mag: Vec::new(),
+        }
+    }
+
+    pub fn from_u128(mut u: u128) -> Self {
+        let mut mag = Vec::new();
+        while u > 0 {
+            mag.push(u as u32);
+            u >>= 32;
+        }
+        Self {
+            negative: false,
+            mag,
+        }
+    }
+
+    pub fn from_i128(i: i128) -> Self {
+        let mut b = Self::from_u128(i.unsigned_abs());
+        b.negative = i < 0;
+        b
+    }
+
+    // Accepts plain decimal text as well as the "(base N) digits" form the
+    // lexer produces for oversized radix literals.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let (radix, body) = match s.strip_prefix("(base ") {
+            Some(rest) => {
+                let (r, digits) = rest.split_once(") ")?;
+                (r.parse::<u32>().ok()?, digits)
+            }
+            None => (10, s),
+        };
+
+        let (negative, digits) = match body.strip_prefix('-') {
+            Some(d) => (true, d),
+            None => (false, body.strip_prefix('+').unwrap_or(body)),
+        };
+        if digits.is_empty() {
+            return None;
+        }
+
+        let mut out = Self::zero();
+        for c in digits.chars() {
+            let d = c.to_digit(radix)?;
+            out.mul_small_add(radix, d);
+        }
+        out.negative = negative && !out.mag.is_empty();
+        Some(out)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.mag.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_even(&self) -> bool {
+        self.mag.first().is_none_or(|l| l & 1 == 0)
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            negative: false,
+            mag: self.mag.clone(),
+        }
+    }
+
+    pub fn neg(&self) -> Self {
+        Self {
+            negative: !self.negative && !self.mag.is_empty(),
+            mag: self.mag.clone(),
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.negative || self.mag.len() > 4 {
+            return None;
+        }
+        Some(self.mag.iter().rev().fold(0u128, |acc, l| (acc << 32) | *l as u128))
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.mag.len() > 4 {
+            return None;
+        }
+        let u = self.mag.iter().rev().fold(0u128, |acc, l| (acc << 32) | *l as u128);
+        if self.negative {
+            if u <= i128::MAX as u128 + 1 {
+                Some((u as i128).wrapping_neg())
+            } else {
+                None
+            }
+        } else {
+            i128::try_from(u).ok()
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let f = self
+            .mag
+            .iter()
+            .rev()
+            .fold(0.0f64, |acc, l| acc * 4294967296.0 + *l as f64);
+        if self.negative { -f } else { f }
+    }
+
+    fn trim(&mut self) {
+        while self.mag.last() == Some(&0) {
+            self.mag.pop();
+        }
+        if self.mag.is_empty() {
+            self.negative = false;
+        }
+    }
+
+    fn mul_small_add(&mut self, m: u32, a: u32) {
+        let mut carry = a as u64;
+        for limb in self.mag.iter_mut() {
+            let v = *limb as u64 * m as u64 + carry;
+            *limb = v as u32;
+            carry = v >> 32;
+        }
+        if carry > 0 {
+            self.mag.push(carry as u32);
+        }
+    }
+
+    // Divides the magnitude in place by a small divisor, returning the remainder.
+    fn div_small(&mut self, d: u32) -> u32 {
+        let mut rem = 0u64;
+        for limb in self.mag.iter_mut().rev() {
+            let cur = (rem << 32) | *limb as u64;
+            *limb = (cur / d as u64) as u32;
+            rem = cur % d as u64;
+        }
+        self.trim();
+        rem as u32
+    }
+
+    fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+        a.len()
+            .cmp(&b.len())
+            .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut out = Vec::with_capacity(a.len().max(b.len()) + 1);
+        let mut carry = 0u64;
+        for i in 0..a.len().max(b.len()) {
+            let v = *a.get(i).unwrap_or(&0) as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+            out.push(v as u32);
+            carry = v >> 32;
+        }
+        if carry > 0 {
+            out.push(carry as u32);
+        }
+        out
+    }
+
+    // Requires |a| >= |b|
+    fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut out = Vec::with_capacity(a.len());
+        let mut borrow = 0i64;
+        for (i, limb) in a.iter().enumerate() {
+            let mut v = *limb as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+            borrow = if v < 0 {
+                v += 1 << 32;
+                1
+            } else {
+                0
+            };
+            out.push(v as u32);
+        }
+        out
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let mut out = if self.negative == other.negative {
+            Self {
+                negative: self.negative,
+                mag: Self::add_mag(&self.mag, &other.mag),
+            }
+        } else if Self::cmp_mag(&self.mag, &other.mag) != Ordering::Less {
+            Self {
+                negative: self.negative,
+                mag: Self::sub_mag(&self.mag, &other.mag),
+            }
+        } else {
+            Self {
+                negative: other.negative,
+                mag: Self::sub_mag(&other.mag, &self.mag),
+            }
+        };
+        out.trim();
+        out
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.neg())
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+        let mut mag = vec![0u32; self.mag.len() + other.mag.len()];
+        for (i, a) in self.mag.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, b) in other.mag.iter().enumerate() {
+                let v = mag[i + j] as u64 + *a as u64 * *b as u64 + carry;
+                mag[i + j] = v as u32;
+                carry = v >> 32;
+            }
+            let mut k = i + other.mag.len();
+            while carry > 0 {
+                let v = mag[k] as u64 + carry;
+                mag[k] = v as u32;
+                carry = v >> 32;
+                k += 1;
+            }
+        }
+        let mut out = Self {
+            negative: self.negative != other.negative,
+            mag,
+        };
+        out.trim();
+        out
+    }
+
+    // Truncating division (quotient rounds toward zero, remainder takes the
+    // sign of the dividend), like Rust's `/` and `%` on primitives.
+    pub fn div_rem(&self, other: &Self) -> Option<(Self, Self)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (mut q, mut r) = Self::div_rem_mag(&self.mag, &other.mag);
+        q.negative = self.negative != other.negative;
+        r.negative = self.negative;
+        q.trim();
+        r.trim();
+        Some((q, r))
+    }
+
+    // Euclidean remainder, always in [0, |m|)
+    pub fn rem_euclid(&self, m: &Self) -> Option<Self> {
+        let (_, r) = self.div_rem(m)?;
+        Some(if r.negative { r.add(&m.abs()) } else { r })
+    }
+
+    // Knuth, TAOCP vol. 2, algorithm D
+    fn div_rem_mag(a: &[u32], b: &[u32]) -> (Self, Self) {
+        if Self::cmp_mag(a, b) == Ordering::Less {
+            return (
+                Self::zero(),
+                Self {
+                    negative: false,
+                    mag: a.to_vec(),
+                },
+            );
+        }
+
+        if b.len() == 1 {
+            let mut q = Self {
+                negative: false,
+                mag: a.to_vec(),
+            };
+            let r = q.div_small(b[0]);
+            return (q, Self::from_u128(r as u128));
+        }
+
+        let shift = b[b.len() - 1].leading_zeros();
+        let shl = |x: &[u32], extra: bool| -> Vec<u32> {
+            let mut out = Vec::with_capacity(x.len() + 1);
+            let mut carry = 0u32;
+            for limb in x {
+                out.push((limb << shift) | carry);
+                carry = if shift == 0 { 0 } else { limb >> (32 - shift) };
+            }
+            if extra {
+                out.push(carry);
+            }
+            out
+        };
+
+        let v = shl(b, false);
+        let mut u = shl(a, true);
+        let n = v.len();
+        let m = a.len() - n;
+        let mut q = vec![0u32; m + 1];
+        let base: u64 = 1 << 32;
+
+        for j in (0..=m).rev() {
+            let num = ((u[j + n] as u64) << 32) | u[j + n - 1] as u64;
+            let mut qhat = num / v[n - 1] as u64;
+            let mut rhat = num % v[n - 1] as u64;
+
+            while qhat >= base
+                || qhat as u128 * v[n - 2] as u128 > ((rhat as u128) << 32 | u[j + n - 2] as u128)
+            {
+                qhat -= 1;
+                rhat += v[n - 1] as u64;
+                if rhat >= base {
+                    break;
+                }
+            }
+
+            // Multiply and subtract
+            let mut borrow = 0i64;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let p = qhat * v[i] as u64 + carry;
+                carry = p >> 32;
+                let t = u[i + j] as i64 - borrow - (p & 0xffff_ffff) as i64;
+                u[i + j] = t as u32;
+                borrow = if t < 0 { 1 } else { 0 };
+            }
+            let t = u[j + n] as i64 - borrow - carry as i64;
+            u[j + n] = t as u32;
+
+            if t < 0 {
+                // qhat was one too large: add back
+                qhat -= 1;
+                let mut carry = 0u64;
+                for i in 0..n {
+                    let s = u[i + j] as u64 + v[i] as u64 + carry;
+                    u[i + j] = s as u32;
+                    carry = s >> 32;
+                }
+                u[j + n] = u[j + n].wrapping_add(carry as u32);
+            }
+            q[j] = qhat as u32;
+        }
+
+        // Unnormalize the remainder
+        let mut r = vec![0u32; n];
+        for i in 0..n {
+            r[i] = if shift == 0 {
+                u[i]
+            } else {
+                (u[i] >> shift) | (u[i + 1] << (32 - shift))
+            };
+        }
+
+        let mut q = Self {
+            negative: false,
+            mag: q,
+        };
+        let mut r = Self {
+            negative: false,
+            mag: r,
+        };
+        q.trim();
+        r.trim();
+        (q, r)
+    }
+
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.abs();
+        let mut b = other.abs();
+        while !b.is_zero() {
+            let r = a.rem_euclid(&b).unwrap_or_else(Self::zero);
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    // Floor of the square root of a non-negative number (Newton's method)
+    pub fn isqrt(&self) -> Option<Self> {
+        if self.negative {
+            return None;
+        }
+        if self.is_zero() {
+            return Some(Self::zero());
+        }
+        // Initial guess 2^ceil(bits/2) is always >= the root
+        let bits = self.mag.len() as u32 * 32 - self.mag[self.mag.len() - 1].leading_zeros();
+        let mut x = Self::from_u128(1).shl_bits(bits.div_ceil(2));
+        loop {
+            let (q, _) = self.div_rem(&x)?;
+            let mut y = x.add(&q);
+            y.div_small(2);
+            if Self::cmp_mag(&y.mag, &x.mag) != Ordering::Less {
+                return Some(x);
+            }
+            x = y;
+        }
+    }
+
+    fn shl_bits(&self, bits: u32) -> Self {
+        let mut out = self.clone();
+        for _ in 0..bits / 32 {
+            out.mag.insert(0, 0);
+        }
+        for _ in 0..bits % 32 {
+            out.mul_small_add(2, 0);
+        }
+        out
+    }
+
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut base = self.clone();
+        let mut acc = Self::from_u128(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc.mul(&base);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.mul(&base);
+            }
+        }
+        acc
+    }
+
+    pub fn pow_mod(&self, exp: &Self, modulus: &Self) -> Option<Self> {
+        if modulus.is_zero() || exp.negative {
+            return None;
+        }
+        let mut base = self.rem_euclid(modulus)?;
+        let mut acc = Self::from_u128(1).rem_euclid(modulus)?;
+        let mut e = exp.clone();
+        while !e.is_zero() {
+            if !e.is_even() {
+                acc = acc.mul(&base).rem_euclid(modulus)?;
+            }
+            e.div_small(2);
+            if !e.is_zero() {
+                base = base.mul(&base).rem_euclid(modulus)?;
+            }
+        }
+        Some(acc)
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => Self::cmp_mag(&self.mag, &other.mag),
+            (true, true) => Self::cmp_mag(&other.mag, &self.mag),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl std::fmt::Display for BigInt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // Peel off 9 decimal digits at a time
+        let mut n = self.abs();
+        let mut chunks = Vec::new();
+        while !n.is_zero() {
+            chunks.push(n.div_small(1_000_000_000));
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", chunks.pop().unwrap_or(0))?;
+        for c in chunks.iter().rev() {
+            write!(f, "{:09}", c)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use crate::values::values::Value;
+
+// A method maps onto a builtin: the receiver is passed as `receiver`
+// (normally "src") and positional arguments fill `params` in order.
+pub struct MethodSig {
+    pub builtin: &'static str,
+    pub receiver: &'static str,
+    pub params: &'static [&'static str],
+}
+
+type MethodTable = &'static [(&'static str, MethodSig)];
+
+const fn sig(builtin: &'static str, params: &'static [&'static str]) -> MethodSig {
+    MethodSig { builtin, receiver: "src", params }
+}
+
+// Binary builtins such as set_union(x, y) take the receiver as `x`
+const fn sig_xy(builtin: &'static str) -> MethodSig {
+    MethodSig { builtin, receiver: "x", params: &["y"] }
+}
+
+const ARRAY_METHODS: MethodTable = &[
+    ("len", sig("array_len", &[])),
+    ("is_empty", sig("array_is_empty", &[])),
+    ("get", sig("array_get", &["idx"])),
+    ("set", sig("array_set", &["idx", "value"])),
+    ("push", sig("array_push", &["value"])),
+    ("pop", sig("array_pop", &[])),
+    ("insert", sig("array_insert", &["idx", "value"])),
+    ("remove", sig("array_remove", &["idx"])),
+    ("clear", sig("array_clear", &[])),
+    ("clone", sig("array_clone", &[])),
+    ("slice", sig("array_slice", &["start", "end"])),
+    ("concat", MethodSig { builtin: "array_concat", receiver: "a", params: &["b"] }),
+    ("reverse", sig("array_reverse", &[])),
+    ("sort", sig("array_sort", &[])),
+    ("find", sig("array_find", &["value"])),
+    ("contains", sig("array_contains", &["value"])),
+    ("shuffle", sig("array_shuffle", &[])),
+    ("join", sig("str_join", &["sep"])),
+];
+
+const STRING_METHODS: MethodTable = &[
+    ("len", sig("str_len", &[])),
+    ("is_empty", sig("str_empty", &[])),
+    ("upper", sig("str_upper", &[])),
+    ("lower", sig("str_lower", &[])),
+    ("trim", sig("str_trim", &[])),
+    ("reverse", sig("str_reverse", &[])),
+    ("clone", sig("str_clone", &[])),
+    ("capitalize", sig("str_capitalize", &[])),
+    ("concat", sig("str_concat", &["value"])),
+    ("contains", sig("str_contains", &["value"])),
+    ("starts_with", sig("str_starts_with", &["value"])),
+    ("ends_with", sig("str_ends_with", &["value"])),
+    ("find", sig("str_find", &["value"])),
+    ("count", sig("str_count", &["value"])),
+    ("compare", sig("str_compare", &["value"])),
+    ("replace", sig("str_replace", &["from", "to"])),
+    ("split", sig("str_split", &["sep"])),
+    ("slice", sig("str_slice", &["start", "end"])),
+    ("hash", sig("str_hash", &[])),
+];
+
+const DICT_METHODS: MethodTable = &[
+    ("len", sig("dict_len", &[])),
+    ("keys", sig("dict_keys", &[])),
+    ("values", sig("dict_values", &[])),
+    ("contains", sig("dict_contains", &["key"])),
+    ("remove", sig("dict_remove", &["key"])),
+];
+
+const SET_METHODS: MethodTable = &[
+    ("len", sig("set_len", &[])),
+    ("contains", sig("set_contains", &["value"])),
+    ("add", sig("set_add", &["value"])),
+    ("remove", sig("set_remove", &["value"])),
+    ("union", sig_xy("set_union")),
+    ("intersection", sig_xy("set_intersection")),
+    ("difference", sig_xy("set_difference")),
+    ("is_subset", sig_xy("set_is_subset")),
+    ("to_array", sig("set_to_array", &[])),
+];
+
+const AXIS_METHODS: MethodTable = &[
+    ("shape", sig("axis_shape", &[])),
+    ("reshape", sig("axis_reshape", &["shape"])),
+    ("transpose", sig("axis_transpose", &[])),
+    ("dot", sig_xy("axis_dot")),
+    ("matmul", sig_xy("axis_matmul")),
+    ("sum", sig("axis_sum", &["dim"])),
+    ("mean", sig("axis_mean", &["dim"])),
+    ("min", sig("axis_min", &["dim"])),
+    ("max", sig("axis_max", &["dim"])),
+    ("slice", sig("axis_slice", &["start", "end", "dim"])),
+    ("to_array", sig("axis_to_array", &[])),
+];
+
+const RANGE_METHODS: MethodTable = &[
+    ("len", sig("range_len", &[])),
+    ("contains", sig("range_contains", &["value"])),
+    ("reverse", sig("range_reverse", &[])),
+    ("to_array", sig("range_to_array", &[])),
+];
+
+const GENERATOR_METHODS: MethodTable = &[
+    ("next", sig("next", &[])),
+    ("take", sig("gen_take", &["n"])),
+];
+
+pub fn lookup_method(receiver: &Value, method: &str) -> Option<&'static MethodSig> {
+    let table = match receiver {
+        Value::Array(_) => ARRAY_METHODS,
+        Value::String(_) => STRING_METHODS,
+        Value::Dictionary(_) => DICT_METHODS,
+        Value::Set(_) => SET_METHODS,
+        Value::Axis(_) => AXIS_METHODS,
+        Value::Range(_) => RANGE_METHODS,
+        Value::Generator(_) => GENERATOR_METHODS,
+        _ => return None,
+    };
+    table.iter().find(|(name, _)| *name == method).map(|(_, sig)| sig)
+}
@@ -1,6 +1,7 @@
 
 pub mod type_cast;
 pub mod call_buildin;
+pub mod methods;
 
 pub mod array_utils;
 pub mod array_new;
@@ -20,3 +21,114 @@ pub mod array_reverse;
 pub mod array_sort;
 pub mod array_find;
 pub mod array_contains;
+pub mod array_shuffle;
+
+pub mod time_utils;
+pub mod time_monotonic;
+pub mod time_diff;
+pub mod time_epoch;
+pub mod time_sleep;
+pub mod time_utc;
+pub mod time_local;
+pub mod time_format;
+pub mod time_parse;
+
+pub mod math_utils;
+pub mod math_abs;
+pub mod math_sqrt;
+pub mod math_cbrt;
+pub mod math_exp;
+pub mod math_log;
+pub mod math_log10;
+pub mod math_pow;
+pub mod math_sin;
+pub mod math_cos;
+pub mod math_tan;
+pub mod math_asin;
+pub mod math_acos;
+pub mod math_atan;
+pub mod math_atan2;
+pub mod math_floor;
+pub mod math_ceil;
+pub mod math_round;
+pub mod math_trunc;
+pub mod math_min;
+pub mod math_max;
+pub mod math_gcd;
+pub mod math_lcm;
+pub mod math_isqrt;
+pub mod math_pow_mod;
+pub mod math_clamp;
+
+pub mod rand_utils;
+pub mod rand_seed;
+pub mod rand_int;
+pub mod rand_float;
+pub mod rand_choice;
+pub mod rand_bytes;
+
+pub mod axis_utils;
+pub mod axis_shape;
+pub mod axis_reshape;
+pub mod axis_transpose;
+pub mod axis_dot;
+pub mod axis_matmul;
+pub mod axis_sum;
+pub mod axis_mean;
+pub mod axis_min;
+pub mod axis_max;
+pub mod axis_slice;
+pub mod axis_to_array;
+
+pub mod set_utils;
+pub mod set_len;
+pub mod set_contains;
+pub mod set_add;
+pub mod set_remove;
+pub mod set_union;
+pub mod set_intersection;
+pub mod set_difference;
+pub mod set_is_subset;
+pub mod set_to_array;
+pub mod set_from_array;
+
+pub mod range_utils;
+pub mod range_len;
+pub mod range_contains;
+pub mod range_reverse;
+pub mod range_to_array;
+
+pub mod gen_utils;
+pub mod gen_next;
+pub mod gen_take;
+
+pub mod var_history;
+pub mod veto;
+
+pub mod str_len;
+pub mod str_empty;
+pub mod str_upper;
+pub mod str_lower;
+pub mod str_trim;
+pub mod str_reverse;
+pub mod str_clone;
+pub mod str_capitalize;
+pub mod str_concat;
+pub mod str_contains;
+pub mod str_starts_with;
+pub mod str_ends_with;
+pub mod str_find;
+pub mod str_count;
+pub mod str_compare;
+pub mod str_replace;
+pub mod str_split;
+pub mod str_join;
+pub mod str_slice;
+pub mod str_hash;
+
+pub mod dict_utils;
+pub mod dict_len;
+pub mod dict_keys;
+pub mod dict_values;
+pub mod dict_contains;
+pub mod dict_remove;
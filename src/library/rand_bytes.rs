@@ -0,0 +1,28 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_int;
+
+impl Interpreter {
+    // rand_bytes(count=Int) -> array of Int in [0, 255]
+    pub(crate) fn rand_bytes(&mut self, count: Value) -> RuntimeResult<Value> {
+        let n = expect_int(count, "rand_bytes", "count")?;
+        if n < 0 {
+            return Err(RuntimeError::custom(format!(
+                "rand_bytes expects a non-negative count, got {}",
+                n
+            )));
+        }
+
+        let mut out = Vec::with_capacity(n as usize);
+        while out.len() < n as usize {
+            for b in self.rng().next_u64().to_le_bytes() {
+                if out.len() == n as usize {
+                    break;
+                }
+                out.push(Value::Int(b as i128));
+            }
+        }
+        Ok(Value::array(out))
+    }
+}
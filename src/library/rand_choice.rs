@@ -0,0 +1,16 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_array;
+
+impl Interpreter {
+    // rand_choice(src=array) -> random element
+    pub(crate) fn rand_choice(&mut self, src: Value) -> RuntimeResult<Value> {
+        let arr = expect_array(src, "rand_choice")?;
+        if arr.is_empty() {
+            return Err(RuntimeError::custom("rand_choice on empty array"));
+        }
+        let i = self.rng().below(arr.len() as u128) as usize;
+        Ok(arr[i].clone())
+    }
+}
@@ -0,0 +1,10 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+
+impl Interpreter {
+    // rand_float() -> Float in [0, 1)
+    pub(crate) fn rand_float(&mut self) -> RuntimeResult<Value> {
+        Ok(Value::Float(self.rng().next_f64()))
+    }
+}
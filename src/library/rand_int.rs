@@ -0,0 +1,23 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_int;
+
+impl Interpreter {
+    // rand_int(lo=Int, hi=Int) -> Int uniformly drawn from [lo, hi]
+    pub(crate) fn rand_int(&mut self, lo: Value, hi: Value) -> RuntimeResult<Value> {
+        let lo = expect_int(lo, "rand_int", "lo")?;
+        let hi = expect_int(hi, "rand_int", "hi")?;
+        if lo > hi {
+            return Err(RuntimeError::custom(format!(
+                "rand_int expects lo <= hi, got lo={}, hi={}",
+                lo, hi
+            )));
+        }
+
+        // Span of the inclusive range; wraps to 0 for the full i128 range
+        let span = (hi.wrapping_sub(lo) as u128).wrapping_add(1);
+        let offset = self.rng().below(span);
+        Ok(Value::Int(lo.wrapping_add(offset as i128)))
+    }
+}
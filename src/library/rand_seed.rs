@@ -0,0 +1,30 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::rand_utils::Rng;
+
+impl Interpreter {
+    // rand_seed(seed=Int?) -> Nil
+    // With a seed every following rand_* call is reproducible; without one
+    // the generator is reseeded from /dev/urandom.
+    pub(crate) fn rand_seed(&mut self, seed: Option<Value>) -> RuntimeResult<Value> {
+        self.rng = Some(match seed {
+            None | Some(Value::Nil) => Rng::from_entropy(),
+            Some(Value::Int(i)) => Rng::from_seed(i as u64 ^ ((i >> 64) as u64)),
+            Some(Value::UInt(u)) => Rng::from_seed(u as u64 ^ ((u >> 64) as u64)),
+            Some(other) => {
+                return Err(RuntimeError::custom(format!(
+                    "rand_seed expects Int for 'seed', got {}",
+                    other.type_name()
+                )))
+            }
+        });
+        Ok(Value::Nil)
+    }
+
+    // The generator is created lazily so scripts that never use randomness
+    // do not touch /dev/urandom.
+    pub(crate) fn rng(&mut self) -> &mut Rng {
+        self.rng.get_or_insert_with(Rng::from_entropy)
+    }
+}
@@ -0,0 +1,92 @@
+use std::io::Read;
+
+// xoshiro256** (Blackman & Vigna), seeded through splitmix64 so that any
+// 64-bit seed expands into a well-mixed, non-zero state.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    s: [u64; 4],
+}
+
+impl Rng {
+    pub fn from_seed(seed: u64) -> Self {
+        let mut sm = seed;
+        let mut next = || {
+            sm = sm.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = sm;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+        Self {
+            s: [next(), next(), next(), next()],
+        }
+    }
+
+    // Seeds from /dev/urandom, falling back to the clock and pid if the
+    // device cannot be read.
+    pub fn from_entropy() -> Self {
+        let mut buf = [0u8; 8];
+        let seeded = std::fs::File::open("/dev/urandom")
+            .and_then(|mut f| f.read_exact(&mut buf))
+            .is_ok();
+
+        let seed = if seeded {
+            u64::from_le_bytes(buf)
+        } else {
+            let nanos = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0);
+            nanos ^ ((std::process::id() as u64) << 32)
+        };
+        Self::from_seed(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.s[1] << 17;
+
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+
+        result
+    }
+
+    pub fn next_u128(&mut self) -> u128 {
+        ((self.next_u64() as u128) << 64) | self.next_u64() as u128
+    }
+
+    // Uniform float in [0, 1) with 53 bits of precision
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    // Uniform integer in [0, bound); bound == 0 means the full u128 range.
+    // Values below 2^N mod bound are rejected so the result is unbiased.
+    pub fn below(&mut self, bound: u128) -> u128 {
+        if bound == 0 {
+            return self.next_u128();
+        }
+        if bound <= u64::MAX as u128 {
+            let b = bound as u64;
+            let threshold = b.wrapping_neg() % b;
+            loop {
+                let v = self.next_u64();
+                if v >= threshold {
+                    return (v % b) as u128;
+                }
+            }
+        }
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let v = self.next_u128();
+            if v >= threshold {
+                return v % bound;
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::range_utils::expect_range;
+
+impl Interpreter {
+    // range_contains(src=range, value=any) -> Bool, without expanding the range
+    pub(crate) fn range_contains(src: Value, value: Value) -> RuntimeResult<Value> {
+        let range = expect_range(src, "range_contains")?;
+        Ok(Value::Bool(match value {
+            Value::Int(i) => range.contains(i),
+            _ => false,
+        }))
+    }
+}
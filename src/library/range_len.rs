@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::range_utils::expect_range;
+
+impl Interpreter {
+    // range_len(src=range) -> Int, or BigInt when the count is past the largest Int
+    pub(crate) fn range_len(src: Value) -> RuntimeResult<Value> {
+        let range = expect_range(src, "range_len")?;
+        let len = range.len();
+        Ok(i128::try_from(len).map_or_else(|_| Value::BigInt(len.to_string()), Value::Int))
+    }
+}
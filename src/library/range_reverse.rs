@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::range_utils::expect_range;
+
+impl Interpreter {
+    // range_reverse(src=range) -> Range over the same elements, last to first
+    pub(crate) fn range_reverse(src: Value) -> RuntimeResult<Value> {
+        let range = expect_range(src, "range_reverse")?;
+        Ok(Value::Range(range.reversed()?))
+    }
+}
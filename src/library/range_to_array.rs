@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::range_utils::expect_range;
+
+impl Interpreter {
+    // range_to_array(src=range) -> Array
+    pub(crate) fn range_to_array(src: Value) -> RuntimeResult<Value> {
+        let range = expect_range(src, "range_to_array")?;
+        Ok(Value::array(range.to_values("range_to_array")?))
+    }
+}
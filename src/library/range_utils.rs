@@ -0,0 +1,14 @@
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::range::Range;
+use crate::values::values::Value;
+
+pub fn expect_range(value: Value, fname: &str) -> RuntimeResult<Range> {
+    match value {
+        Value::Range(r) => Ok(r),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects Range, got {}",
+            fname,
+            other.type_name()
+        ))),
+    }
+}
@@ -0,0 +1,14 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::set_utils::expect_set_mut;
+use crate::values::set::SetKey;
+
+impl Interpreter {
+    // set_add(src=set, value=any) -> Set
+    pub(crate) fn set_add(src: &mut Value, value: Value) -> RuntimeResult<Value> {
+        let set = expect_set_mut(src, "set_add")?;
+        set.insert(SetKey::from_value(value)?);
+        Ok(src.clone())
+    }
+}
@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::set_utils::expect_set;
+use crate::values::set::SetKey;
+
+impl Interpreter {
+    // set_contains(src=set, value=any) -> Bool
+    pub(crate) fn set_contains(src: Value, value: Value) -> RuntimeResult<Value> {
+        let set = expect_set(src, "set_contains")?;
+        Ok(Value::Bool(set.contains(&SetKey::from_value(value)?)))
+    }
+}
@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::set_utils::expect_set;
+
+impl Interpreter {
+    // set_difference(x=set, y=set) -> elements of x not in y
+    pub(crate) fn set_difference(x: Value, y: Value) -> RuntimeResult<Value> {
+        let a = expect_set(x, "set_difference")?;
+        let b = expect_set(y, "set_difference")?;
+        Ok(Value::set(a.difference(&b).cloned().collect()))
+    }
+}
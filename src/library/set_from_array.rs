@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_array;
+use crate::values::set::{Set, SetKey};
+
+impl Interpreter {
+    // set_from_array(src=array) -> Set with duplicates dropped
+    pub(crate) fn set_from_array(src: Value) -> RuntimeResult<Value> {
+        let arr = expect_array(src, "set_from_array")?;
+        let set = arr
+            .into_iter()
+            .map(SetKey::from_value)
+            .collect::<RuntimeResult<Set>>()?;
+        Ok(Value::set(set))
+    }
+}
@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::set_utils::expect_set;
+
+impl Interpreter {
+    // set_intersection(x=set, y=set) -> elements in both
+    pub(crate) fn set_intersection(x: Value, y: Value) -> RuntimeResult<Value> {
+        let a = expect_set(x, "set_intersection")?;
+        let b = expect_set(y, "set_intersection")?;
+        Ok(Value::set(a.intersection(&b).cloned().collect()))
+    }
+}
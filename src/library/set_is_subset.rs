@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::set_utils::expect_set;
+
+impl Interpreter {
+    // set_is_subset(x=set, y=set) -> Bool, true when every element of x is in y
+    pub(crate) fn set_is_subset(x: Value, y: Value) -> RuntimeResult<Value> {
+        let a = expect_set(x, "set_is_subset")?;
+        let b = expect_set(y, "set_is_subset")?;
+        Ok(Value::Bool(a.is_subset(&b)))
+    }
+}
@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::set_utils::expect_set;
+
+impl Interpreter {
+    // set_len(src=set) -> Int
+    pub(crate) fn set_len(src: Value) -> RuntimeResult<Value> {
+        let set = expect_set(src, "set_len")?;
+        Ok(Value::Int(set.len() as i128))
+    }
+}
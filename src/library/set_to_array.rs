@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::set_utils::expect_set;
+use crate::values::set::SetKey;
+
+impl Interpreter {
+    // set_to_array(src=set) -> Array in set order
+    pub(crate) fn set_to_array(src: Value) -> RuntimeResult<Value> {
+        let set = expect_set(src, "set_to_array")?;
+        Ok(Value::array(set.iter().map(SetKey::to_value).collect()))
+    }
+}
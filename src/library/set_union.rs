@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::set_utils::expect_set;
+
+impl Interpreter {
+    // set_union(x=set, y=set) -> elements in either
+    pub(crate) fn set_union(x: Value, y: Value) -> RuntimeResult<Value> {
+        let a = expect_set(x, "set_union")?;
+        let b = expect_set(y, "set_union")?;
+        Ok(Value::set(a.union(&b).cloned().collect()))
+    }
+}
@@ -0,0 +1,27 @@
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::set::Set;
+use crate::values::values::Value;
+use std::rc::Rc;
+
+pub fn expect_set(value: Value, fname: &str) -> RuntimeResult<Set> {
+    match value {
+        Value::Set(s) => Ok(Rc::unwrap_or_clone(s)),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects Set, got {}",
+            fname,
+            other.type_name()
+        ))),
+    }
+}
+
+// Mutable access for in-place builtins; copies the storage only if it is shared
+pub fn expect_set_mut<'a>(value: &'a mut Value, fname: &str) -> RuntimeResult<&'a mut Set> {
+    match value {
+        Value::Set(v) => Ok(Rc::make_mut(v)),
+        other => Err(RuntimeError::custom(format!(
+            "{} expects Set, got {}",
+            fname,
+            other.type_name()
+        ))),
+    }
+}
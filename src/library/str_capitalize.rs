@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_capitalize(src=string) -> string with the first character upper-cased
+    pub(crate) fn str_capitalize(src: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_capitalize", "src")?;
+        let mut chars = s.chars();
+        let out = match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        };
+        Ok(Value::String(out))
+    }
+}
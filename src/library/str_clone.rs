@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_clone(src=string) -> string
+    pub(crate) fn str_clone(src: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_clone", "src")?;
+        Ok(Value::String(s))
+    }
+}
@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_compare(src=string, value=string) -> Int (-1, 0 or 1)
+    pub(crate) fn str_compare(src: Value, value: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_compare", "src")?;
+        let v = expect_string(value, "str_compare", "value")?;
+        Ok(Value::Int(s.cmp(&v) as i128))
+    }
+}
@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_concat(src=string, value=string) -> string
+    pub(crate) fn str_concat(src: Value, value: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_concat", "src")?;
+        let v = expect_string(value, "str_concat", "value")?;
+        Ok(Value::String(s + &v))
+    }
+}
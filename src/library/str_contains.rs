@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_contains(src=string, value=string) -> Bool
+    pub(crate) fn str_contains(src: Value, value: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_contains", "src")?;
+        let v = expect_string(value, "str_contains", "value")?;
+        Ok(Value::Bool(s.contains(&v)))
+    }
+}
@@ -0,0 +1,16 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_count(src=string, value=string) -> Int (non-overlapping occurrences)
+    pub(crate) fn str_count(src: Value, value: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_count", "src")?;
+        let v = expect_string(value, "str_count", "value")?;
+        if v.is_empty() {
+            return Err(RuntimeError::custom("str_count expects a non-empty 'value'"));
+        }
+        Ok(Value::Int(s.matches(&v).count() as i128))
+    }
+}
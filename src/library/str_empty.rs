@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_empty(src=string) -> Bool
+    pub(crate) fn str_empty(src: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_empty", "src")?;
+        Ok(Value::Bool(s.is_empty()))
+    }
+}
@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_ends_with(src=string, value=string) -> Bool
+    pub(crate) fn str_ends_with(src: Value, value: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_ends_with", "src")?;
+        let v = expect_string(value, "str_ends_with", "value")?;
+        Ok(Value::Bool(s.ends_with(&v)))
+    }
+}
@@ -0,0 +1,16 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_find(src=string, value=string) -> Int (character index) or Nil
+    pub(crate) fn str_find(src: Value, value: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_find", "src")?;
+        let v = expect_string(value, "str_find", "value")?;
+        Ok(match s.find(&v) {
+            Some(byte) => Value::Int(s[..byte].chars().count() as i128),
+            None => Value::Nil,
+        })
+    }
+}
@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_hash(src=string) -> Int (64-bit FNV-1a, stable across runs)
+    pub(crate) fn str_hash(src: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_hash", "src")?;
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in s.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        Ok(Value::Int(hash as i128))
+    }
+}
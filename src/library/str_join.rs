@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::{expect_array, expect_string};
+
+impl Interpreter {
+    // str_join(src=array of strings, sep=string) -> string
+    pub(crate) fn str_join(src: Value, sep: Value) -> RuntimeResult<Value> {
+        let arr = expect_array(src, "str_join")?;
+        let p = expect_string(sep, "str_join", "sep")?;
+        let parts = arr
+            .into_iter()
+            .map(|v| expect_string(v, "str_join", "src"))
+            .collect::<RuntimeResult<Vec<String>>>()?;
+        Ok(Value::String(parts.join(&p)))
+    }
+}
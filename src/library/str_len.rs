@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_len(src=string) -> Int (number of characters)
+    pub(crate) fn str_len(src: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_len", "src")?;
+        Ok(Value::Int(s.chars().count() as i128))
+    }
+}
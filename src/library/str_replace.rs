@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_replace(src=string, from=string, to=string) -> string
+    pub(crate) fn str_replace(src: Value, from: Value, to: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_replace", "src")?;
+        let f = expect_string(from, "str_replace", "from")?;
+        let t = expect_string(to, "str_replace", "to")?;
+        if f.is_empty() {
+            return Err(RuntimeError::custom("str_replace expects a non-empty 'from'"));
+        }
+        Ok(Value::String(s.replace(&f, &t)))
+    }
+}
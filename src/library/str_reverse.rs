@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_reverse(src=string) -> string
+    pub(crate) fn str_reverse(src: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_reverse", "src")?;
+        Ok(Value::String(s.chars().rev().collect()))
+    }
+}
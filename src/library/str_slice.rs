@@ -0,0 +1,25 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::{expect_int, expect_string};
+
+impl Interpreter {
+    // str_slice(src=string, start=int, end=int) -> string (character positions)
+    pub(crate) fn str_slice(src: Value, start: Value, end: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_slice", "src")?;
+        let st = expect_int(start, "str_slice", "start")?;
+        let en = expect_int(end, "str_slice", "end")?;
+
+        let chars: Vec<char> = s.chars().collect();
+        let len = chars.len() as i128;
+
+        let clamp = |i: i128| -> i128 {
+            let real = if i < 0 { len + i } else { i };
+            real.clamp(0, len)
+        };
+        let ss = clamp(st);
+        let ee = clamp(en).max(ss);
+
+        Ok(Value::String(chars[ss as usize..ee as usize].iter().collect()))
+    }
+}
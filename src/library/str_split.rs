@@ -0,0 +1,18 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_split(src=string, sep=string) -> Array of strings
+    pub(crate) fn str_split(src: Value, sep: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_split", "src")?;
+        let p = expect_string(sep, "str_split", "sep")?;
+        let parts: Vec<Value> = if p.is_empty() {
+            s.chars().map(|c| Value::String(c.to_string())).collect()
+        } else {
+            s.split(p.as_str()).map(|part| Value::String(part.to_string())).collect()
+        };
+        Ok(Value::array(parts))
+    }
+}
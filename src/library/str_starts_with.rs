@@ -0,0 +1,13 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_starts_with(src=string, value=string) -> Bool
+    pub(crate) fn str_starts_with(src: Value, value: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_starts_with", "src")?;
+        let v = expect_string(value, "str_starts_with", "value")?;
+        Ok(Value::Bool(s.starts_with(&v)))
+    }
+}
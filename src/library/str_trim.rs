@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_trim(src=string) -> string without leading/trailing whitespace
+    pub(crate) fn str_trim(src: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_trim", "src")?;
+        Ok(Value::String(s.trim().to_string()))
+    }
+}
@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+
+impl Interpreter {
+    // str_upper(src=string) -> string
+    pub(crate) fn str_upper(src: Value) -> RuntimeResult<Value> {
+        let s = expect_string(src, "str_upper", "src")?;
+        Ok(Value::String(s.to_uppercase()))
+    }
+}
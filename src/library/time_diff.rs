@@ -0,0 +1,16 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_int;
+
+impl Interpreter {
+    // elapsed(since=Int) -> Int (nanoseconds since a now_ns() reading)
+    pub(crate) fn elapsed(since: Value) -> RuntimeResult<Value> {
+        let start = expect_int(since, "elapsed", "since")?;
+        let now = match Self::now_ns()? {
+            Value::Int(n) => n,
+            _ => unreachable!(),
+        };
+        Ok(Value::Int(now - start))
+    }
+}
@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::time_utils::realtime_now;
+
+impl Interpreter {
+    // unix_time() -> Int (seconds since 1970-01-01T00:00:00Z)
+    pub(crate) fn unix_time() -> RuntimeResult<Value> {
+        let (secs, _) = realtime_now();
+        Ok(Value::Int(secs as i128))
+    }
+}
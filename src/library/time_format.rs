@@ -0,0 +1,103 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_string;
+use crate::library::time_utils::DateTime;
+
+const WEEKDAYS: [&str; 7] = [
+    "Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday",
+];
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June", "July", "August", "September",
+    "October", "November", "December",
+];
+
+impl Interpreter {
+    // time_format(src=Dictionary, fmt=String) -> String (strftime-style)
+    pub(crate) fn time_format(src: Value, fmt: Value) -> RuntimeResult<Value> {
+        let dt = DateTime::from_value(src, "time_format")?;
+        let fmt = expect_string(fmt, "time_format", "fmt")?;
+        Ok(Value::String(format_datetime(&dt, &fmt)?))
+    }
+}
+
+fn format_datetime(dt: &DateTime, fmt: &str) -> RuntimeResult<String> {
+    let mut out = String::new();
+    let mut chars = fmt.chars();
+
+    let hour12 = if dt.hour.is_multiple_of(12) { 12 } else { dt.hour % 12 };
+    let weekday = dt.weekday() as usize;
+    let month_name = MONTHS[(dt.month as usize).saturating_sub(1) % 12];
+
+    let zone_offset = |colon: bool| -> String {
+        let sign = if dt.offset < 0 { '-' } else { '+' };
+        let abs = dt.offset.abs();
+        if colon {
+            format!("{}{:02}:{:02}", sign, abs / 3600, abs % 3600 / 60)
+        } else {
+            format!("{}{:02}{:02}", sign, abs / 3600, abs % 3600 / 60)
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+
+        let spec = chars
+            .next()
+            .ok_or_else(|| RuntimeError::custom("time_format: dangling '%' at end of format"))?;
+
+        match spec {
+            'Y' => out.push_str(&dt.year.to_string()),
+            'C' => out.push_str(&format!("{:02}", dt.year.div_euclid(100))),
+            'y' => out.push_str(&format!("{:02}", dt.year.rem_euclid(100))),
+            'm' => out.push_str(&format!("{:02}", dt.month)),
+            'd' => out.push_str(&format!("{:02}", dt.day)),
+            'e' => out.push_str(&format!("{:2}", dt.day)),
+            'H' => out.push_str(&format!("{:02}", dt.hour)),
+            'I' => out.push_str(&format!("{:02}", hour12)),
+            'M' => out.push_str(&format!("{:02}", dt.minute)),
+            'S' => out.push_str(&format!("{:02}", dt.second)),
+            'f' => out.push_str(&format!("{:06}", dt.nanosecond / 1000)),
+            'p' => out.push_str(if dt.hour < 12 { "AM" } else { "PM" }),
+            'j' => out.push_str(&format!("{:03}", dt.yday())),
+            'a' => out.push_str(&WEEKDAYS[weekday][..3]),
+            'A' => out.push_str(WEEKDAYS[weekday]),
+            'b' | 'h' => out.push_str(&month_name[..3]),
+            'B' => out.push_str(month_name),
+            'u' => out.push_str(&(if weekday == 0 { 7 } else { weekday }).to_string()),
+            'w' => out.push_str(&weekday.to_string()),
+            's' => out.push_str(&dt.timestamp().to_string()),
+            'Z' => out.push_str(&dt.zone),
+            'z' => out.push_str(&zone_offset(false)),
+            ':' => {
+                if chars.next() != Some('z') {
+                    return Err(RuntimeError::custom("time_format: expected 'z' after '%:'"));
+                }
+                out.push_str(&zone_offset(true));
+            }
+            'F' => out.push_str(&format!("{}-{:02}-{:02}", dt.year, dt.month, dt.day)),
+            'T' => out.push_str(&format!("{:02}:{:02}:{:02}", dt.hour, dt.minute, dt.second)),
+            'R' => out.push_str(&format!("{:02}:{:02}", dt.hour, dt.minute)),
+            'D' => out.push_str(&format!(
+                "{:02}/{:02}/{:02}",
+                dt.month,
+                dt.day,
+                dt.year.rem_euclid(100)
+            )),
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            '%' => out.push('%'),
+            other => {
+                return Err(RuntimeError::custom(format!(
+                    "time_format: unknown format specifier '%{}'",
+                    other
+                )))
+            }
+        }
+    }
+
+    Ok(out)
+}
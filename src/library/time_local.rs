@@ -0,0 +1,25 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use std::rc::Rc;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::array_utils::expect_string;
+use crate::library::time_utils::{timestamp_arg, DateTime, Zone};
+
+impl Interpreter {
+    // time_local(ts=Int?, zone=String?) -> Dictionary
+    pub(crate) fn time_local(ts: Option<Value>, zone: Option<Value>) -> RuntimeResult<Value> {
+        let (secs, nanos) = timestamp_arg(ts, "time_local")?;
+
+        let zone = match zone {
+            None | Some(Value::Nil) => Zone::load(None)?,
+            Some(v) => Zone::load(Some(&expect_string(v, "time_local", "zone")?))?,
+        };
+
+        let (offset, dst, abbr) = zone.lookup(secs);
+        let mut dt = DateTime::from_timestamp(secs, nanos, offset, &abbr, dst)?.to_value();
+        if let Value::Dictionary(map) = &mut dt {
+            Rc::make_mut(map).insert("tz".into(), Value::String(zone.name().to_string()));
+        }
+        Ok(dt)
+    }
+}
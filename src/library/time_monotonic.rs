@@ -0,0 +1,18 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+
+impl Interpreter {
+    // now_ns() -> Int (monotonic clock, nanoseconds)
+    pub(crate) fn now_ns() -> RuntimeResult<Value> {
+        let mut ts = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        let rc = unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
+        if rc != 0 {
+            return Err(RuntimeError::custom("now_ns: monotonic clock unavailable"));
+        }
+        Ok(Value::Int(ts.tv_sec as i128 * 1_000_000_000 + ts.tv_nsec as i128))
+    }
+}
@@ -0,0 +1,136 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_string;
+use crate::library::time_utils::{days_in_month, DateTime};
+
+impl Interpreter {
+    // time_parse(src=String) -> Dictionary
+    // Accepts ISO-8601 dates and date-times:
+    //   2024-03-01
+    //   2024-03-01T14:30
+    //   2024-03-01T14:30:05.250Z
+    //   2024-03-01 14:30:05+05:30
+    pub(crate) fn time_parse(src: Value) -> RuntimeResult<Value> {
+        let text = expect_string(src, "time_parse", "src")?;
+        let dt = parse_iso8601(text.trim()).ok_or_else(|| {
+            RuntimeError::custom(format!("time_parse: '{}' is not a valid ISO-8601 date", text))
+        })?;
+        Ok(dt.to_value())
+    }
+}
+
+fn parse_iso8601(s: &str) -> Option<DateTime> {
+    let b = s.as_bytes();
+    let mut i = 0;
+
+    let digits = |i: &mut usize, n: usize| -> Option<u32> {
+        let part = s.get(*i..*i + n)?;
+        if !part.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        *i += n;
+        part.parse().ok()
+    };
+    let expect = |i: &mut usize, c: u8| -> Option<()> {
+        if b.get(*i) == Some(&c) {
+            *i += 1;
+            Some(())
+        } else {
+            None
+        }
+    };
+
+    let year = digits(&mut i, 4)? as i64;
+    expect(&mut i, b'-')?;
+    let month = digits(&mut i, 2)?;
+    expect(&mut i, b'-')?;
+    let day = digits(&mut i, 2)?;
+
+    if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let (mut hour, mut minute, mut second, mut nanosecond) = (0, 0, 0, 0);
+    let mut offset = 0i64;
+    let mut zone = String::from("UTC");
+
+    if i < b.len() {
+        if b[i] != b'T' && b[i] != b't' && b[i] != b' ' {
+            return None;
+        }
+        i += 1;
+
+        hour = digits(&mut i, 2)?;
+        expect(&mut i, b':')?;
+        minute = digits(&mut i, 2)?;
+
+        if b.get(i) == Some(&b':') {
+            i += 1;
+            second = digits(&mut i, 2)?;
+
+            if b.get(i) == Some(&b'.') || b.get(i) == Some(&b',') {
+                i += 1;
+                let start = i;
+                while i < b.len() && b[i].is_ascii_digit() {
+                    i += 1;
+                }
+                if i == start {
+                    return None;
+                }
+                let mut frac = s[start..i.min(start + 9)].to_string();
+                while frac.len() < 9 {
+                    frac.push('0');
+                }
+                nanosecond = frac.parse().ok()?;
+            }
+        }
+
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+
+        match b.get(i) {
+            None => {}
+            Some(b'Z') | Some(b'z') => i += 1,
+            Some(sign @ (b'+' | b'-')) => {
+                let sign = if *sign == b'-' { -1 } else { 1 };
+                i += 1;
+                let oh = digits(&mut i, 2)? as i64;
+                if b.get(i) == Some(&b':') {
+                    i += 1;
+                }
+                let om = if i < b.len() { digits(&mut i, 2)? as i64 } else { 0 };
+                if oh > 23 || om > 59 {
+                    return None;
+                }
+                offset = sign * (oh * 3600 + om * 60);
+                let abs = offset.abs();
+                zone = format!(
+                    "{}{:02}:{:02}",
+                    if offset < 0 { '-' } else { '+' },
+                    abs / 3600,
+                    abs % 3600 / 60
+                );
+            }
+            Some(_) => return None,
+        }
+    }
+
+    if i != b.len() {
+        return None;
+    }
+
+    Some(DateTime {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        nanosecond,
+        offset,
+        zone,
+        dst: false,
+    })
+}
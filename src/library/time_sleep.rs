@@ -0,0 +1,19 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::library::array_utils::expect_int;
+
+impl Interpreter {
+    // sleep_ms(ms=Int) -> Nil
+    pub(crate) fn sleep_ms(ms: Value) -> RuntimeResult<Value> {
+        let ms = expect_int(ms, "sleep_ms", "ms")?;
+        if ms < 0 || ms > u64::MAX as i128 {
+            return Err(RuntimeError::custom(format!(
+                "sleep_ms expects a non-negative duration, got {}",
+                ms
+            )));
+        }
+        std::thread::sleep(std::time::Duration::from_millis(ms as u64));
+        Ok(Value::Nil)
+    }
+}
@@ -0,0 +1,12 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::RuntimeResult;
+use crate::library::time_utils::{timestamp_arg, DateTime};
+
+impl Interpreter {
+    // time_utc(ts=Int?) -> Dictionary
+    pub(crate) fn time_utc(ts: Option<Value>) -> RuntimeResult<Value> {
+        let (secs, nanos) = timestamp_arg(ts, "time_utc")?;
+        Ok(DateTime::from_timestamp(secs, nanos, 0, "UTC", false)?.to_value())
+    }
+}
@@ -0,0 +1,657 @@
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::values::Value;
+use std::collections::HashMap;
+
+const ZONEINFO_DIR: &str = "/usr/share/zoneinfo";
+const LOCALTIME_PATH: &str = "/etc/localtime";
+
+// Bounds for dictionaries turned back into times
+const MAX_YEAR: i128 = 1_000_000_000;
+const MAX_OFFSET: i128 = 86_400;
+
+// Broken-down calendar time, the Rust side of the dictionaries returned
+// by time_utc / time_local / time_parse.
+#[derive(Debug, Clone)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+    pub offset: i64, // seconds east of UTC
+    pub zone: String,
+    pub dst: bool,
+}
+
+impl DateTime {
+    pub fn from_timestamp(secs: i64, nanos: u32, offset: i64, zone: &str, dst: bool) -> RuntimeResult<Self> {
+        let local = secs
+            .checked_add(offset)
+            .ok_or_else(|| RuntimeError::custom(format!("Timestamp {} is out of range in zone {}", secs, zone)))?;
+        let days = local.div_euclid(86_400);
+        let rem = local.rem_euclid(86_400);
+        let (year, month, day) = civil_from_days(days);
+
+        Ok(Self {
+            year,
+            month,
+            day,
+            hour: (rem / 3600) as u32,
+            minute: (rem % 3600 / 60) as u32,
+            second: (rem % 60) as u32,
+            nanosecond: nanos,
+            offset,
+            zone: zone.to_string(),
+            dst,
+        })
+    }
+
+    pub fn timestamp(&self) -> i64 {
+        let days = days_from_civil(self.year, self.month, self.day);
+        days * 86_400
+            + self.hour as i64 * 3600
+            + self.minute as i64 * 60
+            + self.second as i64
+            - self.offset
+    }
+
+    pub fn weekday(&self) -> u32 {
+        // 1970-01-01 was a Thursday; 0 = Sunday
+        (days_from_civil(self.year, self.month, self.day) + 4).rem_euclid(7) as u32
+    }
+
+    pub fn yday(&self) -> u32 {
+        (days_from_civil(self.year, self.month, self.day) - days_from_civil(self.year, 1, 1)) as u32
+            + 1
+    }
+
+    pub fn to_value(&self) -> Value {
+        let mut map: HashMap<String, Value> = HashMap::new();
+        map.insert("year".into(), Value::Int(self.year as i128));
+        map.insert("month".into(), Value::Int(self.month as i128));
+        map.insert("day".into(), Value::Int(self.day as i128));
+        map.insert("hour".into(), Value::Int(self.hour as i128));
+        map.insert("minute".into(), Value::Int(self.minute as i128));
+        map.insert("second".into(), Value::Int(self.second as i128));
+        map.insert(
+            "millisecond".into(),
+            Value::Int((self.nanosecond / 1_000_000) as i128),
+        );
+        map.insert("nanosecond".into(), Value::Int(self.nanosecond as i128));
+        map.insert("weekday".into(), Value::Int(self.weekday() as i128));
+        map.insert("yday".into(), Value::Int(self.yday() as i128));
+        map.insert("offset".into(), Value::Int(self.offset as i128));
+        map.insert("zone".into(), Value::String(self.zone.clone()));
+        map.insert("dst".into(), Value::Bool(self.dst));
+        map.insert("timestamp".into(), Value::Int(self.timestamp() as i128));
+        Value::dict(map)
+    }
+
+    pub fn from_value(value: Value, fname: &str) -> RuntimeResult<Self> {
+        let map = match value {
+            Value::Dictionary(map) => map,
+            other => {
+                return Err(RuntimeError::custom(format!(
+                    "{} expects Dictionary, got {}",
+                    fname,
+                    other.type_name()
+                )))
+            }
+        };
+
+        let field = |key: &str, default: i128| -> RuntimeResult<i128> {
+            match map.get(key) {
+                None => Ok(default),
+                Some(Value::Int(i)) => Ok(*i),
+                Some(other) => Err(RuntimeError::custom(format!(
+                    "{} expects Int for field '{}', got {}",
+                    fname,
+                    key,
+                    other.type_name()
+                ))),
+            }
+        };
+
+        // Each field within `range`, so the calendar arithmetic cannot overflow
+        let ranged = |key: &str, default: i128, range: std::ops::RangeInclusive<i128>| -> RuntimeResult<i128> {
+            let value = field(key, default)?;
+            if range.contains(&value) {
+                Ok(value)
+            } else {
+                Err(RuntimeError::custom(format!(
+                    "{} field '{}' must be between {} and {}, got {}",
+                    fname,
+                    key,
+                    range.start(),
+                    range.end(),
+                    value
+                )))
+            }
+        };
+
+        let year = ranged("year", 1970, -MAX_YEAR..=MAX_YEAR)?;
+        let month = ranged("month", 1, 1..=12)?;
+        let day = ranged("day", 1, 1..=31)?;
+        if day > days_in_month(year as i64, month as u32) as i128 {
+            return Err(RuntimeError::custom(format!(
+                "{} got an invalid date: month={}, day={}",
+                fname, month, day
+            )));
+        }
+
+        let nanosecond = match map.get("nanosecond") {
+            Some(_) => ranged("nanosecond", 0, 0..=999_999_999)?,
+            None => ranged("millisecond", 0, 0..=999)? * 1_000_000,
+        };
+
+        let zone = match map.get("zone") {
+            Some(Value::String(s)) => s.clone(),
+            _ => String::new(),
+        };
+
+        Ok(Self {
+            year: year as i64,
+            month: month as u32,
+            day: day as u32,
+            hour: ranged("hour", 0, 0..=23)? as u32,
+            minute: ranged("minute", 0, 0..=59)? as u32,
+            second: ranged("second", 0, 0..=59)? as u32,
+            nanosecond: nanosecond as u32,
+            offset: ranged("offset", 0, -MAX_OFFSET..=MAX_OFFSET)? as i64,
+            zone,
+            dst: matches!(map.get("dst"), Some(Value::Bool(true))),
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Calendar arithmetic (proleptic Gregorian, days relative to 1970-01-01)
+// ---------------------------------------------------------------------------
+
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let m = month as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+pub fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+pub fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+pub fn realtime_now() -> (i64, u32) {
+    match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        Ok(d) => (d.as_secs() as i64, d.subsec_nanos()),
+        Err(e) => {
+            // Clock is before the epoch
+            let d = e.duration();
+            (-(d.as_secs() as i64) - 1, 1_000_000_000 - d.subsec_nanos())
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Zoneinfo (TZif) support
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+struct LocalType {
+    offset: i64,
+    dst: bool,
+    abbr: String,
+}
+
+#[derive(Debug, Clone)]
+struct PosixRule {
+    std_abbr: String,
+    std_offset: i64,
+    dst: Option<(String, i64, RuleDate, i64, RuleDate, i64)>, // abbr, offset, start, start time, end, end time
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RuleDate {
+    Julian1(u32),           // Jn: 1..=365, Feb 29 never counted
+    Julian0(u32),           // n: 0..=365
+    MonthWeekDay(u32, u32, u32), // Mm.w.d
+}
+
+#[derive(Debug, Clone)]
+pub struct Zone {
+    name: String,
+    transitions: Vec<i64>,
+    indices: Vec<usize>,
+    types: Vec<LocalType>,
+    footer: Option<PosixRule>,
+}
+
+impl Zone {
+    pub fn utc() -> Self {
+        Self {
+            name: "UTC".into(),
+            transitions: Vec::new(),
+            indices: Vec::new(),
+            types: vec![LocalType {
+                offset: 0,
+                dst: false,
+                abbr: "UTC".into(),
+            }],
+            footer: None,
+        }
+    }
+
+    // Resolve a zone name (e.g. "Europe/Berlin") against /usr/share/zoneinfo.
+    // With no name, honour $TZ and fall back to /etc/localtime, then UTC.
+    pub fn load(name: Option<&str>) -> RuntimeResult<Self> {
+        let requested = match name {
+            Some(n) => Some(n.to_string()),
+            None => std::env::var("TZ").ok().filter(|s| !s.is_empty()),
+        };
+
+        let (label, path) = match requested {
+            Some(tz) => {
+                let tz = tz.trim_start_matches(':').to_string();
+                if tz.contains("..") {
+                    return Err(RuntimeError::custom(format!("Invalid time zone name '{}'", tz)));
+                }
+                let path = if tz.starts_with('/') {
+                    tz.clone()
+                } else {
+                    format!("{}/{}", ZONEINFO_DIR, tz)
+                };
+                (tz, path)
+            }
+            None => {
+                if !std::path::Path::new(LOCALTIME_PATH).exists() {
+                    return Ok(Self::utc());
+                }
+                let label = std::fs::read_link(LOCALTIME_PATH)
+                    .ok()
+                    .and_then(|p| {
+                        p.to_string_lossy()
+                            .split_once("zoneinfo/")
+                            .map(|(_, rest)| rest.to_string())
+                    })
+                    .unwrap_or_else(|| "localtime".into());
+                (label, LOCALTIME_PATH.to_string())
+            }
+        };
+
+        let bytes = std::fs::read(&path).map_err(|e| {
+            RuntimeError::custom(format!("Cannot read time zone '{}': {}", label, e))
+        })?;
+        Self::parse_tzif(&label, &bytes)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn parse_tzif(name: &str, bytes: &[u8]) -> RuntimeResult<Self> {
+        let bad = || RuntimeError::custom(format!("Malformed zoneinfo data for '{}'", name));
+
+        let header = |at: usize| -> Option<(u8, [usize; 6])> {
+            if bytes.get(at..at + 4)? != b"TZif" {
+                return None;
+            }
+            let version = *bytes.get(at + 4)?;
+            let mut counts = [0usize; 6];
+            for (i, c) in counts.iter_mut().enumerate() {
+                let off = at + 20 + i * 4;
+                *c = u32::from_be_bytes(bytes.get(off..off + 4)?.try_into().ok()?) as usize;
+            }
+            Some((version, counts))
+        };
+
+        let (version, counts) = header(0).ok_or_else(bad)?;
+        let [isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt] = counts;
+        let v1_len = timecnt * 5 + typecnt * 6 + charcnt + leapcnt * 8 + isstdcnt + isutcnt;
+
+        // Version 2+ files repeat the data with 64-bit transition times
+        let (start, counts, time_size) = if version >= b'2' {
+            let at = 44 + v1_len;
+            let (_, counts) = header(at).ok_or_else(bad)?;
+            (at + 44, counts, 8)
+        } else {
+            (44, counts, 4)
+        };
+        let [isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt] = counts;
+
+        let mut pos = start;
+        let mut take = |n: usize| -> RuntimeResult<&[u8]> {
+            let slice = bytes.get(pos..pos + n).ok_or_else(bad)?;
+            pos += n;
+            Ok(slice)
+        };
+
+        let mut transitions = Vec::with_capacity(timecnt);
+        for chunk in take(timecnt * time_size)?.chunks(time_size) {
+            transitions.push(if time_size == 8 {
+                i64::from_be_bytes(chunk.try_into().map_err(|_| bad())?)
+            } else {
+                i32::from_be_bytes(chunk.try_into().map_err(|_| bad())?) as i64
+            });
+        }
+
+        let indices: Vec<usize> = take(timecnt)?.iter().map(|b| *b as usize).collect();
+
+        let mut raw_types = Vec::with_capacity(typecnt);
+        for chunk in take(typecnt * 6)?.chunks(6) {
+            let offset = i32::from_be_bytes(chunk[0..4].try_into().map_err(|_| bad())?) as i64;
+            raw_types.push((offset, chunk[4] != 0, chunk[5] as usize));
+        }
+
+        let chars = take(charcnt)?;
+        let mut types = Vec::with_capacity(typecnt);
+        for (offset, dst, abbr_idx) in raw_types {
+            let tail = chars.get(abbr_idx..).ok_or_else(bad)?;
+            let end = tail.iter().position(|b| *b == 0).unwrap_or(tail.len());
+            types.push(LocalType {
+                offset,
+                dst,
+                abbr: String::from_utf8_lossy(&tail[..end]).into_owned(),
+            });
+        }
+
+        if types.is_empty() || indices.iter().any(|i| *i >= types.len()) {
+            return Err(bad());
+        }
+
+        take(leapcnt * (time_size + 4) + isstdcnt + isutcnt)?;
+
+        let footer = if version >= b'2' {
+            let rest = bytes.get(pos..).unwrap_or(&[]);
+            let text = String::from_utf8_lossy(rest);
+            let line = text.trim_matches('\n').lines().next().unwrap_or("").to_string();
+            if line.is_empty() {
+                None
+            } else {
+                Some(parse_posix_tz(&line).ok_or_else(bad)?)
+            }
+        } else {
+            None
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            transitions,
+            indices,
+            types,
+            footer,
+        })
+    }
+
+    // Returns (offset, is_dst, abbreviation) in effect at the UTC instant `t`.
+    pub fn lookup(&self, t: i64) -> (i64, bool, String) {
+        let found = |lt: &LocalType| (lt.offset, lt.dst, lt.abbr.clone());
+
+        // Past the last transition (or with none at all) the footer rule applies
+        let last = self.transitions.last().copied().unwrap_or(i64::MIN);
+        if t >= last && let Some(rule) = &self.footer {
+            return rule.lookup(t);
+        }
+
+        if self.transitions.is_empty() || t < self.transitions[0] {
+            let first_std = self.types.iter().find(|lt| !lt.dst).unwrap_or(&self.types[0]);
+            return found(first_std);
+        }
+
+        let i = match self.transitions.binary_search(&t) {
+            Ok(i) => i,
+            Err(i) => i - 1,
+        };
+        found(&self.types[self.indices[i]])
+    }
+}
+
+impl PosixRule {
+    fn lookup(&self, t: i64) -> (i64, bool, String) {
+        let (dst_abbr, dst_offset, start, start_time, end, end_time) = match &self.dst {
+            None => return (self.std_offset, false, self.std_abbr.clone()),
+            Some(d) => d.clone(),
+        };
+
+        let (year, _, _) = civil_from_days((t + self.std_offset).div_euclid(86_400));
+
+        // Transition instants in UTC: DST starts in standard time and ends in DST
+        let starts = rule_day(start, year) * 86_400 + start_time - self.std_offset;
+        let ends = rule_day(end, year) * 86_400 + end_time - dst_offset;
+
+        let in_dst = if starts < ends {
+            t >= starts && t < ends
+        } else {
+            !(t >= ends && t < starts)
+        };
+
+        if in_dst {
+            (dst_offset, true, dst_abbr)
+        } else {
+            (self.std_offset, false, self.std_abbr.clone())
+        }
+    }
+}
+
+// Day (relative to the epoch) on which a POSIX rule date falls in `year`.
+fn rule_day(date: RuleDate, year: i64) -> i64 {
+    let jan1 = days_from_civil(year, 1, 1);
+    match date {
+        RuleDate::Julian1(n) => {
+            let n = n as i64;
+            let leap_shift = if is_leap_year(year) && n >= 60 { 1 } else { 0 };
+            jan1 + n - 1 + leap_shift
+        }
+        RuleDate::Julian0(n) => jan1 + n as i64,
+        RuleDate::MonthWeekDay(m, w, d) => {
+            let first = days_from_civil(year, m, 1);
+            let first_wd = (first + 4).rem_euclid(7);
+            let mut day = first + (d as i64 - first_wd).rem_euclid(7) + (w as i64 - 1) * 7;
+            let month_end = first + days_in_month(year, m) as i64;
+            while day >= month_end {
+                day -= 7;
+            }
+            day
+        }
+    }
+}
+
+// Parses TZ strings such as "CET-1CEST,M3.5.0,M10.5.0/3" (see tzset(3)).
+fn parse_posix_tz(s: &str) -> Option<PosixRule> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut i = 0;
+
+    fn abbr(chars: &[char], i: &mut usize) -> Option<String> {
+        let mut out = String::new();
+        if chars.get(*i) == Some(&'<') {
+            *i += 1;
+            while *chars.get(*i)? != '>' {
+                out.push(chars[*i]);
+                *i += 1;
+            }
+            *i += 1;
+        } else {
+            while let Some(c) = chars.get(*i) {
+                if !c.is_ascii_alphabetic() {
+                    break;
+                }
+                out.push(*c);
+                *i += 1;
+            }
+        }
+        if out.is_empty() { None } else { Some(out) }
+    }
+
+    // [+-]hh[:mm[:ss]] in seconds
+    fn clock(chars: &[char], i: &mut usize) -> Option<i64> {
+        let mut sign = 1;
+        match chars.get(*i) {
+            Some('-') => {
+                sign = -1;
+                *i += 1;
+            }
+            Some('+') => *i += 1,
+            _ => {}
+        }
+        // Hours go up to 167 (RFC 8536), minutes and seconds to 59
+        let mut total = 0;
+        for (unit, max) in [(3600, 167), (60, 59), (1, 59)] {
+            let n = number(chars, i)?;
+            if n > max {
+                return None;
+            }
+            total += n as i64 * unit;
+            if unit == 1 || chars.get(*i) != Some(&':') {
+                break;
+            }
+            *i += 1;
+        }
+        Some(sign * total)
+    }
+
+    fn number(chars: &[char], i: &mut usize) -> Option<u32> {
+        let mut n: Option<u32> = None;
+        while let Some(d) = chars.get(*i).and_then(|c| c.to_digit(10)) {
+            n = Some(n.unwrap_or(0).checked_mul(10)?.checked_add(d)?);
+            *i += 1;
+        }
+        n
+    }
+
+    fn date(chars: &[char], i: &mut usize) -> Option<(RuleDate, i64)> {
+        let d = match chars.get(*i)? {
+            'J' => {
+                *i += 1;
+                let n = number(chars, i)?;
+                if !(1..=365).contains(&n) {
+                    return None;
+                }
+                RuleDate::Julian1(n)
+            }
+            'M' => {
+                *i += 1;
+                let m = number(chars, i)?;
+                if chars.get(*i) != Some(&'.') {
+                    return None;
+                }
+                *i += 1;
+                let w = number(chars, i)?;
+                if chars.get(*i) != Some(&'.') {
+                    return None;
+                }
+                *i += 1;
+                let d = number(chars, i)?;
+                if !(1..=12).contains(&m) || !(1..=5).contains(&w) || d > 6 {
+                    return None;
+                }
+                RuleDate::MonthWeekDay(m, w, d)
+            }
+            _ => {
+                let n = number(chars, i)?;
+                if n > 365 {
+                    return None;
+                }
+                RuleDate::Julian0(n)
+            }
+        };
+        let time = if chars.get(*i) == Some(&'/') {
+            *i += 1;
+            clock(chars, i)?
+        } else {
+            7200
+        };
+        Some((d, time))
+    }
+
+    let std_abbr = abbr(&chars, &mut i)?;
+    // POSIX offsets count hours *west* of Greenwich
+    let std_offset = -clock(&chars, &mut i)?;
+
+    if i >= chars.len() {
+        return Some(PosixRule {
+            std_abbr,
+            std_offset,
+            dst: None,
+        });
+    }
+
+    let dst_abbr = abbr(&chars, &mut i)?;
+    let dst_offset = if chars.get(i) != Some(&',') && i < chars.len() {
+        -clock(&chars, &mut i)?
+    } else {
+        std_offset + 3600
+    };
+
+    if chars.get(i) != Some(&',') {
+        // No rule given: fall back to the US rules, like glibc does
+        return Some(PosixRule {
+            std_abbr,
+            std_offset,
+            dst: Some((
+                dst_abbr,
+                dst_offset,
+                RuleDate::MonthWeekDay(3, 2, 0),
+                7200,
+                RuleDate::MonthWeekDay(11, 1, 0),
+                7200,
+            )),
+        });
+    }
+    i += 1;
+    let (start, start_time) = date(&chars, &mut i)?;
+    if chars.get(i) != Some(&',') {
+        return None;
+    }
+    i += 1;
+    let (end, end_time) = date(&chars, &mut i)?;
+
+    Some(PosixRule {
+        std_abbr,
+        std_offset,
+        dst: Some((dst_abbr, dst_offset, start, start_time, end, end_time)),
+    })
+}
+
+// Optional `ts=` argument (Unix seconds); defaults to the current time.
+pub fn timestamp_arg(ts: Option<Value>, fname: &str) -> RuntimeResult<(i64, u32)> {
+    match ts {
+        None | Some(Value::Nil) => Ok(realtime_now()),
+        Some(Value::Int(i)) => i64::try_from(i)
+            .map(|s| (s, 0))
+            .map_err(|_| RuntimeError::custom(format!("{} timestamp out of range: {}", fname, i))),
+        Some(Value::Float(f)) => {
+            let secs = f.floor();
+            Ok((secs as i64, ((f - secs) * 1e9) as u32))
+        }
+        Some(other) => Err(RuntimeError::custom(format!(
+            "{} expects Int for 'ts', got {}",
+            fname,
+            other.type_name()
+        ))),
+    }
+}
@@ -0,0 +1,30 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use std::collections::HashMap;
+
+impl Interpreter {
+    // history(name="x") -> array of dictionaries {value, locked},
+    // one per _kill_, most recent first: index n is what `_revive_ x @ n` restores
+    pub(crate) fn var_history(&self, name: Value) -> RuntimeResult<Value> {
+        let Value::String(name) = name else {
+            return Err(RuntimeError::custom(format!(
+                "history expects a variable name as a String, got {}",
+                name.type_name()
+            )));
+        };
+
+        let generations = self
+            .environment
+            .history(&name)?
+            .into_iter()
+            .map(|generation| {
+                Value::dict(HashMap::from([
+                    ("value".to_string(), generation.value),
+                    ("locked".to_string(), Value::Bool(generation.smart_lock)),
+                ]))
+            })
+            .collect();
+        Ok(Value::array(generations))
+    }
+}
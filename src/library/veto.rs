@@ -0,0 +1,17 @@
+use crate::interpreter::interpreter::Interpreter;
+use crate::values::values::Value;
+use crate::interpreter::error::{RuntimeError, RuntimeErrorKind, RuntimeResult};
+
+impl Interpreter {
+    // veto(reason="...") -> never returns; refuses the change the calling
+    // watcher was run for
+    pub(crate) fn veto(reason: Value) -> RuntimeResult<Value> {
+        let Value::String(reason) = reason else {
+            return Err(RuntimeError::custom(format!(
+                "veto expects a reason as a String, got {}",
+                reason.type_name()
+            )));
+        };
+        Err(RuntimeError::new(RuntimeErrorKind::Vetoed(reason)))
+    }
+}
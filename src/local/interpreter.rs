@@ -2,6 +2,7 @@ use super::parser::Command;
 use crate::interpreter::Interpreter;
 use crate::lexer::Lexer;
 use crate::parser::Parser;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::io::{self, Write};
@@ -62,7 +63,7 @@ impl CommandInterpreter {
 
             Command::ExecCode { code } => {
                 if !code.is_empty() {
-                    self.run_ex_source(&code);
+                    self.run_ex_source(&code, HashMap::new());
                 }
                 ExecutionResult::Continue
             }
@@ -81,7 +82,7 @@ impl CommandInterpreter {
         }
     }
 
-    fn run_ex_source(&mut self, source: &str) {
+    fn run_ex_source(&mut self, source: &str, defines: HashMap<String, String>) {
         // 1) Lex
         let tokens = match Lexer::new(source.to_string()).scan_tokens() {
             Ok(t) => t,
@@ -92,7 +93,7 @@ impl CommandInterpreter {
         };
 
         // 2) Parse
-        let mut parser = Parser::new(tokens);
+        let mut parser = Parser::new(tokens).with_defines(defines);
         let statements = match parser.parse() {
             Ok(stmts) => stmts,
             Err(errors) => {
@@ -117,7 +118,10 @@ impl CommandInterpreter {
 
         // Check if it's a .ex file
         if path_obj.extension().and_then(|s| s.to_str()) == Some("ex") {
-            self.run_ex_file(path);
+            match parse_defines(args) {
+                Ok(defines) => self.run_ex_file(path, defines),
+                Err(e) => eprintln!("{}", e),
+            }
         } else {
             // Try to execute as a local binary/script
             match process::Command::new(path).args(args).status() {
@@ -131,7 +135,7 @@ impl CommandInterpreter {
         }
     }
 
-    fn run_ex_file(&mut self, path_str: &str) {
+    fn run_ex_file(&mut self, path_str: &str, defines: HashMap<String, String>) {
         let path = Path::new(path_str);
 
         // Check exists
@@ -158,7 +162,7 @@ impl CommandInterpreter {
             }
         };
 
-        self.run_ex_source(&source);
+        self.run_ex_source(&source, defines);
     }
 
     fn execute_system_command(&self, command: &str, args: &[String]) {
@@ -174,3 +178,26 @@ impl CommandInterpreter {
         }
     }
 }
+
+// `-D NAME=value`, `-D NAME` (value "1") or the joined `-DNAME=value` after a
+// script path; the names can be tested with `_ifdef_` while parsing
+fn parse_defines(args: &[String]) -> Result<HashMap<String, String>, String> {
+    let mut defines = HashMap::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let definition = match arg.strip_prefix("-D") {
+            Some("") => args.next().ok_or("Expected NAME or NAME=value after -D")?.as_str(),
+            Some(joined) => joined,
+            None => return Err(format!("Unknown option for an .ex script: {}", arg)),
+        };
+
+        let (name, value) = definition.split_once('=').unwrap_or((definition, "1"));
+        if name.is_empty() {
+            return Err(format!("Missing name in -D {}", definition));
+        }
+        defines.insert(name.to_string(), value.to_string());
+    }
+
+    Ok(defines)
+}
@@ -1,5 +1,3 @@
-use crate::lexer::TokenKind;
-
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenType {
     // Built-in commands
@@ -1,6 +1,7 @@
 use super::lexer::{Token, TokenType};
 
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 pub enum Command {
     Chd { directory: String },
     Cud,
@@ -1,3 +1,4 @@
+
 use libc::geteuid;
 use std::env;
 use std::io::{self, Write};
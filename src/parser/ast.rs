@@ -1,4 +1,23 @@
-use crate::lexer::Token;
+use crate::lexer::{Token, TokenKind};
+
+// (name, callable, visit, params, internal_names, body, name token)
+pub type LabelItem = (String, bool, Vec<String>, Vec<String>, Vec<Pattern>, Vec<Stmt>, Token);
+
+// Left-hand side of a destructuring binding. A bare name binds the whole
+// value; `[a, b, ...rest]` takes elements in order and `{host, port: p}`
+// takes entries by key. `...rest` collects whatever the pattern left over.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Name(String),
+    Array {
+        items: Vec<Pattern>,
+        rest: Option<String>,
+    },
+    Dict {
+        entries: Vec<(String, Pattern)>,
+        rest: Option<String>,
+    },
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Stmt {
@@ -14,12 +33,32 @@ pub enum Stmt {
     },
     SmartRevive {
         variable: String,
+        // `_revive_ x @ n`: how many kills back to go, 0 when omitted
+        generation: Option<Expr>,
     },
     SmartConst {
         variable: String,
     },
+    // `_watch_ x -> label`
+    SmartWatch {
+        variable: String,
+        label: String,
+    },
+    // `_unwatch_ x` drops every watcher, `_unwatch_ x -> label` just one
+    SmartUnwatch {
+        variable: String,
+        label: Option<String>,
+    },
+    // `_scoped_ x`: kill x when the current scope closes
+    SmartScoped {
+        variable: String,
+    },
+    // `defer { ... }`: run the block when the current scope closes
+    Defer {
+        body: Vec<Stmt>,
+    },
     Label {
-        _label_: Vec<(String, bool, Vec<String>, Vec<String>, Vec<String>, Vec<Stmt>)>,
+        _label_: Vec<LabelItem>,
     },
     Visible {
         _name_: String,
@@ -31,8 +70,11 @@ pub enum Stmt {
         elif_branches: Vec<(Expr, Vec<Stmt>)>,
         else_branch: Option<Vec<Stmt>>,
     },
+    // `jump name` goes to the control flow label `name` defined in this
+    // block or an enclosing one; `token` places errors about it
     Jump {
         jump: String,
+        token: Token,
     },
     Pass,
     While {
@@ -43,11 +85,66 @@ pub enum Stmt {
         body: Vec<Stmt>,
         condition: Expr,
     },
+    // for x _in_ xs / for k, v _in_ d: with a second name the first one
+    // gets the key (dictionaries) or the index (everything else). Either
+    // position may be a pattern: for [x, y] _in_ points
     For {
-        iterator: String,
+        iterator: Pattern,
+        value: Option<Pattern>,
         iterable: Expr,
         body: Vec<Stmt>,
     },
+    // yield expr: makes the enclosing callable label a generator
+    Yield {
+        value: Expr,
+    },
+    // record Name { field, field = default, ... }
+    Record {
+        name: String,
+        fields: Vec<(String, Option<Expr>)>,
+    },
+}
+
+// Refutable pattern of a `match` arm
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchPattern {
+    // _
+    Wildcard,
+    // n, or n @ pattern to bind what the inner pattern matched
+    Bind(String, Option<Box<MatchPattern>>),
+    // 1, -2.5, "prod", 'c', true, nil: compared with ==
+    Literal(Expr),
+    // ::[1..9]: an integer inside the range
+    Range(Expr),
+    // Int, String, Array, or a record name: checked against type_name
+    Type(String),
+    Array {
+        items: Vec<MatchPattern>,
+        rest: Option<String>,
+    },
+    Dict {
+        entries: Vec<(String, MatchPattern)>,
+        rest: Option<String>,
+    },
+    // p | q
+    Alt(Vec<MatchPattern>),
+}
+
+// pattern if guard -> body
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub pattern: MatchPattern,
+    pub guard: Option<Expr>,
+    pub body: Expr,
+}
+
+// One step of an access chain: x[expr] or x.name
+#[derive(Debug, Clone, PartialEq)]
+pub enum Accessor {
+    Index(Expr),
+    Field(String),
+    // x?[expr] / x?.name: nil instead of an error when nothing is there
+    Optional(Box<Accessor>),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -61,9 +158,30 @@ pub enum Expr {
         elements: Vec<Expr>
     },
 
+    Set {
+        elements: Vec<Expr>
+    },
+
     Access {
         ds:String,
-        member:Vec<Expr>
+        member:Vec<Accessor>
+    },
+
+    // x[i].field = value
+    AssignAccess {
+        ds: String,
+        member: Vec<Accessor>,
+        val: Box<Expr>,
+    },
+
+    // x += v, xs[i] -= v, ++x, x-- ...: `operator` is the arithmetic
+    // applied (++/-- use Plus/Minus with 1). `postfix` yields the old value.
+    CompoundAssign {
+        ds: String,
+        member: Vec<Accessor>,
+        operator: TokenKind,
+        val: Box<Expr>,
+        postfix: bool,
     },
 
     Dictionary(Vec<(Expr, Expr)>),
@@ -77,8 +195,11 @@ pub enum Expr {
         operator: Token,
         right: Box<Expr>,
     },
+    // #NAME(args): the body runs in its own scope with each (renamed)
+    // parameter bound to its argument; the last expression is the value
     MacroCall {
-        var: Vec<Expr>,
+        params: Vec<String>,
+        args: Vec<Expr>,
         body: Vec<Stmt>,
     },
     _Literal_(Literal),
@@ -91,12 +212,34 @@ pub enum Expr {
         function: String,
         args: Vec<(String, Expr)>,
     },
+    // recv.method(arg, name=arg); positional args have no name
+    MethodCall {
+        receiver: Box<Expr>,
+        method: String,
+        args: Vec<(Option<String>, Expr)>,
+    },
+    // expr? leaves the current label when expr is nil
+    Propagate(Box<Expr>),
     AllocateVariable {
         name: String,
         val: Box<Expr>,
     },
-    Iterable {
-        value: Vec<i128>,
+    // [a, ...rest] = xs / {host, port: p} = cfg
+    Destructure {
+        pattern: Pattern,
+        val: Box<Expr>,
+    },
+    // match subject { pattern -> value, pattern if guard -> value, ... }
+    Match {
+        subject: Box<Expr>,
+        arms: Vec<MatchArm>,
+    },
+    // ::[start..end], ::[start..<end], ::[start..end:step]
+    Range {
+        start: Box<Expr>,
+        end: Box<Expr>,
+        step: Option<Box<Expr>>,
+        inclusive: bool,
     },
 }
 
@@ -0,0 +1,52 @@
+use crate::parser::ast::{LabelItem, Stmt};
+use crate::parser::visit::{Body, VisitorMut};
+use std::collections::HashMap;
+
+// Hygienic renaming for macro bodies. Every use of a macro parameter is
+// rewritten to `MACRO#param`, a name no source program can spell, so a
+// parameter can never shadow or overwrite a variable of the caller (or of
+// a label the body calls). Free names are left alone: a macro still reads
+// and writes the caller's variables on purpose. A name the caller has not
+// bound is created in the expansion's own scope, so the body's temporaries
+// go away with it.
+//
+// Nested labels are not renamed; their bodies run later, in their own
+// scope, after the expansion is gone.
+pub(crate) struct Renamer {
+    names: HashMap<String, String>,
+}
+
+impl Renamer {
+    pub(crate) fn new(macro_name: &str, params: &[String]) -> Self {
+        let names = params
+            .iter()
+            .map(|p| (p.clone(), format!("{}#{}", macro_name, p)))
+            .collect();
+        Self { names }
+    }
+
+    pub(crate) fn renamed(&self, name: &str) -> String {
+        self.names.get(name).cloned().unwrap_or_else(|| name.to_string())
+    }
+}
+
+impl<'a> VisitorMut<'a> for Renamer {
+    fn read(&mut self, name: &'a mut String) {
+        if let Some(new) = self.names.get(name.as_str()) {
+            *name = new.clone();
+        }
+    }
+
+    fn bind(&mut self, name: &'a mut String) {
+        self.read(name);
+    }
+
+    fn label(&mut self, _item: &'a mut LabelItem) {}
+
+    // A nested expansion already renamed its own body
+    fn block(&mut self, body: &'a mut [Stmt], kind: Body) {
+        if kind != Body::Macro {
+            self.stmts(body);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::parser::ast::Stmt;
+use crate::parser::parser::ParseError;
+use crate::parser::visit::{Body, Visitor};
+use std::collections::HashMap;
+
+// Jumps are resolved before the script runs. `jump name` may go to a
+// control flow label defined in its own block or in any enclosing one,
+// forwards or backwards, and lands there: the label body runs, then the
+// statements after the label. Labels inside a loop body, an if branch or
+// another label are out of reach from outside that block. A callable
+// label's body and a `defer` block can never be left by jumping.
+
+#[derive(Clone, Copy, PartialEq)]
+enum Block {
+    Plain,
+    Loop,
+    // Starts afresh: jumps cannot leave it
+    Unit(&'static str),
+}
+
+// Control flow labels defined directly in `stmt`, with their bodies
+pub(crate) fn flow_labels(stmt: &Stmt) -> impl Iterator<Item = (&str, &[Stmt])> {
+    let items = match stmt {
+        Stmt::Label { _label_ } => _label_.as_slice(),
+        _ => &[],
+    };
+    items
+        .iter()
+        .filter(|item| !item.1)
+        .map(|item| (item.0.as_str(), item.5.as_slice()))
+}
+
+struct Frame<'a> {
+    labels: Vec<&'a str>,
+    kind: Block,
+}
+
+struct Checker<'a> {
+    frames: Vec<Frame<'a>>,
+    everywhere: HashMap<&'a str, bool>,
+    errors: Vec<ParseError>,
+}
+
+// Every control flow label in the script: is it inside a loop body?
+#[derive(Default)]
+struct Collector<'a> {
+    loops: usize,
+    everywhere: HashMap<&'a str, bool>,
+}
+
+impl<'a> Visitor<'a> for Collector<'a> {
+    fn block(&mut self, body: &'a [Stmt], kind: Body) {
+        let in_loop = self.loops > 0;
+        for (name, _) in body.iter().flat_map(flow_labels) {
+            let seen = self.everywhere.entry(name).or_insert(in_loop);
+            *seen = *seen && in_loop;
+        }
+        let depth = usize::from(kind == Body::Loop);
+        self.loops += depth;
+        self.stmts(body);
+        self.loops -= depth;
+    }
+}
+
+pub(crate) fn check_jumps(statements: &[Stmt]) -> Vec<ParseError> {
+    let mut collector = Collector::default();
+    collector.block(statements, Body::Script);
+    let mut checker = Checker {
+        frames: Vec::new(),
+        everywhere: collector.everywhere,
+        errors: Vec::new(),
+    };
+    checker.block(statements, Body::Script);
+    checker.errors
+}
+
+impl<'a> Visitor<'a> for Checker<'a> {
+    fn block(&mut self, body: &'a [Stmt], kind: Body) {
+        let labels = body.iter().flat_map(flow_labels).map(|(name, _)| name).collect();
+        let kind = match kind {
+            Body::Script => Block::Unit("the script"),
+            Body::Label => Block::Unit("a label call"),
+            Body::Defer => Block::Unit("a defer block"),
+            Body::Loop => Block::Loop,
+            // A macro body is spliced in where it is used
+            Body::Branch | Body::FlowLabel | Body::Macro => Block::Plain,
+        };
+        self.frames.push(Frame { labels, kind });
+        self.stmts(body);
+        self.frames.pop();
+    }
+
+    fn stmt(&mut self, stmt: &'a Stmt) {
+        if let Stmt::Jump { jump, token } = stmt
+            && let Err(message) = self.resolve(jump)
+        {
+            self.errors.push(ParseError {
+                token: token.clone(),
+                message,
+            });
+        }
+        self.walk_stmt(stmt);
+    }
+}
+
+impl Checker<'_> {
+    fn resolve(&self, name: &str) -> Result<(), String> {
+        let mut left = None;
+        for frame in self.frames.iter().rev() {
+            if frame.labels.contains(&name) {
+                return match left {
+                    None => Ok(()),
+                    Some(unit) => Err(format!("Cannot jump out of {} to label '{}'", unit, name)),
+                };
+            }
+            if let Block::Unit(unit) = frame.kind {
+                left = left.or(Some(unit));
+            }
+        }
+
+        Err(match self.everywhere.get(name) {
+            Some(true) => format!("Cannot jump into a loop body: label '{}' is defined inside one", name),
+            Some(false) => format!("Label '{}' is not defined in this block or an enclosing one", name),
+            None => format!("Undefined jump target '{}'", name),
+        })
+    }
+}
@@ -1,7 +1,14 @@
 
 pub mod ast;
+#[allow(clippy::module_inception)]
 pub mod parser;
+pub mod hygiene;
+pub mod jumps;
+pub mod visit;
+pub mod visibility;
+pub mod token_macro;
 
 #[allow(unused)]
 pub use ast::*;
+#[allow(unused)]
 pub use parser::{ParseError, Parser};
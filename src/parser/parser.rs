@@ -1,8 +1,13 @@
 use std::collections::HashMap;
-use std::fmt::Arguments;
 
 use crate::lexer::{Token, TokenKind};
-use crate::parser::ast::{Expr, Literal, Stmt};
+use crate::parser::hygiene::Renamer;
+use crate::parser::jumps::check_jumps;
+use crate::parser::visibility::check_visibility;
+use crate::parser::visit::VisitorMut;
+use crate::parser::token_macro::{TokenMacro, MAX_EXPANSION_DEPTH};
+use crate::stdvars::stander_variables::compile_time_vars;
+use crate::parser::ast::{Accessor, Expr, LabelItem, Literal, MatchArm, MatchPattern, Pattern, Stmt};
 
 #[derive(Debug, Clone)]
 pub struct ParseError {
@@ -15,6 +20,9 @@ pub struct Parser {
     current: usize,
     errors: Vec<ParseError>,
     macro_map: HashMap<String, (Vec<String>, Vec<Stmt>)>,
+    // Names given with `-D NAME=value` on the command line
+    defines: HashMap<String, String>,
+    token_macros: HashMap<String, TokenMacro>,
 }
 
 impl Parser {
@@ -28,9 +36,16 @@ impl Parser {
             current: 0,
             errors: Vec::new(),
             macro_map: HashMap::new(),
+            defines: HashMap::new(),
+            token_macros: HashMap::new(),
         }
     }
 
+    pub fn with_defines(mut self, defines: HashMap<String, String>) -> Self {
+        self.defines = defines;
+        self
+    }
+
     // =========================================================
     // Entry point
     // =========================================================
@@ -45,6 +60,11 @@ impl Parser {
             }
         }
 
+        if self.errors.is_empty() {
+            self.errors = check_jumps(&statements);
+            self.errors.extend(check_visibility(&statements));
+        }
+
         if self.errors.is_empty() {
             Ok(statements)
         } else {
@@ -101,8 +121,14 @@ impl Parser {
                     .consume(TokenKind::Identifier, "Expected 'Identifier'")
                     .unwrap()
                     .lexeme;
+                let generation = if self.matches(&[TokenKind::At]) {
+                    Some(self.expression()?)
+                } else {
+                    None
+                };
                 Ok(Stmt::SmartRevive {
                     variable: identifier,
+                    generation,
                 })
             }
 
@@ -116,6 +142,31 @@ impl Parser {
                     variable: identifier,
                 })
             }
+
+            TokenKind::VWatch => {
+                self.advance();
+                let variable = self.consume(TokenKind::Identifier, "Expected 'Identifier'")?.lexeme;
+                self.consume(TokenKind::Arrow, "Expected '->' before the watcher label")?;
+                let label = self.consume(TokenKind::Identifier, "Expected a label name after '->'")?.lexeme;
+                Ok(Stmt::SmartWatch { variable, label })
+            }
+
+            TokenKind::VScoped => {
+                self.advance();
+                let variable = self.consume(TokenKind::Identifier, "Expected 'Identifier'")?.lexeme;
+                Ok(Stmt::SmartScoped { variable })
+            }
+
+            TokenKind::VUnwatch => {
+                self.advance();
+                let variable = self.consume(TokenKind::Identifier, "Expected 'Identifier'")?.lexeme;
+                let label = if self.matches(&[TokenKind::Arrow]) {
+                    Some(self.consume(TokenKind::Identifier, "Expected a label name after '->'")?.lexeme)
+                } else {
+                    None
+                };
+                Ok(Stmt::SmartUnwatch { variable, label })
+            }
             TokenKind::Label => {
                 self.advance();
                 self.consume_label()
@@ -126,10 +177,9 @@ impl Parser {
             }
             TokenKind::Jump => {
                 self.advance();
-                let _where_: String = self
-                    .consume_identifier("Expected 'identifier' after Jump")
-                    .unwrap();
-                Ok(Stmt::Jump { jump: _where_ })
+                let token = self.peek().clone();
+                let _where_: String = self.consume_identifier("Expected 'identifier' after Jump")?;
+                Ok(Stmt::Jump { jump: _where_, token })
             }
             TokenKind::Pass => {
                 self.advance();
@@ -144,14 +194,31 @@ impl Parser {
 
             TokenKind::Visible => self.def_visible_block(),
 
+            TokenKind::Record => self.def_record(),
+
+            TokenKind::Defer => self.defer_block(),
+
+            TokenKind::Yield => {
+                self.advance();
+                let value = self.expression()?;
+                Ok(Stmt::Yield { value })
+            }
+
             TokenKind::DEFINE => {
                 self.advance();
                 self.define_macro()
             }
-            TokenKind::IFNDEF => {
-                self.advance();
-                self.ifndef_macro()
+            TokenKind::MACRO => self.define_token_macro(),
+            // A token macro used as a statement may expand to several
+            TokenKind::Hash if self.at_token_macro_call() => {
+                self.expand_token_macro(false)?;
+                Ok(Stmt::Pass)
             }
+            TokenKind::IFDEF | TokenKind::IFNDEF => self.conditional(),
+            TokenKind::ELIF | TokenKind::ELSE | TokenKind::ENDIF => Err(self.error(&format!(
+                "'{}' without a matching '_ifdef_' or '_ifndef_'",
+                self.peek().lexeme
+            ))),
             TokenKind::UNDEF => {
                 self.advance();
                 self.undef_macro()
@@ -163,9 +230,10 @@ impl Parser {
 
     fn undef_macro(&mut self) -> Result<Stmt, ParseError> {
         let macro_name = self.consume_identifier("Expected Macro name after _undef_ keyword")?;
-        if self.macro_map.contains_key(&macro_name) {
-            self.macro_map.remove(&macro_name);
-        } else {
+        if self.macro_map.remove(&macro_name).is_none()
+            && self.token_macros.remove(&macro_name).is_none()
+            && self.defines.remove(&macro_name).is_none()
+        {
             return Err(self.error(
                 format!("_undef_ {}, but macro not define in this scope", macro_name).as_str(),
             ));
@@ -173,55 +241,177 @@ impl Parser {
         Ok(Stmt::Pass)
     }
 
-    fn ifndef_macro(&mut self) -> Result<Stmt, ParseError> {
-        let macro_name = self.consume_identifier("Expected Macro Name")?;
-
-        if !self.macro_map.contains_key(&macro_name) {
-            // Macro NOT defined - define macros inside the block
-            while !self.check(TokenKind::ENDIF) && !self.is_at_end() {
-                self.consume(TokenKind::DEFINE, "Expected '_macro_' keyword")?;
-                self.define_macro()?;
+    // _ifdef_ COND ... [_elif_ COND ...]* [_else_ ...] _endif_
+    //
+    // Resolved while parsing: the tokens of the chosen branch replace the
+    // whole block and are parsed as if written there, so a block can wrap
+    // any statements and sees the macros defined before it.
+    fn conditional(&mut self) -> Result<Stmt, ParseError> {
+        let start = self.current;
+        let negate = self.advance().kind == TokenKind::IFNDEF;
+        let mut holds = self.condition()? != negate;
+        let mut branch_start = self.current;
+        let mut chosen: Option<(usize, usize)> = None;
+        let mut seen_else = false;
+        let mut depth = 0;
+
+        loop {
+            if self.is_at_end() {
+                return Err(self.error("Expected '_endif_' to close conditional block"));
             }
-            self.consume(TokenKind::ENDIF, "Expected 'ENDIF' to close 'IFNDEF' block")?;
-            Ok(Stmt::Pass)
-        } else {
-            // Macro IS defined - skip everything until ENDIF
-            while !self.check(TokenKind::ENDIF) && !self.is_at_end() {
-                self.skip_macro()?; // Skip each macro definition
+
+            match self.peek().kind {
+                TokenKind::IFDEF | TokenKind::IFNDEF => depth += 1,
+                TokenKind::ENDIF if depth > 0 => depth -= 1,
+                TokenKind::ENDIF => {
+                    if holds && chosen.is_none() {
+                        chosen = Some((branch_start, self.current));
+                    }
+                    self.advance();
+                    break;
+                }
+                kind @ (TokenKind::ELIF | TokenKind::ELSE) if depth == 0 => {
+                    if seen_else {
+                        return Err(self.error("No '_elif_' or '_else_' may follow '_else_'"));
+                    }
+                    if holds && chosen.is_none() {
+                        chosen = Some((branch_start, self.current));
+                    }
+                    self.advance();
+                    seen_else = kind == TokenKind::ELSE;
+                    holds = seen_else || self.condition()?;
+                    branch_start = self.current;
+                    continue;
+                }
+                _ => {}
             }
-            self.consume(TokenKind::ENDIF, "Expected 'ENDIF' to close 'IFNDEF' block")?;
+            self.advance();
+        }
 
-            Ok(Stmt::Pass) // Added return value
+        let body = chosen.map(|(from, to)| self.tokens[from..to].to_vec()).unwrap_or_default();
+        self.tokens.splice(start..self.current, body);
+        self.current = start;
+        Ok(Stmt::Pass)
+    }
+
+    // NAME holds when it is defined; NAME == value / NAME != value compare
+    // its value as text
+    fn condition(&mut self) -> Result<bool, ParseError> {
+        let name = self.consume_identifier("Expected a name to test")?;
+        let value = self.symbol_value(&name);
+
+        if !self.matches(&[TokenKind::EqualEqual, TokenKind::BangEqual]) {
+            return Ok(value.is_some());
         }
+        let equal = self.previous().kind == TokenKind::EqualEqual;
+
+        let token = self.advance();
+        let expected = match (token.kind, token.literal) {
+            (TokenKind::String, Some(crate::lexer::Literal::String(s))) => s,
+            (TokenKind::Number | TokenKind::Identifier | TokenKind::True | TokenKind::False, _) => token.lexeme,
+            _ => return Err(self.error("Expected a value to compare against")),
+        };
+
+        // An undefined name equals nothing
+        Ok((value.as_deref() == Some(expected.as_str())) == equal)
     }
 
-    fn skip_macro(&mut self) -> Result<Stmt, ParseError> {
-        self.consume_identifier("Expected Macro Name")?;
+    // -D defines, then macros, then the compile-time std vars
+    fn symbol_value(&self, name: &str) -> Option<String> {
+        if let Some(value) = self.defines.get(name) {
+            return Some(value.clone());
+        }
+        if self.macro_map.contains_key(name) || self.token_macros.contains_key(name) {
+            return Some(String::new());
+        }
+        compile_time_vars()
+            .into_iter()
+            .find(|(var, _)| *var == name)
+            .map(|(_, value)| value.to_string())
+    }
 
-        self.consume(TokenKind::LeftParen, "Expected '(' for macro parameters")?;
-        while !self.check(TokenKind::RightParen) {
-            self.consume_identifier("Expected 'identifier' as macro parameters")?;
+    // _macro_ NAME { (matcher) -> [ transcriber ] ... }, see token_macro.rs
+    fn define_token_macro(&mut self) -> Result<Stmt, ParseError> {
+        let line = self.advance().line;
+        let name = self.consume_identifier("Expected macro name after _macro_")?;
+        let mut token_macro = TokenMacro::new(name.clone(), line);
 
-            // Optional: handle comma separation
-            if self.check(TokenKind::Comma) {
-                self.advance();
+        self.consume(TokenKind::LeftBrace, "Expected '{' to open macro rules")?;
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            let matcher = self.token_group(TokenKind::LeftParen, "Expected '(' to open a macro matcher")?;
+            self.consume(TokenKind::Arrow, "Expected '->' after macro matcher")?;
+            let transcriber = self.token_group(TokenKind::LeftBracket, "Expected '[' to open a macro transcriber")?;
+
+            if let Err(message) = token_macro.add_rule(&matcher, &transcriber) {
+                return Err(self.error(&format!("In macro {} defined at line {}: {}", name, line, message)));
             }
         }
-        self.consume(
-            TokenKind::RightParen,
-            "Expected ')' to enclose macro parameters",
-        )?;
-        self.consume(TokenKind::LeftBracket, "Expected '[' to enclose macro body")?;
+        self.consume(TokenKind::RightBrace, "Expected '}' to close macro rules")?;
 
-        while !self.check(TokenKind::RightBracket) {
-            self.statement()?;
+        self.token_macros.insert(name, token_macro);
+        Ok(Stmt::Pass)
+    }
+
+    // The tokens inside a bracketed group, consuming the brackets
+    fn token_group(&mut self, open: TokenKind, message: &str) -> Result<Vec<Token>, ParseError> {
+        self.consume(open, message)?;
+        let start = self.current;
+        let mut depth = 0usize;
+
+        loop {
+            match self.peek().kind {
+                TokenKind::Eof => return Err(self.error("Unclosed bracket in macro")),
+                TokenKind::LeftParen | TokenKind::LeftBracket | TokenKind::LeftBrace => depth += 1,
+                TokenKind::RightParen | TokenKind::RightBracket | TokenKind::RightBrace if depth == 0 => break,
+                TokenKind::RightParen | TokenKind::RightBracket | TokenKind::RightBrace => depth -= 1,
+                _ => {}
+            }
+            self.advance();
         }
-        self.consume(
-            TokenKind::RightBracket,
-            "Expected ']' to enclose macro body",
-        )?; // Fixed: was RightParen
 
-        Ok(Stmt::Pass)
+        let tokens = self.tokens[start..self.current].to_vec();
+        self.advance(); // closing bracket
+        Ok(tokens)
+    }
+
+    fn at_token_macro_call(&self) -> bool {
+        self.tokens
+            .get(self.current + 1)
+            .is_some_and(|t| t.kind == TokenKind::Identifier && self.token_macros.contains_key(&t.lexeme))
+    }
+
+    // Replaces `#NAME(args)` by its expansion; parsing then continues over
+    // the new tokens
+    fn expand_token_macro(&mut self, wrap: bool) -> Result<(), ParseError> {
+        let start = self.current;
+        let depth = self.peek().expansion.as_ref().map_or(0, |e| e.depth) + 1;
+        self.advance(); // '#'
+        let name_token = self.advance();
+        let args = self.token_group(TokenKind::LeftParen, "Expected '(' after macro name")?;
+
+        let token_macro = &self.token_macros[&name_token.lexeme];
+        let expanded = if depth > MAX_EXPANSION_DEPTH {
+            Err(format!("expansion nested more than {} deep", MAX_EXPANSION_DEPTH))
+        } else {
+            token_macro.expand(&args, name_token.line, depth, wrap)
+        };
+
+        let tokens = match expanded {
+            Ok(tokens) => tokens,
+            Err(message) => {
+                let message = format!(
+                    "Macro {} (defined at line {}, called at line {}): {}",
+                    token_macro.name, token_macro.line, name_token.line, message
+                );
+                self.current = start;
+                let token = self.peek().clone();
+                return Err(self.report(token, message));
+            }
+        };
+
+        self.tokens.splice(start..self.current, tokens);
+        self.current = start;
+        Ok(())
     }
 
     fn define_macro(&mut self) -> Result<Stmt, ParseError> {
@@ -252,6 +442,11 @@ impl Parser {
             "Expected ']' to enclose macro body",
         )?; // Fixed: was RightParen
 
+        // Parameters get names the caller cannot clash with
+        let mut renamer = Renamer::new(&macro_name, &macro_param);
+        renamer.stmts(&mut macro_body);
+        let macro_param = macro_param.iter().map(|p| renamer.renamed(p)).collect();
+
         let _macro_: (Vec<String>, Vec<Stmt>) = (macro_param, macro_body);
         self.macro_map.insert(macro_name, _macro_);
 
@@ -289,6 +484,40 @@ impl Parser {
         })
     }
 
+    fn def_record(&mut self) -> Result<Stmt, ParseError> {
+        self.advance(); // consume 'record'
+        let name: String = self.consume_identifier("Expected record name")?;
+        self.consume(TokenKind::LeftBrace, "Expected '{' to open record fields")?;
+
+        let mut fields: Vec<(String, Option<Expr>)> = Vec::new();
+
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            let field = self.consume_identifier("Expected field name in record")?;
+            if fields.iter().any(|(f, _)| *f == field) {
+                return Err(self.error(&format!(
+                    "Duplicate field '{}' in record '{}'",
+                    field, name
+                )));
+            }
+
+            // Optional default value
+            let default = if self.matches(&[TokenKind::Equal]) {
+                Some(self.expression()?)
+            } else {
+                None
+            };
+            fields.push((field, default));
+
+            // Optional: handle comma separation
+            if self.check(TokenKind::Comma) {
+                self.advance();
+            }
+        }
+
+        self.consume(TokenKind::RightBrace, "Expected '}' to close record fields")?;
+        Ok(Stmt::Record { name, fields })
+    }
+
     fn while_loop(&mut self) -> Result<Stmt, ParseError> {
         self.advance(); // consume 'while'
 
@@ -304,6 +533,20 @@ impl Parser {
         Ok(Stmt::While { condition, body })
     }
 
+    fn defer_block(&mut self) -> Result<Stmt, ParseError> {
+        self.advance(); // consume 'defer'
+
+        self.consume(TokenKind::LeftBrace, "Expected '{' after 'defer'")?;
+
+        let mut body = Vec::new();
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            body.push(self.statement()?);
+        }
+
+        self.consume(TokenKind::RightBrace, "Expected '}' after defer body")?;
+        Ok(Stmt::Defer { body })
+    }
+
     fn do_while_loop(&mut self) -> Result<Stmt, ParseError> {
         self.advance(); // consume 'do'
 
@@ -324,7 +567,12 @@ impl Parser {
     fn for_loop(&mut self) -> Result<Stmt, ParseError> {
         self.advance(); // consume 'for'
 
-        let iterator = self.consume_identifier("Expected iterator variable in for loop")?;
+        let iterator = self.pattern()?;
+        let value = if self.matches(&[TokenKind::Comma]) {
+            Some(self.pattern()?)
+        } else {
+            None
+        };
         self.consume(TokenKind::In, "Expected 'in' keyword in for loop")?;
         let iterable = self.expression()?;
 
@@ -339,6 +587,7 @@ impl Parser {
 
         Ok(Stmt::For {
             iterator,
+            value,
             iterable,
             body,
         })
@@ -405,22 +654,10 @@ impl Parser {
     }
 
     fn consume_label(&mut self) -> Result<Stmt, ParseError> {
-        let mut label: Vec<(
-            String,
-            bool,
-            Vec<String>,
-            Vec<String>,
-            Vec<String>,
-            Vec<Stmt>,
-        )> = Vec::new();
-
-        let callable: bool;
-
-        if self.check(TokenKind::At) {
-            callable = false; // Control flow label (has @)
-        } else {
-            callable = true; // Callable label (no @)
-        }
+        let mut label: Vec<LabelItem> = Vec::new();
+
+        // Control flow labels start with '@', callable labels do not
+        let callable: bool = !self.check(TokenKind::At);
 
         if callable {
             // Get label name
@@ -444,16 +681,24 @@ impl Parser {
                 TokenKind::RightBracket,
                 "Expected ']' to enclose function visit",
             )?;
-            let name = self.consume_identifier("Expected label name")?;
+            let token = self.peek().clone();
+            let mut name = self.consume_identifier("Expected label name")?;
+
+            // Record.method attaches the label as a method of that record type
+            if self.matches(&[TokenKind::Dot]) {
+                let method = self.consume_identifier("Expected method name after '.'")?;
+                name = format!("{}.{}", name, method);
+            }
+
             self.consume(TokenKind::LeftParen, "Expected '(' after label name")?;
 
             let mut params: Vec<String> = Vec::new(); // External parameter names
-            let mut internal_names: Vec<String> = Vec::new(); // Internal variable names
+            let mut internal_names: Vec<Pattern> = Vec::new(); // Internal names or patterns
 
             while !self.check(TokenKind::RightParen) {
                 let external_param = self.consume_identifier("Expected parameter name")?;
                 self.consume(TokenKind::Equal, "Expected '=' in parameter mapping")?;
-                let internal_name = self.consume_identifier("Expected internal variable name")?;
+                let internal_name = self.pattern()?;
 
                 params.push(external_param); // Add to params
                 internal_names.push(internal_name); // Add to internal names
@@ -475,10 +720,11 @@ impl Parser {
 
             self.consume(TokenKind::RightBrace, "Expected '}' after label body")?;
 
-            label.push((name, callable, visit, params, internal_names, body));
+            label.push((name, callable, visit, params, internal_names, body, token));
         } else {
             // Control flow label code...
             self.advance();
+            let token = self.peek().clone();
             let name = self.consume_identifier("Expected label name")?;
             self.consume(TokenKind::LeftBrace, "Expected '{' before label body")?;
             let mut body: Vec<Stmt> = Vec::new();
@@ -489,7 +735,7 @@ impl Parser {
 
             self.consume(TokenKind::RightBrace, "Expected '}' after label body")?;
 
-            label.push((name, callable, vec![], vec![], vec![], body));
+            label.push((name, callable, vec![], vec![], vec![], body, token));
         }
 
         Ok(Stmt::Label { _label_: label })
@@ -521,7 +767,24 @@ impl Parser {
     // =========================================================
 
     fn expression(&mut self) -> Result<Expr, ParseError> {
-        self.logical_or()
+        self.nil_coalesce()
+    }
+
+    // a ?? b: lowest binary precedence, b is only evaluated when a is nil
+    fn nil_coalesce(&mut self) -> Result<Expr, ParseError> {
+        let mut expr = self.logical_or()?;
+
+        while self.matches(&[TokenKind::QuestionQuestion]) {
+            let operator = self.previous().clone();
+            let right = self.logical_or()?;
+            expr = Expr::Binary {
+                left: Box::new(expr),
+                operator,
+                right: Box::new(right),
+            };
+        }
+
+        Ok(expr)
     }
 
     fn logical_or(&mut self) -> Result<Expr, ParseError> {
@@ -612,7 +875,7 @@ impl Parser {
     fn factor(&mut self) -> Result<Expr, ParseError> {
         let mut expr = self.unary()?;
 
-        while self.matches(&[TokenKind::Star, TokenKind::Slash]) {
+        while self.matches(&[TokenKind::Star, TokenKind::Slash, TokenKind::Percent]) {
             let operator = self.previous().clone();
             let right = self.unary()?;
             expr = Expr::Binary {
@@ -635,7 +898,37 @@ impl Parser {
                     right: Box::new(right),
                 })
             }
-            _ => self.primary(),
+            // ++x / --xs[i]: update first, yield the new value
+            TokenKind::PlusPlus | TokenKind::MinusMinus => {
+                let operator = self.advance();
+                let (ds, member) = match self.unary()? {
+                    Expr::Variable { name } => (name, Vec::new()),
+                    Expr::Access { ds, member } => (ds, member),
+                    _ => {
+                        return Err(self.error(&format!(
+                            "Expected a variable or indexed target after '{}'",
+                            operator.lexeme
+                        )));
+                    }
+                };
+                self.step_update(ds, member, operator.kind, false)
+            }
+            _ => {
+                let mut expr = self.primary()?;
+
+                // Method calls on literals and groupings: "a,b".split(",")
+                if self.check(TokenKind::Dot) && self.at_accessor() {
+                    self.advance(); // consume '.'
+                    let method = self.consume_identifier("Expected method name after '.'")?;
+                    expr = self.method_call(expr, method)?;
+                }
+
+                // Postfix `?` (one that does not start `?[` or `?.name`)
+                while self.matches(&[TokenKind::IdentityOperator]) {
+                    expr = Expr::Propagate(Box::new(expr));
+                }
+                Ok(expr)
+            }
         }
     }
 
@@ -643,6 +936,7 @@ impl Parser {
         // [&d, ] -> dictionary
         // [&l, ] -> array/list
         // [&a, ] -> axis (const vector)
+        // [&s, ] -> set
         self.advance();
         self.consume(TokenKind::Ampersand, "Expected '&' after '['")?;
 
@@ -730,61 +1024,283 @@ impl Parser {
                 Ok(Expr::Axis{elements})
             }
 
-            _ => Err(self.error("Expected 'd', 'l', or 'a' after '&' in dataset syntax")),
+            // 's' is matched by lexeme rather than reserved as a keyword so
+            // that it stays usable as an ordinary variable name.
+            TokenKind::Identifier if self.peek().lexeme == "s" => {
+                self.advance();
+                self.consume(TokenKind::Comma, "Expected ',' after [&s] as [&s, ...]")?;
+
+                let mut elements: Vec<Expr> = Vec::new();
+
+                while !self.check(TokenKind::RightBracket) {
+                    // Parse each element expression
+                    let element = self.expression()?;
+                    elements.push(element);
+
+                    // Handle comma separation between elements
+                    if !self.check(TokenKind::RightBracket) {
+                        self.consume(TokenKind::Comma, "Expected ',' between set elements")?;
+                    }
+                }
+
+                self.consume(
+                    TokenKind::RightBracket,
+                    "Expected ']' after set elements",
+                )?;
+
+                Ok(Expr::Set{elements})
+            }
+
+            _ => Err(self.error("Expected 'd', 'l', 'a', or 's' after '&' in dataset syntax")),
         }
     }
 
-    fn primary(&mut self) -> Result<Expr, ParseError> {
+    // name, [a, b, ...rest] or {key, key: pattern, ...rest}
+    fn pattern(&mut self) -> Result<Pattern, ParseError> {
+        if self.matches(&[TokenKind::LeftBracket]) {
+            let mut items = Vec::new();
+            let mut rest = None;
+
+            while !self.check(TokenKind::RightBracket) {
+                if let Some(name) = self.rest_pattern()? {
+                    rest = Some(name);
+                    break;
+                }
+                items.push(self.pattern()?);
+                if !self.matches(&[TokenKind::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(TokenKind::RightBracket, "Expected ']' to close array pattern")?;
+            return Ok(Pattern::Array { items, rest });
+        }
+
+        if self.matches(&[TokenKind::LeftBrace]) {
+            let mut entries = Vec::new();
+            let mut rest = None;
+
+            while !self.check(TokenKind::RightBrace) {
+                if let Some(name) = self.rest_pattern()? {
+                    rest = Some(name);
+                    break;
+                }
+
+                let key = self.pattern_key()?;
+
+                // {host} is short for {host: host}
+                let target = if self.matches(&[TokenKind::Colon]) {
+                    self.pattern()?
+                } else {
+                    Pattern::Name(key.clone())
+                };
+                entries.push((key, target));
+
+                if !self.matches(&[TokenKind::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(TokenKind::RightBrace, "Expected '}' to close dictionary pattern")?;
+            return Ok(Pattern::Dict { entries, rest });
+        }
+
+        Ok(Pattern::Name(self.consume_identifier("Expected a name or a pattern")?))
+    }
+
+    // Key of a dictionary pattern entry: a name or a string
+    fn pattern_key(&mut self) -> Result<String, ParseError> {
+        if !self.check(TokenKind::String) {
+            return self.consume_identifier("Expected key in dictionary pattern");
+        }
+        match self.advance().literal {
+            Some(crate::lexer::Literal::String(s)) => Ok(s),
+            _ => Err(self.error("Invalid string key in dictionary pattern")),
+        }
+    }
+
+    // `...name` at the end of a pattern
+    fn rest_pattern(&mut self) -> Result<Option<String>, ParseError> {
+        if !self.check(TokenKind::Dot) {
+            return Ok(None);
+        }
+        for _ in 0..3 {
+            self.consume(TokenKind::Dot, "Expected '...' before rest name")?;
+        }
+        let name = self.consume_identifier("Expected a name after '...'")?;
+        if !self.check(TokenKind::RightBracket) && !self.check(TokenKind::RightBrace) {
+            return Err(self.error("'...rest' must come last in a pattern"));
+        }
+        Ok(Some(name))
+    }
+
+    fn destructure(&mut self) -> Result<Expr, ParseError> {
+        let pattern = self.pattern()?;
+        self.consume(TokenKind::Equal, "Expected '=' after destructuring pattern")?;
+        let value = self.expression()?;
+        Ok(Expr::Destructure {
+            pattern,
+            val: Box::new(value),
+        })
+    }
+
+    fn match_expr(&mut self) -> Result<Expr, ParseError> {
+        self.advance(); // consume 'match'
+        let subject = self.expression()?;
+        self.consume(TokenKind::LeftBrace, "Expected '{' after match subject")?;
+
+        let mut arms = Vec::new();
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            let pattern = self.match_pattern()?;
+            let guard = if self.matches(&[TokenKind::If]) {
+                Some(self.expression()?)
+            } else {
+                None
+            };
+            self.consume(TokenKind::Arrow, "Expected '->' after match pattern")?;
+            let body = self.expression()?;
+            arms.push(MatchArm { pattern, guard, body });
+
+            // Arms may be separated by commas
+            self.matches(&[TokenKind::Comma]);
+        }
+
+        self.consume(TokenKind::RightBrace, "Expected '}' after match arms")?;
+        Ok(Expr::Match {
+            subject: Box::new(subject),
+            arms,
+        })
+    }
+
+    // pattern | pattern | ...
+    fn match_pattern(&mut self) -> Result<MatchPattern, ParseError> {
+        let first = self.match_alternative()?;
+        if !self.check(TokenKind::Or) {
+            return Ok(first);
+        }
+
+        let mut alternatives = vec![first];
+        while self.matches(&[TokenKind::Or]) {
+            alternatives.push(self.match_alternative()?);
+        }
+        Ok(MatchPattern::Alt(alternatives))
+    }
+
+    fn match_alternative(&mut self) -> Result<MatchPattern, ParseError> {
         match self.peek().kind {
-            TokenKind::LeftBracket => self.consume_dataset(),
+            // Capitalised names are types, anything else binds
+            TokenKind::Identifier => {
+                let name = self.advance().lexeme;
+                if name == "_" {
+                    Ok(MatchPattern::Wildcard)
+                } else if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+                    Ok(MatchPattern::Type(name))
+                } else if self.matches(&[TokenKind::At]) {
+                    Ok(MatchPattern::Bind(name, Some(Box::new(self.match_alternative()?))))
+                } else {
+                    Ok(MatchPattern::Bind(name, None))
+                }
+            }
 
-            TokenKind::ColonColon => {
-                self.advance();
-                self.consume(
-                    TokenKind::LeftBracket,
-                    "Expected '[' to consume dynamic array",
-                )?;
+            TokenKind::Number
+            | TokenKind::String
+            | TokenKind::Char
+            | TokenKind::True
+            | TokenKind::False
+            | TokenKind::Nil
+            | TokenKind::Minus => Ok(MatchPattern::Literal(self.unary()?)),
 
-                let start: String = self
-                    .consume(
-                        TokenKind::Number,
-                        "Expected Starting Number to create dynamic Array",
-                    )
-                    .unwrap()
-                    .lexeme;
+            TokenKind::ColonColon => Ok(MatchPattern::Range(self.primary()?)),
 
-                self.consume(TokenKind::Dot, "Missing a '.' in for loop")?;
-                self.consume(TokenKind::Dot, "Missing another '.' in for loop")?;
-                let end: String = self
-                    .consume(
-                        TokenKind::Number,
-                        "Expected Starting Number to create dynamic Array",
-                    )
-                    .unwrap()
-                    .lexeme;
+            TokenKind::LeftBracket => {
+                self.advance();
+                let mut items = Vec::new();
+                let mut rest = None;
 
-                self.consume(
-                    TokenKind::RightBracket,
-                    "Expected ']' to consume dynamic array",
-                )?;
+                while !self.check(TokenKind::RightBracket) {
+                    if let Some(name) = self.rest_pattern()? {
+                        rest = Some(name);
+                        break;
+                    }
+                    items.push(self.match_pattern()?);
+                    if !self.matches(&[TokenKind::Comma]) {
+                        break;
+                    }
+                }
 
-                let _start_ = start.parse::<i128>().unwrap();
-                let _end_ = end.parse::<i128>().unwrap();
+                self.consume(TokenKind::RightBracket, "Expected ']' to close array pattern")?;
+                Ok(MatchPattern::Array { items, rest })
+            }
 
-                let mut values = Vec::new();
+            TokenKind::LeftBrace => {
+                self.advance();
+                let mut entries = Vec::new();
+                let mut rest = None;
 
-                if _start_ <= _end_ {
-                    for i in _start_..=_end_ {
-                        values.push(i);
+                while !self.check(TokenKind::RightBrace) {
+                    if let Some(name) = self.rest_pattern()? {
+                        rest = Some(name);
+                        break;
                     }
-                } else {
-                    // descending range support
-                    for i in (_end_..=_start_).rev() {
-                        values.push(i);
+
+                    let key = self.pattern_key()?;
+                    let target = if self.matches(&[TokenKind::Colon]) {
+                        self.match_pattern()?
+                    } else {
+                        MatchPattern::Bind(key.clone(), None)
+                    };
+                    entries.push((key, target));
+
+                    if !self.matches(&[TokenKind::Comma]) {
+                        break;
                     }
                 }
 
-                Ok(Expr::Iterable { value: values })
+                self.consume(TokenKind::RightBrace, "Expected '}' to close dictionary pattern")?;
+                Ok(MatchPattern::Dict { entries, rest })
+            }
+
+            _ => Err(self.error("Expected a match pattern")),
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        match self.peek().kind {
+            TokenKind::Match => self.match_expr(),
+
+            // [&l, ...] builds a collection, a bare [ starts a pattern
+            TokenKind::LeftBracket
+                if self.tokens.get(self.current + 1).map(|t| t.kind) == Some(TokenKind::Ampersand) =>
+            {
+                self.consume_dataset()
+            }
+
+            TokenKind::LeftBracket | TokenKind::LeftBrace => self.destructure(),
+
+            TokenKind::ColonColon => {
+                self.advance();
+                self.consume(TokenKind::LeftBracket, "Expected '[' after '::' to start a range")?;
+
+                let start = self.expression()?;
+                self.consume(TokenKind::Dot, "Expected '..' after range start")?;
+                self.consume(TokenKind::Dot, "Expected '..' after range start")?;
+                let inclusive = !self.matches(&[TokenKind::Less]);
+                let end = self.expression()?;
+
+                let step = if self.matches(&[TokenKind::Colon]) {
+                    Some(Box::new(self.expression()?))
+                } else {
+                    None
+                };
+
+                self.consume(TokenKind::RightBracket, "Expected ']' to close range")?;
+
+                Ok(Expr::Range {
+                    start: Box::new(start),
+                    end: Box::new(end),
+                    step,
+                    inclusive,
+                })
             }
 
             TokenKind::False => {
@@ -866,8 +1382,15 @@ impl Parser {
                 Ok(Expr::Print(Box::new(expr)))
             }
 
+            // Used as an expression, the expansion is parenthesised
+            TokenKind::Hash if self.at_token_macro_call() => {
+                self.expand_token_macro(true)?;
+                self.primary()
+            }
+
             TokenKind::Hash => {
-                self.advance();
+                // Argument count errors point at the call, not past it
+                let call_token = self.advance();
                 let macro_name = self
                     .consume_identifier("Expected 'Identifier as macro name'")
                     .unwrap();
@@ -893,19 +1416,21 @@ impl Parser {
                             return Err(self.error(&format!("undefined macro {}", macro_name)));
                         }
                     };
-                    let (macro_params, macro_stmts) = macro_body;
-
-                    let mut variables: Vec<Expr> = Vec::new();
-                    for (_param_, _arg_) in macro_params.iter().zip(args) {
-                        variables.push(Expr::AllocateVariable {
-                            name: _param_.clone(),
-                            val: Box::new(_arg_),
-                        });
+                    let (macro_params, macro_stmts) = macro_body.clone();
+
+                    if macro_params.len() != args.len() {
+                        return Err(self.error_at(call_token, &format!(
+                            "Macro {} expects {} argument(s), got {}",
+                            macro_name,
+                            macro_params.len(),
+                            args.len()
+                        )));
                     }
 
                     Ok(Expr::MacroCall {
-                        var: variables,
-                        body: macro_stmts.clone(),
+                        params: macro_params,
+                        args,
+                        body: macro_stmts,
                     })
                 } else {
                     Err(self.error(&format!(
@@ -931,26 +1456,68 @@ impl Parser {
 
         match self.peek().kind {
         
-            TokenKind::LeftBracket => {
-                self.advance(); // consume '['
-                let mut indices: Vec<Expr> = Vec::new();
-                
-                // Parse first index
-                indices.push(self.expression()?);
-                
-                // Consume the closing ']'
-                self.consume(TokenKind::RightBracket, "Expected ']' after index")?;
-                
-                // Check for chained access: x["key1"]["key2"]
-                while self.check(TokenKind::LeftBracket) {
-                    self.advance(); // consume '['
-                    indices.push(self.expression()?);
-                    self.consume(TokenKind::RightBracket, "Expected ']' after index")?;
+            // ---------------------------------------------------
+            // Access chain with optional assignment:
+            // x["key"][0].field  /  x[0].field = expr
+            // ---------------------------------------------------
+            TokenKind::LeftBracket | TokenKind::Dot | TokenKind::IdentityOperator
+                if self.at_accessor() =>
+            {
+                let mut member: Vec<Accessor> = Vec::new();
+
+                while self.at_accessor() {
+                    let optional = self.matches(&[TokenKind::IdentityOperator]);
+
+                    let step = if self.matches(&[TokenKind::LeftBracket]) {
+                        let index = self.expression()?;
+                        self.consume(TokenKind::RightBracket, "Expected ']' after index")?;
+                        Accessor::Index(index)
+                    } else {
+                        self.advance(); // consume '.'
+                        let name = self.consume_identifier("Expected field name after '.'")?;
+
+                        // x.name(...) is a method call on everything before it
+                        if self.check(TokenKind::LeftParen) {
+                            if optional {
+                                return Err(self.error("Cannot call a method after '?.'"));
+                            }
+                            let receiver = if member.is_empty() {
+                                Expr::Variable { name: identifier }
+                            } else {
+                                Expr::Access { ds: identifier, member }
+                            };
+                            return self.method_call(receiver, name);
+                        }
+
+                        Accessor::Field(name)
+                    };
+
+                    member.push(if optional {
+                        Accessor::Optional(Box::new(step))
+                    } else {
+                        step
+                    });
                 }
-                
+
+                if let Some(update) = self.update_suffix(&identifier, &member)? {
+                    return Ok(update);
+                }
+
+                if self.matches(&[TokenKind::Equal]) {
+                    if member.iter().any(|m| matches!(m, Accessor::Optional(_))) {
+                        return Err(self.error("Cannot assign through '?[' or '?.'"));
+                    }
+                    let value: Expr = self.expression()?;
+                    return Ok(Expr::AssignAccess {
+                        ds: identifier,
+                        member,
+                        val: Box::new(value),
+                    });
+                }
+
                 Ok(Expr::Access {
                     ds: identifier,
-                    member: indices,
+                    member,
                 })
             }
 
@@ -1009,6 +1576,20 @@ impl Parser {
                 })
             }
 
+            // ---------------------------------------------------
+            // Compound assignment and increment: x += 1, x++
+            // ---------------------------------------------------
+            TokenKind::PlusPlus
+            | TokenKind::MinusMinus
+            | TokenKind::PlusEqual
+            | TokenKind::MinusEqual
+            | TokenKind::StarEqual
+            | TokenKind::SlashEqual
+            | TokenKind::PercentEqual => {
+                let update = self.update_suffix(&identifier, &[])?;
+                Ok(update.expect("update operator was checked"))
+            }
+
             // ---------------------------------------------------
             // Just a variable reference
             // ---------------------------------------------------
@@ -1016,6 +1597,131 @@ impl Parser {
         }
     }
 
+    // `op= expr`, `++` or `--` following an assignable target
+    fn update_suffix(&mut self, ds: &str, member: &[Accessor]) -> Result<Option<Expr>, ParseError> {
+        let kind = self.peek().kind;
+        if matches!(kind, TokenKind::PlusPlus | TokenKind::MinusMinus) {
+            self.advance();
+            return self.step_update(ds.to_string(), member.to_vec(), kind, true).map(Some);
+        }
+
+        let operator = match kind {
+            TokenKind::PlusEqual => TokenKind::Plus,
+            TokenKind::MinusEqual => TokenKind::Minus,
+            TokenKind::StarEqual => TokenKind::Star,
+            TokenKind::SlashEqual => TokenKind::Slash,
+            TokenKind::PercentEqual => TokenKind::Percent,
+            _ => return Ok(None),
+        };
+        self.advance();
+        self.check_update_target(member)?;
+        let val = self.expression()?;
+
+        Ok(Some(Expr::CompoundAssign {
+            ds: ds.to_string(),
+            member: member.to_vec(),
+            operator,
+            val: Box::new(val),
+            postfix: false,
+        }))
+    }
+
+    // ++/-- are `+= 1` / `-= 1`
+    fn step_update(&mut self, ds: String, member: Vec<Accessor>, kind: TokenKind, postfix: bool) -> Result<Expr, ParseError> {
+        self.check_update_target(&member)?;
+        let operator = if kind == TokenKind::PlusPlus {
+            TokenKind::Plus
+        } else {
+            TokenKind::Minus
+        };
+
+        Ok(Expr::CompoundAssign {
+            ds,
+            member,
+            operator,
+            val: Box::new(Expr::_Literal_(Literal::Int(1))),
+            postfix,
+        })
+    }
+
+    fn check_update_target(&mut self, member: &[Accessor]) -> Result<(), ParseError> {
+        if member.iter().any(|m| matches!(m, Accessor::Optional(_))) {
+            return Err(self.error("Cannot assign through '?[' or '?.'"));
+        }
+        Ok(())
+    }
+
+    // Parses `(args)` of a method call, then any chained `.method(args)`
+    fn method_call(&mut self, receiver: Expr, method: String) -> Result<Expr, ParseError> {
+        let mut expr = receiver;
+        let mut method = method;
+
+        loop {
+            self.consume(TokenKind::LeftParen, "Expected '(' after method name")?;
+            let mut args: Vec<(Option<String>, Expr)> = Vec::new();
+
+            while !self.check(TokenKind::RightParen) {
+                // name=expr is a named argument, anything else is positional
+                let named = self.check(TokenKind::Identifier)
+                    && self
+                        .tokens
+                        .get(self.current + 1)
+                        .is_some_and(|t| t.kind == TokenKind::Equal);
+
+                if named {
+                    let name = self.advance().lexeme;
+                    self.advance(); // consume '='
+                    args.push((Some(name), self.expression()?));
+                } else {
+                    args.push((None, self.expression()?));
+                }
+
+                if !self.matches(&[TokenKind::Comma]) {
+                    break;
+                }
+            }
+
+            self.consume(TokenKind::RightParen, "Expected ')' to enclose method call")?;
+
+            expr = Expr::MethodCall {
+                receiver: Box::new(expr),
+                method,
+                args,
+            };
+
+            if !self.at_accessor() {
+                return Ok(expr);
+            }
+            if !self.matches(&[TokenKind::Dot]) {
+                return Err(self.error("Only a method call can follow a method call"));
+            }
+            method = self.consume_identifier("Expected method name after '.'")?;
+        }
+    }
+
+    // '[' starts an index when it is on the same line as what it indexes,
+    // so a line can open with an array pattern; '.' only starts a field
+    // access when a name follows, so the '..' of range syntax is left
+    // alone. A '?' in front of either makes the step nil-safe.
+    fn at_accessor(&self) -> bool {
+        self.accessor_at(self.current)
+    }
+
+    fn accessor_at(&self, pos: usize) -> bool {
+        let kind = |i: usize| self.tokens.get(i).map(|t| t.kind);
+        match kind(pos) {
+            Some(TokenKind::LeftBracket) => {
+                pos == 0 || self.tokens[pos].line == self.tokens[pos - 1].line
+            }
+            Some(TokenKind::Dot) => kind(pos + 1) == Some(TokenKind::Identifier),
+            Some(TokenKind::IdentityOperator) => {
+                matches!(kind(pos + 1), Some(TokenKind::LeftBracket | TokenKind::Dot))
+                    && self.accessor_at(pos + 1)
+            }
+            _ => false,
+        }
+    }
+
     // =========================================================
     // Cursor utilities
     // =========================================================
@@ -1066,10 +1772,24 @@ impl Parser {
     // =========================================================
 
     fn error(&mut self, message: &str) -> ParseError {
-        let err = ParseError {
-            token: self.peek().clone(),
-            message: message.to_string(),
+        let token = self.peek().clone();
+        self.error_at(token, message)
+    }
+
+    fn error_at(&mut self, token: Token, message: &str) -> ParseError {
+        // Errors inside a token macro expansion also name the macro
+        let message = match &token.expansion {
+            Some(origin) => format!(
+                "{} (in macro {} defined at line {}, called at line {})",
+                message, origin.macro_name, origin.defined_at, origin.called_at
+            ),
+            None => message.to_string(),
         };
+        self.report(token, message)
+    }
+
+    fn report(&mut self, token: Token, message: String) -> ParseError {
+        let err = ParseError { token, message };
         self.errors.push(err.clone());
         err
     }
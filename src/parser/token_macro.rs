@@ -0,0 +1,536 @@
+use crate::lexer::tokens::Expansion;
+use crate::lexer::{Lexer, Literal, Token, TokenKind};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Token macros work on tokens instead of parsed statements, so they can
+// produce any syntax:
+//
+//   _macro_ NAME {
+//       (matcher) -> [ transcriber ]
+//       ...
+//   }
+//
+// A call `#NAME(...)` takes the first rule whose matcher accepts the
+// argument tokens and is replaced by its transcriber. Matchers use
+// `$x:expr`, `$x:ident`, `$x:block` (a `{ ... }` whose statements are
+// substituted without the braces) and `$x:tt` (one token or bracketed
+// group), plus `$( ... ) sep *` or `+` for repetition. Transcribers use
+// `$x`, `$( ... ) sep *`, `#$x` to turn the tokens of `$x` into a string
+// and `a ## b` to paste two tokens into one identifier.
+
+// Expansions that expand further macros may nest this deep
+pub(crate) const MAX_EXPANSION_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Fragment {
+    Expr,
+    Ident,
+    Block,
+    Tt,
+}
+
+#[derive(Debug)]
+enum Matcher {
+    Exact(Token),
+    Fragment { name: String, kind: Fragment },
+    Repeat { body: Vec<Matcher>, sep: Option<Token>, at_least_one: bool },
+}
+
+#[derive(Debug)]
+enum Piece {
+    Exact(Token),
+    Var(String),
+    Stringify(String),
+    Paste,
+    Repeat { body: Vec<Piece>, sep: Option<Token> },
+}
+
+#[derive(Debug)]
+struct Rule {
+    matchers: Vec<Matcher>,
+    pieces: Vec<Piece>,
+    // First line of the transcriber; expanded lines are counted from it
+    line: usize,
+}
+
+#[derive(Debug)]
+pub(crate) struct TokenMacro {
+    pub name: String,
+    pub line: usize,
+    rules: Vec<Rule>,
+}
+
+// What a matcher variable captured; inside a repetition, one entry per round.
+// An `expr` is substituted in parentheses so it stays one operand.
+#[derive(Debug, Clone)]
+enum Capture {
+    One(Vec<Token>),
+    Expr(Vec<Token>),
+    Many(Vec<Capture>),
+}
+
+type Bindings = HashMap<String, Capture>;
+
+impl TokenMacro {
+    pub(crate) fn new(name: String, line: usize) -> Self {
+        Self { name, line, rules: Vec::new() }
+    }
+
+    pub(crate) fn add_rule(&mut self, matcher: &[Token], transcriber: &[Token]) -> Result<(), String> {
+        let matchers = compile_matchers(matcher)?;
+        let pieces = compile_pieces(transcriber)?;
+
+        let mut bound = Vec::new();
+        matcher_names(&matchers, &mut bound);
+        let mut used = Vec::new();
+        piece_names(&pieces, &mut used);
+        if let Some(name) = used.iter().find(|name| !bound.contains(name)) {
+            return Err(format!("'${}' is not bound by the matcher", name));
+        }
+
+        let line = transcriber.first().map_or(self.line, |t| t.line);
+        self.rules.push(Rule { matchers, pieces, line });
+        Ok(())
+    }
+
+    // Tokens replacing a call with these arguments; `wrap` puts them in
+    // parentheses for a call used as an expression
+    pub(crate) fn expand(&self, args: &[Token], called_at: usize, depth: usize, wrap: bool) -> Result<Vec<Token>, String> {
+        let origin = Rc::new(Expansion {
+            macro_name: self.name.clone(),
+            defined_at: self.line,
+            called_at,
+            depth,
+        });
+
+        for rule in &self.rules {
+            let mut binds = Bindings::new();
+            if match_seq(&rule.matchers, args, 0, None, &mut binds) != Some(args.len()) {
+                continue;
+            }
+
+            let mut out = Emitter {
+                tokens: Vec::new(),
+                origin: origin.clone(),
+                base: rule.line,
+                called_at,
+                extra: 0,
+                paste: false,
+            };
+            if wrap {
+                out.emit(vec![out.token(TokenKind::LeftParen, "(")])?;
+            }
+            out.pieces(&rule.pieces, &binds)?;
+            if out.paste {
+                return Err("'##' needs a token on both sides".to_string());
+            }
+            if wrap {
+                out.emit(vec![out.token(TokenKind::RightParen, ")")])?;
+            }
+            return Ok(out.tokens);
+        }
+
+        Err("no rule matches these arguments".to_string())
+    }
+}
+
+fn is_open(kind: TokenKind) -> bool {
+    matches!(kind, TokenKind::LeftParen | TokenKind::LeftBracket | TokenKind::LeftBrace)
+}
+
+fn is_close(kind: TokenKind) -> bool {
+    matches!(kind, TokenKind::RightParen | TokenKind::RightBracket | TokenKind::RightBrace)
+}
+
+// `$name` may use any word, keywords like `a` or `d` included
+fn is_name(token: &Token) -> bool {
+    token.lexeme.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && token.lexeme.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn same(a: &Token, b: &Token) -> bool {
+    a.kind == b.kind && a.lexeme == b.lexeme
+}
+
+// Index just past the token at `pos`, or past the group it opens
+fn group_end(tokens: &[Token], pos: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(pos) {
+        if is_open(token.kind) {
+            depth += 1;
+        } else if is_close(token.kind) {
+            depth = depth.checked_sub(1)?;
+        }
+        if depth == 0 {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+// `$( ... )` followed by an optional separator and `*` or `+`
+fn repetition(tokens: &[Token], open: usize) -> Result<(usize, Option<Token>, bool, usize), String> {
+    let close = group_end(tokens, open).ok_or("unclosed '$('")? - 1;
+    let mut next = close + 1;
+
+    let mut sep = None;
+    let is_op = |t: Option<&Token>| t.is_some_and(|t| matches!(t.kind, TokenKind::Star | TokenKind::Plus));
+    if !is_op(tokens.get(next)) {
+        sep = tokens.get(next).cloned();
+        next += 1;
+    }
+    match tokens.get(next) {
+        Some(op) if is_op(Some(op)) => Ok((close, sep, op.kind == TokenKind::Plus, next + 1)),
+        _ => Err("expected '*' or '+' after '$( ... )'".to_string()),
+    }
+}
+
+fn compile_matchers(tokens: &[Token]) -> Result<Vec<Matcher>, String> {
+    let mut matchers = Vec::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        if tokens[i].kind != TokenKind::Dollar {
+            matchers.push(Matcher::Exact(tokens[i].clone()));
+            i += 1;
+            continue;
+        }
+
+        match tokens.get(i + 1) {
+            Some(token) if is_name(token) => {
+                let name = token.lexeme.clone();
+                let kind = match (tokens.get(i + 2).map(|t| t.kind), tokens.get(i + 3)) {
+                    (Some(TokenKind::Colon), Some(kind)) => match kind.lexeme.as_str() {
+                        "expr" => Fragment::Expr,
+                        "ident" => Fragment::Ident,
+                        "block" => Fragment::Block,
+                        "tt" => Fragment::Tt,
+                        other => return Err(format!("unknown fragment kind '{}' for '${}'", other, name)),
+                    },
+                    _ => return Err(format!("'${}' needs a kind, as in '${}:expr'", name, name)),
+                };
+                matchers.push(Matcher::Fragment { name, kind });
+                i += 4;
+            }
+            Some(token) if token.kind == TokenKind::LeftParen => {
+                let (close, sep, at_least_one, next) = repetition(tokens, i + 1)?;
+                let body = compile_matchers(&tokens[i + 2..close])?;
+                matchers.push(Matcher::Repeat { body, sep, at_least_one });
+                i = next;
+            }
+            _ => return Err("expected a name or '(' after '$'".to_string()),
+        }
+    }
+
+    Ok(matchers)
+}
+
+fn compile_pieces(tokens: &[Token]) -> Result<Vec<Piece>, String> {
+    let mut pieces = Vec::new();
+    let mut i = 0;
+    // Dollar followed by a name counts as TokenKind::Identifier here
+    let kind = |i: usize| {
+        tokens.get(i).map(|t| match t.kind {
+            TokenKind::Dollar | TokenKind::Hash | TokenKind::LeftParen => t.kind,
+            _ if is_name(t) => TokenKind::Identifier,
+            other => other,
+        })
+    };
+
+    while i < tokens.len() {
+        match (kind(i), kind(i + 1), kind(i + 2)) {
+            (Some(TokenKind::Hash), Some(TokenKind::Hash), _) => {
+                pieces.push(Piece::Paste);
+                i += 2;
+            }
+            (Some(TokenKind::Hash), Some(TokenKind::Dollar), Some(TokenKind::Identifier)) => {
+                pieces.push(Piece::Stringify(tokens[i + 2].lexeme.clone()));
+                i += 3;
+            }
+            (Some(TokenKind::Dollar), Some(TokenKind::Identifier), _) => {
+                pieces.push(Piece::Var(tokens[i + 1].lexeme.clone()));
+                i += 2;
+            }
+            (Some(TokenKind::Dollar), Some(TokenKind::LeftParen), _) => {
+                let (close, sep, _, next) = repetition(tokens, i + 1)?;
+                let body = compile_pieces(&tokens[i + 2..close])?;
+                pieces.push(Piece::Repeat { body, sep });
+                i = next;
+            }
+            (Some(TokenKind::Dollar), _, _) => return Err("expected a name or '(' after '$'".to_string()),
+            _ => {
+                pieces.push(Piece::Exact(tokens[i].clone()));
+                i += 1;
+            }
+        }
+    }
+
+    Ok(pieces)
+}
+
+fn matcher_names(matchers: &[Matcher], names: &mut Vec<String>) {
+    for matcher in matchers {
+        match matcher {
+            Matcher::Exact(_) => {}
+            Matcher::Fragment { name, .. } => names.push(name.clone()),
+            Matcher::Repeat { body, .. } => matcher_names(body, names),
+        }
+    }
+}
+
+fn piece_names(pieces: &[Piece], names: &mut Vec<String>) {
+    for piece in pieces {
+        match piece {
+            Piece::Var(name) | Piece::Stringify(name) => names.push(name.clone()),
+            Piece::Repeat { body, .. } => piece_names(body, names),
+            Piece::Exact(_) | Piece::Paste => {}
+        }
+    }
+}
+
+// Matches `matchers` against `input` from `pos`; the position reached, or
+// None. `follow` is the token expected right after this sequence.
+fn match_seq(matchers: &[Matcher], input: &[Token], mut pos: usize, follow: Option<&Token>, binds: &mut Bindings) -> Option<usize> {
+    for (i, matcher) in matchers.iter().enumerate() {
+        let next = match matchers.get(i + 1) {
+            Some(Matcher::Exact(token)) => Some(token),
+            Some(_) => None,
+            None => follow,
+        };
+
+        pos = match matcher {
+            Matcher::Exact(token) => {
+                if !same(input.get(pos)?, token) {
+                    return None;
+                }
+                pos + 1
+            }
+            Matcher::Fragment { name, kind } => {
+                let (end, captured) = match_fragment(*kind, input, pos, next)?;
+                let capture = if *kind == Fragment::Expr && captured.len() > 1 {
+                    Capture::Expr(captured)
+                } else {
+                    Capture::One(captured)
+                };
+                binds.insert(name.clone(), capture);
+                end
+            }
+            Matcher::Repeat { body, sep, at_least_one } => {
+                let mut rounds: Vec<Bindings> = Vec::new();
+                let mut at = pos;
+                loop {
+                    let start = if rounds.is_empty() {
+                        at
+                    } else {
+                        match sep {
+                            Some(sep) if input.get(at).is_some_and(|t| same(t, sep)) => at + 1,
+                            Some(_) => break,
+                            None => at,
+                        }
+                    };
+                    let mut round = Bindings::new();
+                    match match_seq(body, input, start, sep.as_ref().or(next), &mut round) {
+                        Some(end) if end > start => {
+                            rounds.push(round);
+                            at = end;
+                        }
+                        _ => break,
+                    }
+                }
+                if *at_least_one && rounds.is_empty() {
+                    return None;
+                }
+
+                let mut names = Vec::new();
+                matcher_names(body, &mut names);
+                for name in names {
+                    let each = rounds.iter_mut().filter_map(|round| round.remove(&name)).collect();
+                    binds.insert(name, Capture::Many(each));
+                }
+                at
+            }
+        };
+    }
+    Some(pos)
+}
+
+// (end, captured tokens) for one fragment starting at `pos`
+fn match_fragment(kind: Fragment, input: &[Token], pos: usize, follow: Option<&Token>) -> Option<(usize, Vec<Token>)> {
+    let first = input.get(pos)?;
+    match kind {
+        Fragment::Ident => (first.kind == TokenKind::Identifier).then(|| (pos + 1, vec![first.clone()])),
+        Fragment::Tt => {
+            let end = group_end(input, pos)?;
+            Some((end, input[pos..end].to_vec()))
+        }
+        Fragment::Block => {
+            if first.kind != TokenKind::LeftBrace {
+                return None;
+            }
+            let end = group_end(input, pos)?;
+            Some((end, input[pos + 1..end - 1].to_vec()))
+        }
+        // Everything up to a top-level ',' or the token the pattern expects next
+        Fragment::Expr => {
+            let mut end = pos;
+            while let Some(token) = input.get(end) {
+                if token.kind == TokenKind::Comma || is_close(token.kind) || follow.is_some_and(|f| same(token, f)) {
+                    break;
+                }
+                end = group_end(input, end)?;
+            }
+            (end > pos).then(|| (end, input[pos..end].to_vec()))
+        }
+    }
+}
+
+// Builds the expansion. Template tokens keep their line breaks, counted
+// from the call site; captured tokens are moved along with them.
+struct Emitter {
+    tokens: Vec<Token>,
+    origin: Rc<Expansion>,
+    base: usize,
+    called_at: usize,
+    // Lines added by multi-line captures so far
+    extra: usize,
+    paste: bool,
+}
+
+impl Emitter {
+    // A token the expansion adds itself, on the current line
+    fn token(&self, kind: TokenKind, lexeme: &str) -> Token {
+        let mut token = Token::new(kind, lexeme, self.current_line());
+        token.expansion = Some(self.origin.clone());
+        token
+    }
+
+    fn current_line(&self) -> usize {
+        self.tokens.last().map_or(self.called_at, |t| t.line)
+    }
+
+    fn pieces(&mut self, pieces: &[Piece], binds: &Bindings) -> Result<(), String> {
+        for piece in pieces {
+            match piece {
+                Piece::Exact(token) => {
+                    let mut token = token.clone();
+                    token.line = self.called_at + token.line.saturating_sub(self.base) + self.extra;
+                    token.expansion = Some(self.origin.clone());
+                    self.emit(vec![token])?;
+                }
+
+                Piece::Var(name) => match &binds[name] {
+                    Capture::One(captured) if captured.is_empty() => self.emit(Vec::new())?,
+                    Capture::One(captured) => self.captured(captured, false)?,
+                    Capture::Expr(captured) => self.captured(captured, true)?,
+                    Capture::Many(_) => return Err(format!("'${}' repeats; use it inside '$( ... )'", name)),
+                },
+
+                Piece::Stringify(name) => {
+                    let (Capture::One(captured) | Capture::Expr(captured)) = &binds[name] else {
+                        return Err(format!("'#${}' repeats; use it inside '$( ... )'", name));
+                    };
+                    let text = captured.iter().map(|t| t.lexeme.as_str()).collect::<Vec<_>>().join(" ");
+                    let mut token = self.token(TokenKind::String, &format!("{:?}", text));
+                    token.literal = Some(Literal::String(text));
+                    self.emit(vec![token])?;
+                }
+
+                Piece::Paste => {
+                    if self.tokens.is_empty() || self.paste {
+                        return Err("'##' needs a token on both sides".to_string());
+                    }
+                    self.paste = true;
+                }
+
+                Piece::Repeat { body, sep } => {
+                    let mut names = Vec::new();
+                    piece_names(body, &mut names);
+
+                    let mut rounds = None;
+                    for name in &names {
+                        if let Capture::Many(each) = &binds[name] {
+                            match rounds {
+                                Some(n) if n != each.len() => {
+                                    return Err(format!("'${}' repeats {} times, other variables {}", name, each.len(), n));
+                                }
+                                _ => rounds = Some(each.len()),
+                            }
+                        }
+                    }
+                    let rounds = rounds.ok_or("'$( ... )' in a transcriber needs a repeating variable")?;
+
+                    for round in 0..rounds {
+                        if round > 0
+                            && let Some(sep) = sep
+                        {
+                            let sep = Token {
+                                literal: sep.literal.clone(),
+                                ..self.token(sep.kind, &sep.lexeme)
+                            };
+                            self.emit(vec![sep])?;
+                        }
+                        let mut inner = binds.clone();
+                        for name in &names {
+                            if let Some(Capture::Many(each)) = binds.get(name) {
+                                inner.insert(name.clone(), each[round].clone());
+                            }
+                        }
+                        self.pieces(body, &inner)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Captured tokens move to the current line, keeping their own breaks
+    fn captured(&mut self, captured: &[Token], parenthesise: bool) -> Result<(), String> {
+        let line = self.current_line();
+        let shift = |t: &Token| t.line.saturating_sub(captured[0].line);
+        let mut moved: Vec<Token> = captured
+            .iter()
+            .map(|t| Token { line: line + shift(t), ..t.clone() })
+            .collect();
+        let last_line = moved.last().map_or(line, |t| t.line);
+        self.extra += last_line - line;
+
+        if parenthesise {
+            moved.insert(0, self.token(TokenKind::LeftParen, "("));
+            moved.push(self.token(TokenKind::RightParen, ")"));
+            if let Some(close) = moved.last_mut() {
+                close.line = last_line;
+            }
+        }
+        self.emit(moved)
+    }
+
+    fn emit(&mut self, tokens: Vec<Token>) -> Result<(), String> {
+        let mut tokens = tokens.into_iter();
+        if self.paste {
+            // An empty capture leaves nothing to paste onto
+            let Some(right) = tokens.next() else {
+                return Ok(());
+            };
+            self.paste = false;
+            let left = self.tokens.pop().expect("paste follows a token");
+            self.tokens.push(paste(&left, &right)?);
+        }
+        self.tokens.extend(tokens);
+        Ok(())
+    }
+}
+
+// `left ## right`: the two lexemes must form one identifier
+fn paste(left: &Token, right: &Token) -> Result<Token, String> {
+    let text = format!("{}{}", left.lexeme, right.lexeme);
+    let lexed = Lexer::new(text.clone()).scan_tokens().map_err(|_| format!("'{}' is not a valid identifier", text))?;
+    match lexed.as_slice() {
+        [token, _eof] if token.kind == TokenKind::Identifier => Ok(Token {
+            line: left.line,
+            expansion: left.expansion.clone(),
+            ..token.clone()
+        }),
+        _ => Err(format!("pasting '{}' and '{}' does not form an identifier", left.lexeme, right.lexeme)),
+    }
+}
@@ -0,0 +1,139 @@
+use crate::parser::ast::{LabelItem, Stmt};
+use crate::parser::parser::ParseError;
+use crate::parser::visit::Visitor;
+use std::collections::{BTreeMap, HashSet};
+
+// Visible block permissions, checked before the script runs. Every block a
+// label lists in `visit[...]` must be declared with `visible`, and a label
+// may only read a block variable if it visits a block declaring it. A name
+// that is also bound in a scope around the read (assigned, a parameter, a
+// loop variable, ...) is left to the runtime: the read may mean that
+// binding. Bindings in other labels, loops or macro expansions do not count.
+pub(crate) fn check_visibility(statements: &[Stmt]) -> Vec<ParseError> {
+    let mut walker = Walker {
+        scopes: vec![(0, HashSet::new())],
+        ..Walker::default()
+    };
+    walker.stmts(statements);
+
+    // Assigning a block variable from a label that visits the block writes
+    // to the block; anywhere else it makes a binding of its own
+    let visits = |label: &LabelItem, var: &str| {
+        let declares = |block: &String| walker.blocks.get(block.as_str()).is_some_and(|vars| vars.contains(var));
+        label.2.iter().any(declares)
+    };
+    let mut scopes = walker.scopes.clone();
+    for &(var, scope, label) in &walker.assigned {
+        if !label.is_some_and(|label| visits(walker.labels[label].0, var)) {
+            scopes[scope].1.insert(var);
+        }
+    }
+    let bound = |var: &str, mut scope: usize| loop {
+        let (parent, names) = &scopes[scope];
+        if names.contains(var) {
+            return true;
+        }
+        if scope == 0 {
+            return false;
+        }
+        scope = *parent;
+    };
+
+    let mut errors = Vec::new();
+    for (label, reads) in &walker.labels {
+        let (name, visit, token) = (&label.0, &label.2, &label.6);
+
+        for block in visit {
+            if !walker.blocks.contains_key(block.as_str()) {
+                errors.push(ParseError {
+                    token: token.clone(),
+                    message: format!("Label '{}' references undefined visible block '{}'", name, block),
+                });
+            }
+        }
+
+        let mut reported = HashSet::new();
+        for &(var, scope) in reads {
+            if bound(var, scope) || visits(label, var) || !reported.insert(var) {
+                continue;
+            }
+            if let Some((first, _)) = walker.blocks.iter().find(|(_, vars)| vars.contains(var)) {
+                errors.push(ParseError {
+                    token: token.clone(),
+                    message: format!(
+                        "Label '{}' reads '{}' from visible block '{}', which is not in its visit[...]",
+                        name, var, first
+                    ),
+                });
+            }
+        }
+    }
+    errors
+}
+
+#[derive(Default)]
+struct Walker<'a> {
+    // Declared blocks and their variables, by name
+    blocks: BTreeMap<&'a str, HashSet<&'a str>>,
+    // Every scope with its parent and the names bound in it; 0 is the script
+    scopes: Vec<(usize, HashSet<&'a str>)>,
+    scope: usize,
+    // Each callable label with the names its own body reads, and the scope
+    // each read happens in
+    labels: Vec<(&'a LabelItem, Vec<(&'a str, usize)>)>,
+    // The callable label being walked
+    label: Option<usize>,
+    // Plain assignments, with their scope and label: whether one binds a
+    // name is only known once every block is declared
+    assigned: Vec<(&'a str, usize, Option<usize>)>,
+}
+
+impl<'a> Visitor<'a> for Walker<'a> {
+    fn read(&mut self, name: &'a String) {
+        if let Some(label) = self.label {
+            self.labels[label].1.push((name, self.scope));
+        }
+    }
+
+    fn bind(&mut self, name: &'a String) {
+        self.scopes[self.scope].1.insert(name);
+    }
+
+    fn assign(&mut self, name: &'a String) {
+        self.assigned.push((name, self.scope, self.label));
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push((self.scope, HashSet::new()));
+        self.scope = self.scopes.len() - 1;
+    }
+
+    fn exit_scope(&mut self) {
+        self.scope = self.scopes[self.scope].0;
+    }
+
+    fn stmt(&mut self, stmt: &'a Stmt) {
+        match stmt {
+            Stmt::Visible { _name_, _block_ } => {
+                let vars = self.blocks.entry(_name_.as_str()).or_default();
+                vars.extend(_block_.iter().map(|(var, _)| var.as_str()));
+            }
+            Stmt::Record { name, .. } => self.bind(name),
+            _ => {}
+        }
+        self.walk_stmt(stmt);
+    }
+
+    fn label(&mut self, item: &'a LabelItem) {
+        self.bind(&item.0);
+        if !item.1 {
+            // Control flow labels run as part of the enclosing body
+            self.walk_label(item);
+            return;
+        }
+        self.labels.push((item, Vec::new()));
+        let outer = self.label.replace(self.labels.len() - 1);
+        self.walk_label(item);
+        self.label = outer;
+    }
+}
@@ -0,0 +1,317 @@
+use crate::parser::ast::{Accessor, Expr, LabelItem, MatchArm, MatchPattern, Pattern, Stmt};
+
+// One walk over statements and expressions, shared by the passes that run
+// on the tree (macro hygiene, jump checks, visible block permissions,
+// finding `yield`). A pass overrides the hooks it cares about and calls the
+// matching `walk_*` to keep descending; everything else is visited in
+// source order. `Visitor` borrows the tree, `VisitorMut` may rewrite it.
+
+// What a nested statement list is
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum Body {
+    // The whole program; the walk itself never produces it
+    Script,
+    // if / elif / else
+    Branch,
+    Loop,
+    // A callable label's body
+    Label,
+    // A control flow label's body, run where it is defined
+    FlowLabel,
+    Defer,
+    // A macro expansion, spliced in where it is used
+    Macro,
+}
+
+macro_rules! make_visitor {
+    ($visitor:ident $(, $mutability:ident)?) => {
+        pub(crate) trait $visitor<'a> {
+            // A name used as a value, or the value a write goes into
+            fn read(&mut self, _name: &'a $($mutability)? String) {}
+
+            // A name bound in the current scope: a pattern, a match binding,
+            // a macro parameter
+            fn bind(&mut self, _name: &'a $($mutability)? String) {}
+
+            // `name = value`: rebinds the name where it is found, binds it in
+            // the current scope otherwise
+            fn assign(&mut self, name: &'a $($mutability)? String) {
+                self.bind(name);
+            }
+
+            // Loops, label bodies, defer blocks, macro expansions and match
+            // arms each run in a scope of their own
+            fn enter_scope(&mut self) {}
+
+            fn exit_scope(&mut self) {}
+
+            fn stmts(&mut self, body: &'a $($mutability)? [Stmt]) {
+                for stmt in body {
+                    self.stmt(stmt);
+                }
+            }
+
+            fn block(&mut self, body: &'a $($mutability)? [Stmt], _kind: Body) {
+                self.stmts(body);
+            }
+
+            fn stmt(&mut self, stmt: &'a $($mutability)? Stmt) {
+                self.walk_stmt(stmt);
+            }
+
+            fn expr(&mut self, expr: &'a $($mutability)? Expr) {
+                self.walk_expr(expr);
+            }
+
+            fn label(&mut self, item: &'a $($mutability)? LabelItem) {
+                self.walk_label(item);
+            }
+
+            fn walk_stmt(&mut self, stmt: &'a $($mutability)? Stmt) {
+                match stmt {
+                    Stmt::Expression(expr) | Stmt::Yield { value: expr } => self.expr(expr),
+                    Stmt::SmartLock { variable }
+                    | Stmt::SmartUnlock { variable }
+                    | Stmt::SmartKill { variable }
+                    | Stmt::SmartConst { variable }
+                    | Stmt::SmartWatch { variable, .. }
+                    | Stmt::SmartUnwatch { variable, .. }
+                    | Stmt::SmartScoped { variable } => self.read(variable),
+                    Stmt::SmartRevive { variable, generation } => {
+                        self.read(variable);
+                        if let Some(expr) = generation {
+                            self.expr(expr);
+                        }
+                    }
+                    Stmt::Defer { body } => {
+                        self.enter_scope();
+                        self.block(body, Body::Defer);
+                        self.exit_scope();
+                    }
+                    Stmt::Label { _label_ } => {
+                        for item in _label_ {
+                            self.label(item);
+                        }
+                    }
+                    Stmt::Visible { _block_, .. } => {
+                        for (_, expr) in _block_ {
+                            self.expr(expr);
+                        }
+                    }
+                    Stmt::If {
+                        condition,
+                        then_branch,
+                        elif_branches,
+                        else_branch,
+                    } => {
+                        self.expr(condition);
+                        self.block(then_branch, Body::Branch);
+                        for (cond, body) in elif_branches {
+                            self.expr(cond);
+                            self.block(body, Body::Branch);
+                        }
+                        if let Some(body) = else_branch {
+                            self.block(body, Body::Branch);
+                        }
+                    }
+                    Stmt::While { condition, body } | Stmt::DoWhile { body, condition } => {
+                        self.expr(condition);
+                        self.enter_scope();
+                        self.block(body, Body::Loop);
+                        self.exit_scope();
+                    }
+                    Stmt::For {
+                        iterator,
+                        value,
+                        iterable,
+                        body,
+                    } => {
+                        self.expr(iterable);
+                        self.enter_scope();
+                        self.pattern(iterator);
+                        if let Some(value) = value {
+                            self.pattern(value);
+                        }
+                        self.block(body, Body::Loop);
+                        self.exit_scope();
+                    }
+                    Stmt::Record { fields, .. } => {
+                        for (_, default) in fields {
+                            if let Some(expr) = default {
+                                self.expr(expr);
+                            }
+                        }
+                    }
+                    Stmt::Jump { .. } | Stmt::Pass => {}
+                }
+            }
+
+            fn walk_label(&mut self, item: &'a $($mutability)? LabelItem) {
+                let (_, callable, _, _, patterns, body, _) = item;
+                self.enter_scope();
+                for pattern in patterns {
+                    self.pattern(pattern);
+                }
+                self.block(body, if *callable { Body::Label } else { Body::FlowLabel });
+                self.exit_scope();
+            }
+
+            fn walk_expr(&mut self, expr: &'a $($mutability)? Expr) {
+                match expr {
+                    Expr::Array { elements } | Expr::Axis { elements } | Expr::Set { elements } => {
+                        for element in elements {
+                            self.expr(element);
+                        }
+                    }
+                    Expr::Access { ds, member } => {
+                        self.read(ds);
+                        for step in member {
+                            self.accessor(step);
+                        }
+                    }
+                    // Writing inside a value, or updating it, starts from reading it
+                    Expr::AssignAccess { ds, member, val } | Expr::CompoundAssign { ds, member, val, .. } => {
+                        self.read(ds);
+                        for step in member {
+                            self.accessor(step);
+                        }
+                        self.expr(val);
+                    }
+                    Expr::Dictionary(entries) => {
+                        for (key, value) in entries {
+                            self.expr(key);
+                            self.expr(value);
+                        }
+                    }
+                    Expr::Binary { left, right, .. } => {
+                        self.expr(left);
+                        self.expr(right);
+                    }
+                    Expr::Unary { right: inner, .. }
+                    | Expr::Grouping(inner)
+                    | Expr::Print(inner)
+                    | Expr::Propagate(inner) => self.expr(inner),
+                    Expr::MacroCall { params, args, body } => {
+                        for arg in args {
+                            self.expr(arg);
+                        }
+                        self.enter_scope();
+                        for param in params {
+                            self.bind(param);
+                        }
+                        self.block(body, Body::Macro);
+                        self.exit_scope();
+                    }
+                    Expr::_Literal_(_) => {}
+                    Expr::Variable { name } => self.read(name),
+                    Expr::FunctionCall { args, .. } => {
+                        for (_, arg) in args {
+                            self.expr(arg);
+                        }
+                    }
+                    Expr::MethodCall { receiver, args, .. } => {
+                        self.expr(receiver);
+                        for (_, arg) in args {
+                            self.expr(arg);
+                        }
+                    }
+                    Expr::AllocateVariable { name, val } => {
+                        self.assign(name);
+                        self.expr(val);
+                    }
+                    Expr::Destructure { pattern, val } => {
+                        self.pattern(pattern);
+                        self.expr(val);
+                    }
+                    Expr::Match { subject, arms } => {
+                        self.expr(subject);
+                        for MatchArm { pattern, guard, body } in arms {
+                            self.enter_scope();
+                            self.match_pattern(pattern);
+                            if let Some(guard) = guard {
+                                self.expr(guard);
+                            }
+                            self.expr(body);
+                            self.exit_scope();
+                        }
+                    }
+                    Expr::Range { start, end, step, .. } => {
+                        self.expr(start);
+                        self.expr(end);
+                        if let Some(step) = step {
+                            self.expr(step);
+                        }
+                    }
+                }
+            }
+
+            fn accessor(&mut self, step: &'a $($mutability)? Accessor) {
+                match step {
+                    Accessor::Index(index) => self.expr(index),
+                    Accessor::Field(_) => {}
+                    Accessor::Optional(inner) => self.accessor(inner),
+                }
+            }
+
+            fn pattern(&mut self, pattern: &'a $($mutability)? Pattern) {
+                match pattern {
+                    Pattern::Name(name) => self.bind(name),
+                    Pattern::Array { items, rest } => {
+                        for item in items {
+                            self.pattern(item);
+                        }
+                        if let Some(rest) = rest {
+                            self.bind(rest);
+                        }
+                    }
+                    // Keys are looked up as written; only the targets bind
+                    Pattern::Dict { entries, rest } => {
+                        for (_, target) in entries {
+                            self.pattern(target);
+                        }
+                        if let Some(rest) = rest {
+                            self.bind(rest);
+                        }
+                    }
+                }
+            }
+
+            fn match_pattern(&mut self, pattern: &'a $($mutability)? MatchPattern) {
+                match pattern {
+                    MatchPattern::Wildcard | MatchPattern::Type(_) => {}
+                    MatchPattern::Bind(name, inner) => {
+                        self.bind(name);
+                        if let Some(inner) = inner {
+                            self.match_pattern(inner);
+                        }
+                    }
+                    MatchPattern::Literal(expr) | MatchPattern::Range(expr) => self.expr(expr),
+                    MatchPattern::Array { items, rest } => {
+                        for item in items {
+                            self.match_pattern(item);
+                        }
+                        if let Some(rest) = rest {
+                            self.bind(rest);
+                        }
+                    }
+                    MatchPattern::Dict { entries, rest } => {
+                        for (_, target) in entries {
+                            self.match_pattern(target);
+                        }
+                        if let Some(rest) = rest {
+                            self.bind(rest);
+                        }
+                    }
+                    MatchPattern::Alt(alternatives) => {
+                        for alternative in alternatives {
+                            self.match_pattern(alternative);
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+make_visitor!(Visitor);
+make_visitor!(VisitorMut, mut);
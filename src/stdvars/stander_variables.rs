@@ -1,20 +1,28 @@
 use crate::interpreter::error::RuntimeResult;
 use crate::values::values::{Environment, Value};
 
-pub(crate) fn define_std_vars(env: &mut Environment) -> RuntimeResult<()> {
-    // -------------------------------------------------
-    // Language / Runtime
-    // -------------------------------------------------
-    env.define_constant("__VERSION__", Value::String("0.1.0".into()))?;
-    env.define_constant("__LANG__", Value::String("EX".into()))?;
+// Std vars that are already known while parsing, so `_ifdef_ __OS__ == "linux"`
+// can pick a branch before anything runs
+pub(crate) fn compile_time_vars() -> [(&'static str, &'static str); 6] {
+    [
+        // Language / Runtime
+        ("__VERSION__", "0.1.0"),
+        ("__LANG__", "EX"),
+        // OS / Architecture
+        ("__OS__", std::env::consts::OS),
+        ("__ARCH__", std::env::consts::ARCH),
+        ("__FAMILY__", std::env::consts::FAMILY),
+        ("__ABI__", "sysv"),
+    ]
+}
 
+pub(crate) fn define_std_vars(env: &mut Environment) -> RuntimeResult<()> {
     // -------------------------------------------------
-    // OS / Architecture
+    // Language / Runtime, OS / Architecture
     // -------------------------------------------------
-    env.define_constant("__OS__", Value::String(std::env::consts::OS.into()))?;
-    env.define_constant("__ARCH__", Value::String(std::env::consts::ARCH.into()))?;
-    env.define_constant("__FAMILY__", Value::String(std::env::consts::FAMILY.into()))?;
-    env.define_constant("__ABI__", Value::String("sysv".into()))?;
+    for (name, value) in compile_time_vars() {
+        env.define_constant(name, Value::String(value.into()))?;
+    }
 
     // -------------------------------------------------
     // CPU information
@@ -86,6 +94,14 @@ pub(crate) fn define_std_vars(env: &mut Environment) -> RuntimeResult<()> {
     env.define_constant("__FLOAT_MAX__", Value::Float(f64::MAX))?;
     env.define_constant("__FLOAT_MIN__", Value::Float(f64::MIN))?;
 
+    // -------------------------------------------------
+    // Math constants
+    // -------------------------------------------------
+    env.define_constant("PI", Value::Float(std::f64::consts::PI))?;
+    env.define_constant("E", Value::Float(std::f64::consts::E))?;
+    env.define_constant("INF", Value::Float(f64::INFINITY))?;
+    env.define_constant("NAN", Value::Float(f64::NAN))?;
+
     // -------------------------------------------------
     // Primitive numeric types
     // TypeCasting
@@ -0,0 +1,419 @@
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::values::Value;
+use std::rc::Rc;
+
+// Every integer up to 2^53 is exact in the f64 storage, nothing beyond
+const MAX_EXACT: i128 = 1 << 53;
+
+// Homogeneous n-dimensional numeric array, stored row-major.
+// `integral` is true while every element is a whole number that came from an
+// Int (or from integer-preserving arithmetic), so element reads give back Int.
+#[derive(Debug, Clone)]
+pub struct Axis {
+    pub shape: Vec<usize>,
+    pub data: Vec<f64>,
+    pub integral: bool,
+}
+
+impl PartialEq for Axis {
+    fn eq(&self, other: &Self) -> bool {
+        self.shape == other.shape && self.data == other.data
+    }
+}
+
+impl Axis {
+    pub fn new(shape: Vec<usize>, data: Vec<f64>, integral: bool) -> Self {
+        Self { shape, data, integral }
+    }
+
+    // Rank-0 axis holding one number; used to broadcast scalars.
+    pub fn scalar(value: &Value) -> Option<Self> {
+        match value {
+            Value::Int(i) => Some(Self::new(Vec::new(), vec![*i as f64], true)),
+            Value::UInt(u) => Some(Self::new(Vec::new(), vec![*u as f64], true)),
+            Value::Float(f) => Some(Self::new(Vec::new(), vec![*f], false)),
+            _ => None,
+        }
+    }
+
+    // Builds an axis from `[&a, ...]` elements. Elements are either all
+    // numbers (a vector) or all rows of identical shape (one more dimension).
+    pub fn from_values(items: Vec<Value>) -> RuntimeResult<Self> {
+        let count = items.len();
+        if items.iter().all(|v| Self::scalar(v).is_some()) {
+            items.iter().try_for_each(check_exact)?;
+            let integral = items.iter().all(|v| !matches!(v, Value::Float(_)));
+            let data = items.iter().filter_map(Self::scalar).map(|a| a.data[0]).collect();
+            return Ok(Self::new(vec![count], data, integral));
+        }
+
+        let mut rows = Vec::with_capacity(count);
+        for item in items {
+            let row = match item {
+                Value::Axis(ax) => ax,
+                Value::Array(arr) => Self::from_values(Rc::unwrap_or_clone(arr))?,
+                other if Self::scalar(&other).is_some() => {
+                    return Err(RuntimeError::custom(
+                        "Axis elements must be all numbers or all rows, not a mix",
+                    ));
+                }
+                other => {
+                    return Err(RuntimeError::custom(format!(
+                        "Axis elements must be numbers, got {}",
+                        other.type_name()
+                    )));
+                }
+            };
+            if let Some(first) = rows.first() {
+                let first: &Axis = first;
+                if first.shape != row.shape {
+                    return Err(RuntimeError::custom(format!(
+                        "Axis rows must share a shape, got {} and {}",
+                        shape_string(&first.shape),
+                        shape_string(&row.shape)
+                    )));
+                }
+            }
+            rows.push(row);
+        }
+
+        let mut shape = vec![count];
+        shape.extend_from_slice(&rows[0].shape);
+        let integral = rows.iter().all(|r| r.integral);
+        let data = rows.into_iter().flat_map(|r| r.data).collect();
+        Ok(Self::new(shape, data, integral))
+    }
+
+    pub fn rank(&self) -> usize {
+        self.shape.len()
+    }
+
+    // Length of the first dimension.
+    pub fn len(&self) -> usize {
+        self.shape.first().copied().unwrap_or(1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    // Turns a single element back into a Value, honouring `integral`.
+    pub fn number(&self, x: f64) -> Value {
+        if self.integral {
+            Value::Int(x as i128)
+        } else {
+            Value::Float(x)
+        }
+    }
+
+    // Collapses rank-0 results back to a plain number.
+    pub fn into_value(self) -> Value {
+        if self.shape.is_empty() {
+            self.number(self.data[0])
+        } else {
+            Value::Axis(self)
+        }
+    }
+
+    // Indexes along the first dimension: a number for vectors, a row otherwise.
+    pub fn get(&self, idx: usize) -> Value {
+        let inner: usize = self.shape[1..].iter().product();
+        let data = self.data[idx * inner..(idx + 1) * inner].to_vec();
+        Self::new(self.shape[1..].to_vec(), data, self.integral).into_value()
+    }
+
+    pub fn map<F: Fn(f64) -> f64>(&self, f: F, integral: bool) -> Self {
+        Self::new(self.shape.clone(), self.data.iter().map(|x| f(*x)).collect(), integral)
+    }
+
+    // Element-wise binary operation with NumPy-style broadcasting: shapes
+    // are aligned from the right and a dimension of 1 stretches to match.
+    pub fn broadcast<F>(&self, other: &Axis, op: F, keep_integral: bool, op_str: &str) -> RuntimeResult<Axis>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        let rank = self.rank().max(other.rank());
+        let pad = |shape: &[usize]| -> Vec<usize> {
+            let mut full = vec![1; rank - shape.len()];
+            full.extend_from_slice(shape);
+            full
+        };
+        let left = pad(&self.shape);
+        let right = pad(&other.shape);
+
+        let mut shape = Vec::with_capacity(rank);
+        for (l, r) in left.iter().zip(&right) {
+            if l != r && *l != 1 && *r != 1 {
+                return Err(RuntimeError::custom(format!(
+                    "Cannot broadcast Axis shapes {} and {} for '{}'",
+                    shape_string(&self.shape),
+                    shape_string(&other.shape),
+                    op_str
+                )));
+            }
+            shape.push(*l.max(r));
+        }
+
+        let left_strides = broadcast_strides(&left, &shape);
+        let right_strides = broadcast_strides(&right, &shape);
+        let total = element_count(&shape).ok_or_else(|| {
+            RuntimeError::custom(format!("Axis shape {} has too many elements", shape_string(&shape)))
+        })?;
+        let mut data = Vec::with_capacity(total);
+        let mut index = vec![0usize; rank];
+        for _ in 0..total {
+            let l: usize = index.iter().zip(&left_strides).map(|(i, s)| i * s).sum();
+            let r: usize = index.iter().zip(&right_strides).map(|(i, s)| i * s).sum();
+            data.push(op(self.data[l], other.data[r]));
+            step_index(&mut index, &shape);
+        }
+
+        let integral = keep_integral && self.integral && other.integral;
+        Axis::new(shape, data, integral).exact()
+    }
+
+    // Same elements under a new shape; one dimension may be -1 and is inferred.
+    pub fn reshape(&self, dims: &[i128]) -> RuntimeResult<Axis> {
+        let inferred = dims.iter().filter(|d| **d == -1).count();
+        if inferred > 1 || dims.iter().any(|d| *d < -1) {
+            return Err(RuntimeError::custom(format!(
+                "axis_reshape got an invalid shape {:?}",
+                dims
+            )));
+        }
+        let known = dims
+            .iter()
+            .filter(|d| **d != -1)
+            .try_fold(1usize, |acc, d| acc.checked_mul(usize::try_from(*d).ok()?))
+            .ok_or_else(|| RuntimeError::custom(format!("axis_reshape shape {:?} has too many elements", dims)))?;
+
+        let total = self.data.len();
+        let shape: Vec<usize> = dims
+            .iter()
+            .map(|d| {
+                if *d == -1 {
+                    total.checked_div(known).unwrap_or(0)
+                } else {
+                    *d as usize
+                }
+            })
+            .collect();
+
+        if element_count(&shape) != Some(total) {
+            return Err(RuntimeError::custom(format!(
+                "axis_reshape cannot turn shape {} into {:?}",
+                shape_string(&self.shape),
+                dims
+            )));
+        }
+        Ok(Axis::new(shape, self.data.clone(), self.integral))
+    }
+
+    // Reverses the order of the dimensions (a plain transpose for matrices).
+    pub fn transpose(&self) -> Axis {
+        let shape: Vec<usize> = self.shape.iter().rev().copied().collect();
+        let strides: Vec<usize> = strides(&self.shape).into_iter().rev().collect();
+        let mut data = Vec::with_capacity(self.data.len());
+        let mut index = vec![0usize; shape.len()];
+        for _ in 0..self.data.len() {
+            let at: usize = index.iter().zip(&strides).map(|(i, s)| i * s).sum();
+            data.push(self.data[at]);
+            step_index(&mut index, &shape);
+        }
+        Axis::new(shape, data, self.integral)
+    }
+
+    // Elements [start, end) along dimension `dim`.
+    pub fn slice(&self, dim: usize, start: usize, end: usize) -> Axis {
+        let outer: usize = self.shape[..dim].iter().product();
+        let inner: usize = self.shape[dim + 1..].iter().product();
+        let n = self.shape[dim];
+
+        let mut data = Vec::with_capacity(outer * (end - start) * inner);
+        for o in 0..outer {
+            let base = o * n * inner;
+            data.extend_from_slice(&self.data[base + start * inner..base + end * inner]);
+        }
+
+        let mut shape = self.shape.clone();
+        shape[dim] = end - start;
+        Axis::new(shape, data, self.integral)
+    }
+
+    // Folds every lane along `dim` with `f`, dropping that dimension.
+    // Without a dimension the whole axis is folded into one number.
+    pub fn reduce<F>(&self, dim: Option<usize>, f: F, integral: bool) -> Axis
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let dim = match dim {
+            Some(d) => d,
+            None => return Axis::new(Vec::new(), vec![f(&self.data)], integral),
+        };
+
+        let outer: usize = self.shape[..dim].iter().product();
+        let inner: usize = self.shape[dim + 1..].iter().product();
+        let n = self.shape[dim];
+
+        let mut data = Vec::with_capacity(outer * inner);
+        let mut lane = Vec::with_capacity(n);
+        for o in 0..outer {
+            for i in 0..inner {
+                lane.clear();
+                lane.extend((0..n).map(|k| self.data[(o * n + k) * inner + i]));
+                data.push(f(&lane));
+            }
+        }
+
+        let mut shape = self.shape.clone();
+        shape.remove(dim);
+        Axis::new(shape, data, integral)
+    }
+
+    // Matrix product for rank 1 and 2 operands; a vector on the left acts
+    // as a row and a vector on the right acts as a column.
+    pub fn matmul(&self, other: &Axis, fname: &str) -> RuntimeResult<Axis> {
+        if self.rank() == 0 || self.rank() > 2 || other.rank() == 0 || other.rank() > 2 {
+            return Err(RuntimeError::custom(format!(
+                "{} expects vectors or matrices, got shapes {} and {}",
+                fname,
+                shape_string(&self.shape),
+                shape_string(&other.shape)
+            )));
+        }
+
+        let (m, k) = if self.rank() == 1 { (1, self.shape[0]) } else { (self.shape[0], self.shape[1]) };
+        let (k2, n) = if other.rank() == 1 { (other.shape[0], 1) } else { (other.shape[0], other.shape[1]) };
+        if k != k2 {
+            return Err(RuntimeError::custom(format!(
+                "{} shape mismatch: {} and {}",
+                fname,
+                shape_string(&self.shape),
+                shape_string(&other.shape)
+            )));
+        }
+
+        let mut data = vec![0.0; m * n];
+        for i in 0..m {
+            for p in 0..k {
+                let a = self.data[i * k + p];
+                for j in 0..n {
+                    data[i * n + j] += a * other.data[p * n + j];
+                }
+            }
+        }
+
+        let mut shape = Vec::new();
+        if self.rank() == 2 {
+            shape.push(m);
+        }
+        if other.rank() == 2 {
+            shape.push(n);
+        }
+        Axis::new(shape, data, self.integral && other.integral).exact()
+    }
+
+    // Integral results must stay within ±2^53 to read back as exact Ints
+    fn exact(self) -> RuntimeResult<Self> {
+        if self.integral && self.data.iter().any(|x| x.abs() > MAX_EXACT as f64) {
+            return Err(RuntimeError::custom(
+                "Axis integer result exceeds 2^53 and cannot be stored exactly",
+            ));
+        }
+        Ok(self)
+    }
+
+    // Nested-bracket rendering used by print: axis([[1, 2], [3, 4]]).
+    pub fn render(&self) -> String {
+        let mut out = String::from("axis(");
+        if self.shape.is_empty() {
+            out.push_str(&self.render_number(self.data[0]));
+        } else {
+            self.render_dim(0, 0, &mut out);
+        }
+        out.push(')');
+        out
+    }
+
+    fn render_dim(&self, dim: usize, offset: usize, out: &mut String) {
+        let inner: usize = self.shape[dim + 1..].iter().product();
+        out.push('[');
+        for i in 0..self.shape[dim] {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            if dim + 1 == self.shape.len() {
+                out.push_str(&self.render_number(self.data[offset + i]));
+            } else {
+                self.render_dim(dim + 1, offset + i * inner, out);
+            }
+        }
+        out.push(']');
+    }
+
+    fn render_number(&self, x: f64) -> String {
+        if self.integral {
+            format!("{}", x as i128)
+        } else {
+            format!("{}", x)
+        }
+    }
+}
+
+// Rejects integers the f64 storage would round
+pub fn check_exact(value: &Value) -> RuntimeResult<()> {
+    let magnitude = match value {
+        Value::Int(i) => i.unsigned_abs(),
+        Value::UInt(u) => *u,
+        _ => return Ok(()),
+    };
+    if magnitude > MAX_EXACT as u128 {
+        return Err(RuntimeError::custom(format!(
+            "Axis cannot store {} exactly; integers must be within ±2^53",
+            match value {
+                Value::Int(i) => i.to_string(),
+                _ => magnitude.to_string(),
+            }
+        )));
+    }
+    Ok(())
+}
+
+// Number of elements in `shape`, None if it does not fit in memory
+fn element_count(shape: &[usize]) -> Option<usize> {
+    shape.iter().try_fold(1usize, |acc, d| acc.checked_mul(*d))
+}
+
+pub fn shape_string(shape: &[usize]) -> String {
+    let dims: Vec<String> = shape.iter().map(|d| d.to_string()).collect();
+    format!("({})", dims.join(", "))
+}
+
+fn strides(shape: &[usize]) -> Vec<usize> {
+    let mut out = vec![1; shape.len()];
+    for i in (0..shape.len().saturating_sub(1)).rev() {
+        out[i] = out[i + 1] * shape[i + 1];
+    }
+    out
+}
+
+// Strides of `shape` read through the broadcast `target` shape: stretched
+// dimensions get stride 0 so the same element is reused.
+fn broadcast_strides(shape: &[usize], target: &[usize]) -> Vec<usize> {
+    strides(shape)
+        .into_iter()
+        .zip(shape.iter().zip(target))
+        .map(|(s, (d, t))| if d == t { s } else { 0 })
+        .collect()
+}
+
+// Advances a row-major multi-index by one position.
+fn step_index(index: &mut [usize], shape: &[usize]) {
+    for i in (0..index.len()).rev() {
+        index[i] += 1;
+        if index[i] < shape[i] {
+            return;
+        }
+        index[i] = 0;
+    }
+}
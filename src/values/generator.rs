@@ -0,0 +1,140 @@
+use crate::interpreter::iter_mod::LoopIter;
+use crate::parser::ast::{Expr, LabelItem, Pattern, Stmt};
+use crate::parser::visit::{Body, Visitor};
+use crate::values::values::Scope;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+// Where a suspended generator body stands. Only statements that contain a
+// `yield` get a frame; everything else runs straight through `execute`.
+// Bodies are shared, so a loop starts each iteration without copying its
+// statements.
+pub(crate) enum Frame {
+    // Statements of a block and the index of the next one; `scoped` blocks
+    // (loop bodies) close their scope when they finish
+    Block { body: Rc<[Stmt]>, pc: usize, scoped: bool },
+    While { condition: Expr, body: Rc<[Stmt]> },
+    DoWhile { condition: Expr, body: Rc<[Stmt]>, started: bool },
+    For {
+        iterator: Pattern,
+        value: Option<Pattern>,
+        keyed: bool,
+        items: LoopIter,
+        body: Rc<[Stmt]>,
+    },
+}
+
+// Scopes and visible blocks of generators dropped before they finished,
+// waiting for the interpreter to close them
+pub(crate) type Abandoned = Rc<RefCell<Vec<(Vec<Scope>, Vec<String>)>>>;
+
+// State of a running label body between two `yield`s
+pub struct Generator {
+    pub name: String,
+    pub(crate) frames: Vec<Frame>,
+    // The label's local scopes while it is suspended
+    pub(crate) scopes: Vec<Scope>,
+    // Visible blocks the label visits
+    pub(crate) visible_blocks: Vec<String>,
+    // Where the scopes go if the generator is dropped while suspended
+    abandoned: Abandoned,
+    pub(crate) running: bool,
+    pub done: bool,
+}
+
+// Copies of a generator share its state, so advancing one advances all
+#[derive(Clone)]
+pub struct GeneratorRef(pub Rc<RefCell<Generator>>);
+
+impl GeneratorRef {
+    pub(crate) fn new(
+        name: &str,
+        body: Rc<[Stmt]>,
+        scopes: Vec<Scope>,
+        visible_blocks: Vec<String>,
+        abandoned: Abandoned,
+    ) -> Self {
+        GeneratorRef(Rc::new(RefCell::new(Generator {
+            name: name.to_string(),
+            frames: vec![Frame::Block { body, pc: 0, scoped: false }],
+            scopes,
+            visible_blocks,
+            abandoned,
+            running: false,
+            done: false,
+        })))
+    }
+
+    pub fn name(&self) -> String {
+        self.0.borrow().name.clone()
+    }
+}
+
+// Deferred work cannot run here, without the interpreter, so the scopes
+// are handed over for it to close
+impl Drop for Generator {
+    fn drop(&mut self) {
+        if !self.scopes.is_empty() {
+            let scopes = std::mem::take(&mut self.scopes);
+            let visible_blocks = std::mem::take(&mut self.visible_blocks);
+            self.abandoned.borrow_mut().push((scopes, visible_blocks));
+        }
+    }
+}
+
+impl PartialEq for GeneratorRef {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for GeneratorRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Generator({})", self.name())
+    }
+}
+
+// True when `yield` appears in these statements outside of nested labels
+pub(crate) fn contains_yield(body: &[Stmt]) -> bool {
+    let mut finder = YieldFinder::default();
+    finder.stmts(body);
+    finder.found
+}
+
+pub(crate) fn stmt_yields(stmt: &Stmt) -> bool {
+    let mut finder = YieldFinder::default();
+    finder.stmt(stmt);
+    finder.found
+}
+
+// A `yield` the generator's frames can reach: not inside an expression,
+// a callable label or a defer block
+#[derive(Default)]
+struct YieldFinder {
+    found: bool,
+}
+
+impl<'a> Visitor<'a> for YieldFinder {
+    fn stmt(&mut self, stmt: &'a Stmt) {
+        if !self.found {
+            self.found = matches!(stmt, Stmt::Yield { .. });
+            self.walk_stmt(stmt);
+        }
+    }
+
+    fn expr(&mut self, _expr: &'a Expr) {}
+
+    // A control flow label's body runs where it is defined, once jumped to
+    fn label(&mut self, item: &'a LabelItem) {
+        if !item.1 {
+            self.walk_label(item);
+        }
+    }
+
+    fn block(&mut self, body: &'a [Stmt], kind: Body) {
+        if kind != Body::Defer {
+            self.stmts(body);
+        }
+    }
+}
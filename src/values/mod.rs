@@ -1,2 +1,8 @@
+#[allow(clippy::module_inception)]
 pub mod values;
+pub mod axis;
+pub mod set;
+pub mod range;
+pub mod generator;
+#[allow(unused)]
 pub use values::Value;
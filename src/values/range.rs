@@ -0,0 +1,159 @@
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::values::Value;
+
+pub const MAX_EXPANDED: u128 = 10_000_000;
+
+// Lazy integer range: `::[a..b]` includes `b`, `::[a..<b]` stops before it,
+// and `::[a..b:step]` sets the step. Elements are computed on demand, so a
+// range costs the same no matter how many elements it spans.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Range {
+    pub start: i128,
+    pub end: i128,
+    pub step: i128,
+    pub inclusive: bool,
+}
+
+impl Range {
+    // Without a step a range counts towards `end`: up by 1 or down by 1
+    pub fn new(start: i128, end: i128, step: Option<i128>, inclusive: bool) -> RuntimeResult<Self> {
+        let step = step.unwrap_or(if start <= end { 1 } else { -1 });
+        if step == 0 {
+            return Err(RuntimeError::custom("Range step cannot be 0"));
+        }
+        let range = Self { start, end, step, inclusive };
+        // Every i128 by steps of one is the only range whose length does
+        // not fit in a u128
+        if range.count().is_none() {
+            return Err(RuntimeError::custom(format!(
+                "Range {} has more elements than can be counted",
+                range.render()
+            )));
+        }
+        Ok(range)
+    }
+
+    fn count(&self) -> Option<u128> {
+        // Distance still to cover in the direction of the step, in u128 so
+        // that a range over all of i128 does not overflow
+        let forward = if self.step > 0 { self.end >= self.start } else { self.end <= self.start };
+        if !forward {
+            return Some(0);
+        }
+        let span = self.end.abs_diff(self.start);
+        let stride = self.step.unsigned_abs();
+
+        match (span, self.inclusive) {
+            (s, true) => (s / stride).checked_add(1),
+            (0, false) => Some(0),
+            (s, false) => Some((s - 1) / stride + 1),
+        }
+    }
+
+    // Range::new refuses the one range without a count
+    pub fn len(&self) -> u128 {
+        self.count().unwrap_or(u128::MAX)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, idx: u128) -> Option<i128> {
+        (idx < self.len()).then(|| self.nth(idx))
+    }
+
+    // Element at an index that counts from the end when negative
+    pub fn at(&self, idx: i128) -> Option<i128> {
+        let real = match idx {
+            0.. => idx.unsigned_abs(),
+            _ => self.len().checked_sub(idx.unsigned_abs())?,
+        };
+        self.get(real)
+    }
+
+    // Element `idx`, which must be in the range. It lies between start and
+    // end, so wrapping arithmetic lands on it even if `idx * step` alone
+    // would not fit in an i128.
+    fn nth(&self, idx: u128) -> i128 {
+        self.start.wrapping_add((idx as i128).wrapping_mul(self.step))
+    }
+
+    // Index of `value`, if the range holds it
+    pub fn position(&self, value: i128) -> Option<u128> {
+        let ahead = if self.step > 0 { value >= self.start } else { value <= self.start };
+        let offset = value.abs_diff(self.start);
+        let stride = self.step.unsigned_abs();
+        let idx = offset / stride;
+        (ahead && offset.is_multiple_of(stride) && idx < self.len()).then_some(idx)
+    }
+
+    pub fn contains(&self, value: i128) -> bool {
+        self.position(value).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = i128> + use<> {
+        let range = *self;
+        (0..self.len()).map(move |i| range.nth(i))
+    }
+
+    // The elements from `idx` on; empty once `idx` reaches the end
+    pub fn skip(&self, idx: u128) -> Range {
+        match self.get(idx) {
+            Some(start) => Range { start, ..*self },
+            None => Range {
+                start: self.end,
+                inclusive: false,
+                ..*self
+            },
+        }
+    }
+
+    // The same elements, last to first
+    pub fn reversed(&self) -> RuntimeResult<Range> {
+        let len = self.len();
+        let Some(last) = self.at(-1) else {
+            return Ok(*self);
+        };
+        // A single element has no step to turn around
+        let step = match len {
+            1 => 1,
+            _ => self.step.checked_neg().ok_or_else(|| {
+                RuntimeError::custom(format!("Cannot reverse range {}: its step is too large", self.render()))
+            })?,
+        };
+        Ok(Range {
+            start: last,
+            end: self.start,
+            step,
+            inclusive: true,
+        })
+    }
+
+    // The elements as values, for builtins that need an Array. Refused
+    // past MAX_EXPANDED elements rather than running out of memory.
+    pub fn to_values(self, fname: &str) -> RuntimeResult<Vec<Value>> {
+        let len = self.len();
+        if len > MAX_EXPANDED {
+            return Err(RuntimeError::custom(format!(
+                "{} cannot expand {}: {} elements is more than {}",
+                fname,
+                self.render(),
+                len,
+                MAX_EXPANDED
+            )));
+        }
+        Ok(self.iter().map(Value::Int).collect())
+    }
+
+    // ::[1..10], ::[0..<10:2]
+    pub fn render(&self) -> String {
+        let default_step = if self.start <= self.end { 1 } else { -1 };
+        let dots = if self.inclusive { ".." } else { "..<" };
+        if self.step == default_step {
+            format!("::[{}{}{}]", self.start, dots, self.end)
+        } else {
+            format!("::[{}{}{}:{}]", self.start, dots, self.end, self.step)
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::interpreter::error::{RuntimeError, RuntimeResult};
+use crate::values::values::Value;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+// Element of a Set. Only primitive values can be members; keeping them in a
+// BTreeSet gives O(log n) membership and a stable order for printing and `for`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SetKey {
+    Nil,
+    Bool(bool),
+    Int(i128),
+    UInt(u128),
+    Float(FloatKey),
+    BigInt(String),
+    Char(char),
+    String(String),
+}
+
+// f64 wrapper ordered by `total_cmp` so floats (NaN included) can be members.
+#[derive(Debug, Clone, Copy)]
+pub struct FloatKey(pub f64);
+
+impl PartialEq for FloatKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_cmp(&other.0) == Ordering::Equal
+    }
+}
+
+impl Eq for FloatKey {}
+
+impl PartialOrd for FloatKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FloatKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+pub type Set = BTreeSet<SetKey>;
+
+impl SetKey {
+    pub fn from_value(value: Value) -> RuntimeResult<Self> {
+        Ok(match value {
+            Value::Nil => SetKey::Nil,
+            Value::Bool(b) => SetKey::Bool(b),
+            Value::Int(i) => SetKey::Int(i),
+            Value::UInt(u) => SetKey::UInt(u),
+            Value::Float(f) => SetKey::Float(FloatKey(f)),
+            Value::BigInt(s) => SetKey::BigInt(s),
+            Value::Char(c) => SetKey::Char(c),
+            Value::String(s) => SetKey::String(s),
+            other => {
+                return Err(RuntimeError::custom(format!(
+                    "Set elements must be primitive types, got {}",
+                    other.type_name()
+                )));
+            }
+        })
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            SetKey::Nil => Value::Nil,
+            SetKey::Bool(b) => Value::Bool(*b),
+            SetKey::Int(i) => Value::Int(*i),
+            SetKey::UInt(u) => Value::UInt(*u),
+            SetKey::Float(f) => Value::Float(f.0),
+            SetKey::BigInt(s) => Value::BigInt(s.clone()),
+            SetKey::Char(c) => Value::Char(*c),
+            SetKey::String(s) => Value::String(s.clone()),
+        }
+    }
+
+    fn render(&self) -> String {
+        match self {
+            SetKey::Nil => "nil".to_string(),
+            SetKey::Bool(b) => b.to_string(),
+            SetKey::Int(i) => i.to_string(),
+            SetKey::UInt(u) => u.to_string(),
+            SetKey::Float(f) => f.0.to_string(),
+            SetKey::BigInt(s) => s.clone(),
+            SetKey::Char(c) => format!("{:?}", c),
+            SetKey::String(s) => format!("{:?}", s),
+        }
+    }
+}
+
+// Sorted rendering used by print: set{1, 2, "x"}.
+pub fn render_set(set: &Set) -> String {
+    let items: Vec<String> = set.iter().map(SetKey::render).collect();
+    format!("set{{{}}}", items.join(", "))
+}
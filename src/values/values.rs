@@ -1,14 +1,21 @@
 use crate::interpreter::error::{RuntimeError, RuntimeErrorKind, RuntimeResult};
-use crate::parser::ast::Stmt;
+use crate::parser::ast::{Expr, Pattern, Stmt};
+use crate::values::axis::Axis;
+use crate::values::generator::GeneratorRef;
+use crate::values::range::Range;
+use crate::values::set::Set;
 use std::collections::HashMap;
+use std::rc::Rc;
 
 #[derive(Debug, Clone)]
 pub struct Function {
     pub name: String,
     pub params: Vec<String>,
-    pub defaults: Vec<String>,
-    pub body: Vec<Stmt>,
+    pub defaults: Vec<Pattern>,
+    pub body: Rc<[Stmt]>,
     pub visible_blocks: Vec<String>,
+    // The body contains `yield`: calling it returns a generator
+    pub generator: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -17,6 +24,29 @@ pub struct ControlFlow {
     pub body: Vec<Stmt>,
 }
 
+// Declared shape of a record: field names in order, with optional defaults
+#[derive(Debug, Clone)]
+pub struct RecordType {
+    pub name: String,
+    pub fields: Vec<(String, Option<Expr>)>,
+}
+
+// Instance of a record; equality compares type name and every field
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub name: String,
+    pub fields: Vec<(String, Value)>,
+}
+
+impl Record {
+    pub fn get(&self, field: &str) -> Option<&Value> {
+        self.fields.iter().find(|(f, _)| f == field).map(|(_, v)| v)
+    }
+
+    pub fn get_mut(&mut self, field: &str) -> Option<&mut Value> {
+        self.fields.iter_mut().find(|(f, _)| f == field).map(|(_, v)| v)
+    }
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
@@ -30,9 +60,15 @@ pub enum Value {
     Nil,
     Function(Function),
     ControlFlow(ControlFlow),
-    Array(Vec<Value>),
-    Dictionary(HashMap<String, Value>),
-    Axis(Vec<Value>)
+    // Collections share their storage and copy on write (Rc::make_mut)
+    Array(Rc<Vec<Value>>),
+    Dictionary(Rc<HashMap<String, Value>>),
+    Axis(Axis),
+    Set(Rc<Set>),
+    Range(Range),
+    Generator(GeneratorRef),
+    RecordType(RecordType),
+    Record(Record)
 }
 
 impl PartialEq for Function {
@@ -47,8 +83,26 @@ impl PartialEq for ControlFlow {
     }
 }
 
+impl PartialEq for RecordType {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
 
 impl Value {
+    pub fn array(items: Vec<Value>) -> Value {
+        Value::Array(Rc::new(items))
+    }
+
+    pub fn dict(map: HashMap<String, Value>) -> Value {
+        Value::Dictionary(Rc::new(map))
+    }
+
+    pub fn set(set: Set) -> Value {
+        Value::Set(Rc::new(set))
+    }
+
     pub fn truthy(&self) -> bool {
         match self {
             Value::Nil => false,
@@ -63,7 +117,12 @@ impl Value {
             Value::ControlFlow(_) => true,
             Value::Array(arr) => !arr.is_empty(),
             Value::Dictionary(dict) => !dict.is_empty(),
-            Value::Axis(ax) => !ax.is_empty()
+            Value::Axis(ax) => !ax.is_empty(),
+            Value::Set(set) => !set.is_empty(),
+            Value::Range(range) => !range.is_empty(),
+            Value::Generator(_) => true,
+            Value::RecordType(_) => true,
+            Value::Record(_) => true
         }
     }
 
@@ -81,75 +140,146 @@ impl Value {
             Value::ControlFlow(_) => "ControlFlow",
             Value::Array(_) => "Array",
             Value::Dictionary(_) => "Dictionary",
-            Value::Axis(_) => "Axis"
+            Value::Axis(_) => "Axis",
+            Value::Set(_) => "Set",
+            Value::Range(_) => "Range",
+            Value::Generator(_) => "Generator",
+            Value::RecordType(_) => "RecordType",
+            Value::Record(rec) => &rec.name
         }
     }
 }
 
 #[derive(Debug, Clone)]
-struct Binding {
+pub struct Binding {
     value: Value,
     is_constant: bool,
     smart_lock: bool,
+    // `_kill_` leaves a tombstone so `_revive_` can tell the name was killed;
+    // lookups skip killed bindings
+    killed: bool,
+    // What each `_kill_` took away, oldest first
+    history: Vec<Generation>,
+    // Labels run by `_watch_` before each change
+    watchers: Vec<String>,
+}
+
+// A killed value and whether it was locked. Constants cannot be killed.
+#[derive(Debug, Clone)]
+pub struct Generation {
+    pub value: Value,
+    pub smart_lock: bool,
+}
+
+impl Binding {
+    fn new(value: Value) -> Self {
+        Self {
+            value,
+            is_constant: false,
+            smart_lock: false,
+            killed: false,
+            history: Vec::new(),
+            watchers: Vec::new(),
+        }
+    }
+
+    // Error for an assignment this binding does not allow
+    fn check_writable(&self, name: &str) -> RuntimeResult<()> {
+        if self.is_constant {
+            return Err(RuntimeError::cannot_reassign_constant(name));
+        }
+        if self.smart_lock {
+            return Err(RuntimeError::cannot_reassign_smart_locked(name));
+        }
+        Ok(())
+    }
+}
+
+// Work queued on a scope, run in reverse order when the scope closes
+#[derive(Debug, Clone)]
+pub enum Deferred {
+    // defer { ... }
+    Block(Vec<Stmt>),
+    // _scoped_ name
+    Kill(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    bindings: HashMap<String, Binding>,
+    deferred: Vec<Deferred>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Environment {
-    scopes: Vec<HashMap<String, Binding>>,
+    scopes: Vec<Scope>,
 }
 
 impl Environment {
     pub fn new() -> Self {
         Self {
-            scopes: vec![HashMap::new()],
+            scopes: vec![Scope::default()],
         }
     }
 
+    // The live binding `name` resolves to, innermost scope first
+    fn resolve(&self, name: &str) -> Option<&Binding> {
+        self.scopes
+            .iter()
+            .rev()
+            .filter_map(|scope| scope.bindings.get(name))
+            .find(|binding| !binding.killed)
+    }
+
+    fn resolve_mut(&mut self, name: &str) -> Option<&mut Binding> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .filter_map(|scope| scope.bindings.get_mut(name))
+            .find(|binding| !binding.killed)
+    }
+
     pub fn exists(&self, name: &str) -> bool {
-        for scope in self.scopes.iter().rev() {
-            if scope.contains_key(name) {
-                return true;
-            }
-        }
-        false
+        self.resolve(name).is_some()
     }
 
     pub fn define(&mut self, name: &str, value: Value) -> RuntimeResult<()> {
-        for scope in self.scopes.iter_mut().rev() {
-            if let Some(binding) = scope.get_mut(name) {
-                if binding.is_constant {
-                    return Err(RuntimeError::cannot_reassign_constant(name));
-                }
-                if binding.smart_lock {
-                    return Err(RuntimeError::cannot_reassign_smart_locked(name));
-                }
-                binding.value = value;
-                return Ok(());
-            }
+        if let Some(binding) = self.resolve_mut(name) {
+            binding.check_writable(name)?;
+            binding.value = value;
+            return Ok(());
         }
 
-        if let Some(scope) = self.scopes.last_mut() {
-            scope.insert(
-                name.to_string(),
-                Binding {
-                    value,
-                    is_constant: false,
-                    smart_lock: false,
-                },
-            );
+        // Assigning a killed name brings its tombstone back, keeping the
+        // history and watchers in the scope it was killed in
+        if let Some(dead) = self.nearest_mut(name) {
+            *dead = Binding {
+                history: std::mem::take(&mut dead.history),
+                watchers: std::mem::take(&mut dead.watchers),
+                ..Binding::new(value)
+            };
+            return Ok(());
         }
 
+        self.define_local(name, value);
         Ok(())
     }
 
+    // Binds `name` in the innermost scope, shadowing any outer binding
+    pub fn define_local(&mut self, name: &str, value: Value) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.bindings.insert(name.to_string(), Binding::new(value));
+        }
+    }
+
+    // Declares a new constant in the innermost scope (std vars)
     pub fn define_constant(&mut self, name: &str, value: Value) -> RuntimeResult<()> {
         if let Some(scope) = self.scopes.last_mut() {
-            scope.insert(
+            scope.bindings.insert(
                 name.to_string(),
                 Binding {
-                    value,
                     is_constant: true,
-                    smart_lock: false,
+                    ..Binding::new(value)
                 },
             );
         }
@@ -157,68 +287,202 @@ impl Environment {
         Ok(())
     }
 
-    pub fn define_smart_lock(&mut self, name: &str, value: Value) -> RuntimeResult<()> {
-        if let Some(scope) = self.scopes.last_mut() {
-            scope.insert(
-                name.to_string(),
-                Binding {
-                    value,
-                    is_constant: false,
-                    smart_lock: true,
-                },
-            );
+    // _lock_ name: the binding `name` resolves to, wherever it lives
+    pub fn lock(&mut self, name: &str) -> RuntimeResult<()> {
+        let binding = self.resolve_mut(name).ok_or_else(|| RuntimeError::undefined_variable(name))?;
+        let failed = |msg: &str| RuntimeError::new(RuntimeErrorKind::SmartLockFailed(format!("'{}' {}", name, msg)));
+        if binding.is_constant {
+            return Err(failed("is a constant"));
         }
-
+        if binding.smart_lock {
+            return Err(failed("is already locked"));
+        }
+        binding.smart_lock = true;
         Ok(())
     }
 
-    pub fn define_smart_unclock(&mut self, name: &str, value: Value) -> RuntimeResult<()> {
-        if let Some(scope) = self.scopes.last_mut() {
-            scope.remove(name);
+    pub fn unlock(&mut self, name: &str) -> RuntimeResult<()> {
+        let binding = self.resolve_mut(name).ok_or_else(|| RuntimeError::undefined_variable(name))?;
+        let failed = |msg: &str| RuntimeError::new(RuntimeErrorKind::SmartUnlockFailed(format!("'{}' {}", name, msg)));
+        if binding.is_constant {
+            return Err(failed("is a constant and cannot be unlocked"));
+        }
+        if !binding.smart_lock {
+            return Err(failed("is not locked"));
+        }
+        binding.smart_lock = false;
+        Ok(())
+    }
 
-            scope.insert(
-                name.to_string(),
-                Binding {
-                    value,
-                    is_constant: false,
-                    smart_lock: false,
-                },
-            );
+    // _const_ name: a locked variable may still become constant
+    pub fn make_constant(&mut self, name: &str) -> RuntimeResult<()> {
+        let binding = self.resolve_mut(name).ok_or_else(|| RuntimeError::undefined_variable(name))?;
+        if binding.is_constant {
+            return Err(RuntimeError::new(RuntimeErrorKind::SmartConstFailed(format!(
+                "'{}' is already a constant",
+                name
+            ))));
         }
+        binding.is_constant = true;
+        binding.smart_lock = false;
         Ok(())
     }
 
     pub fn get(&self, name: &str) -> RuntimeResult<Value> {
-        for scope in self.scopes.iter().rev() {
-            if let Some(binding) = scope.get(name) {
-                return Ok(binding.value.clone());
-            }
+        self.resolve(name)
+            .map(|binding| binding.value.clone())
+            .ok_or_else(|| RuntimeError::undefined_variable(name))
+    }
+
+    // Moves a variable's value out for in-place mutation, leaving Nil behind.
+    // Fails like `define` would when the binding is constant or locked.
+    pub fn take(&mut self, name: &str) -> RuntimeResult<Value> {
+        let binding = self.resolve_mut(name).ok_or_else(|| RuntimeError::undefined_variable(name))?;
+        binding.check_writable(name)?;
+        Ok(std::mem::replace(&mut binding.value, Value::Nil))
+    }
+
+    // Puts back a value previously moved out with `take`
+    pub fn restore(&mut self, name: &str, value: Value) {
+        if let Some(binding) = self.resolve_mut(name) {
+            binding.value = value;
         }
-        Err(RuntimeError::undefined_variable(name))
     }
 
+    pub fn is_constant(&self, name: &str) -> bool {
+        self.resolve(name).is_some_and(|b| b.is_constant)
+    }
+
+    // True when `name` exists and may be reassigned
+    pub fn is_mutable(&self, name: &str) -> bool {
+        self.resolve(name).is_some_and(|b| !b.is_constant && !b.smart_lock)
+    }
+
+    // _kill_ name: the binding stays behind as a tombstone. A locked value
+    // may be killed; reviving it locks it again.
     pub fn delete_variable(&mut self, name: &str) -> RuntimeResult<()> {
-        for scope in self.scopes.iter_mut().rev() {
-            if let Some(binding) = scope.get(name) {
-                if binding.is_constant {
-                    return Err(RuntimeError::cannot_delete_constant(name));
-                }
-                if binding.smart_lock {
-                    return Err(RuntimeError::cannot_delete_smart_locked(name));
-                }
+        let Some(binding) = self.resolve_mut(name) else {
+            return Err(RuntimeError::new(RuntimeErrorKind::CannotDeleteUndefined(
+                name.to_string(),
+            )));
+        };
+        if binding.is_constant {
+            return Err(RuntimeError::cannot_delete_constant(name));
+        }
+
+        binding.history.push(Generation {
+            value: std::mem::replace(&mut binding.value, Value::Nil),
+            smart_lock: std::mem::take(&mut binding.smart_lock),
+        });
+        binding.killed = true;
+        Ok(())
+    }
+
+    // The generation `_revive_ name @ n` would restore: the nth most recent
+    // kill, 0 being the last one
+    pub fn generation(&self, name: &str, generation: usize) -> RuntimeResult<Generation> {
+        let failed = |msg: String| RuntimeError::new(RuntimeErrorKind::SmartReviveFailed(format!("'{}' {}", name, msg)));
+        let Some(binding) = self.scopes.iter().rev().find_map(|scope| scope.bindings.get(name)) else {
+            return Err(failed("was never killed".to_string()));
+        };
+        if !binding.killed {
+            return Err(failed("is still alive".to_string()));
+        }
 
-                scope.remove(name);
-                return Ok(());
+        let count = binding.history.len();
+        count
+            .checked_sub(generation + 1)
+            .map(|i| binding.history[i].clone())
+            .ok_or_else(|| failed(format!("has {} killed generation(s), cannot revive @ {}", count, generation)))
+    }
+
+    // _revive_ name @ n: restores the value and lock of that generation.
+    // The history is kept, so an older generation can still be revived
+    // after the next kill.
+    pub fn revive(&mut self, name: &str, generation: usize) -> RuntimeResult<()> {
+        let snapshot = self.generation(name, generation)?;
+        let Some(binding) = self.nearest_mut(name) else {
+            return Ok(());
+        };
+        binding.value = snapshot.value;
+        binding.smart_lock = snapshot.smart_lock;
+        binding.killed = false;
+        Ok(())
+    }
+
+    // Killed generations of `name`, most recent first
+    pub fn history(&self, name: &str) -> RuntimeResult<Vec<Generation>> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.bindings.get(name))
+            .map(|binding| binding.history.iter().rev().cloned().collect())
+            .ok_or_else(|| RuntimeError::undefined_variable(name))
+    }
+
+    // _watch_ name -> label
+    pub fn watch(&mut self, name: &str, label: &str) -> RuntimeResult<()> {
+        let binding = self.resolve_mut(name).ok_or_else(|| RuntimeError::undefined_variable(name))?;
+        if binding.watchers.iter().any(|w| w == label) {
+            return Err(RuntimeError::custom(format!("'{}' is already watched by '{}'", name, label)));
+        }
+        binding.watchers.push(label.to_string());
+        Ok(())
+    }
+
+    // _unwatch_ name [-> label]: drops one watcher, or all of them
+    pub fn unwatch(&mut self, name: &str, label: Option<&str>) -> RuntimeResult<()> {
+        let binding = self.resolve_mut(name).ok_or_else(|| RuntimeError::undefined_variable(name))?;
+        match label {
+            Some(label) => {
+                let before = binding.watchers.len();
+                binding.watchers.retain(|w| w != label);
+                if binding.watchers.len() == before {
+                    return Err(RuntimeError::custom(format!("'{}' is not watched by '{}'", name, label)));
+                }
             }
+            None if binding.watchers.is_empty() => {
+                return Err(RuntimeError::custom(format!("'{}' is not watched", name)));
+            }
+            None => binding.watchers.clear(),
         }
+        Ok(())
+    }
 
-        Err(RuntimeError::new(RuntimeErrorKind::CannotDeleteUndefined(
-            name.to_string(),
-        )))
+    // Watchers of the binding a change to `name` would reach: the live one,
+    // or else the tombstone `_revive_` and `define` bring back
+    pub fn watchers(&self, name: &str) -> Vec<String> {
+        self.resolve(name)
+            .or_else(|| self.scopes.iter().rev().find_map(|scope| scope.bindings.get(name)))
+            .map(|binding| binding.watchers.clone())
+            .unwrap_or_default()
+    }
+
+    // The nearest binding of `name`, killed or not
+    fn nearest_mut(&mut self, name: &str) -> Option<&mut Binding> {
+        self.scopes.iter_mut().rev().find_map(|scope| scope.bindings.get_mut(name))
+    }
+
+    // Queues work for when the innermost scope closes
+    pub fn defer(&mut self, deferred: Deferred) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.deferred.push(deferred);
+        }
+    }
+
+    // The innermost scope's queued work, most recently queued first
+    pub fn take_deferred(&mut self) -> Vec<Deferred> {
+        let mut deferred = self
+            .scopes
+            .last_mut()
+            .map(|scope| std::mem::take(&mut scope.deferred))
+            .unwrap_or_default();
+        deferred.reverse();
+        deferred
     }
 
     pub fn push_scope(&mut self) {
-        self.scopes.push(HashMap::new());
+        self.scopes.push(Scope::default());
     }
 
     pub fn pop_scope(&mut self) {
@@ -226,4 +490,23 @@ impl Environment {
             self.scopes.pop();
         }
     }
+
+    pub fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    // Removes and returns the scopes above `depth` (a suspended generator's)
+    pub fn split_off(&mut self, depth: usize) -> Vec<Scope> {
+        self.scopes.split_off(depth.clamp(1, self.scopes.len()))
+    }
+
+    // Puts scopes saved with `split_off` back on top
+    pub fn extend(&mut self, scopes: Vec<Scope>) {
+        self.scopes.extend(scopes);
+    }
+
+    // Drops scopes left open by a body that stopped early
+    pub fn unwind_to(&mut self, depth: usize) {
+        self.scopes.truncate(depth.max(1));
+    }
 }
\ No newline at end of file